@@ -0,0 +1,945 @@
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::ErrorKind;
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::hooks::{self, HookEvent, HookSubject};
+use crate::reports;
+use crate::tasks::{self, Task};
+use crate::timer::{self, AppState, IntervalType, TimerAction};
+
+// The control API speaks newline-delimited JSON over a TCP socket bound to
+// 127.0.0.1. Each request line is
+//   {"id": 1, "token": "...", "method": "timer.status", "params": {...}}
+// and is answered by one line
+//   {"id": 1, "ok": true, "result": ...}   or   {"id": 1, "ok": false, "error": "..."}
+// After a `subscribe` request the connection also receives timer events as
+//   {"event": "timer-tick", "payload": {...}}
+
+const DEFAULT_PORT: u16 = 27183;
+/// Longest request line accepted before the connection is dropped.
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+/// Events buffered for subscribers before a lagging one skips the oldest.
+const EVENT_BUFFER: usize = 256;
+/// Lines queued for a connection's writer. Events that don't fit are
+/// dropped for that subscriber, so a client that stops reading can't grow
+/// the queue without bound.
+const OUTGOING_BUFFER: usize = 64;
+/// App events relayed to subscribers, with the payloads the webview sees.
+const FORWARDED_EVENTS: [&str; 3] = ["timer-state-changed", "timer-tick", "timer-complete"];
+/// Written next to the database so scripts can find the port and token.
+const CONNECTION_FILE: &str = "api.json";
+/// How long a restart waits for the previous listener to release its port.
+const REBIND_ATTEMPTS: u32 = 20;
+const REBIND_DELAY: Duration = Duration::from_millis(25);
+
+// ── Types ────────────────────────────────────────────────────
+
+/// The outcome of the last (re)start, for the settings UI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ApiReport {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: Option<u16>,
+    pub token: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ApiEvent {
+    event: &'static str,
+    payload: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    #[serde(default)]
+    token: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    id: Value,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Response {
+    fn from_result(id: Value, result: Result<Value, String>) -> Self {
+        match result {
+            Ok(value) => Self {
+                id,
+                ok: true,
+                result: Some(value),
+                error: None,
+            },
+            Err(e) => Self {
+                id,
+                ok: false,
+                result: None,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+/// Running server handle and the event fan-out shared by all connections.
+pub struct ApiServer {
+    report: Mutex<ApiReport>,
+    /// Dropping the sender stops the listener and all open connections.
+    shutdown: Mutex<Option<watch::Sender<()>>>,
+    events: broadcast::Sender<ApiEvent>,
+}
+
+// ── Settings ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ApiSettings {
+    enabled: bool,
+    port: u16,
+    token: String,
+}
+
+impl ApiSettings {
+    fn load(conn: &Connection) -> Self {
+        let read = |key: &str| {
+            conn.query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                [key],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        };
+
+        Self {
+            enabled: read("api_enabled").as_deref() == Some("true"),
+            port: read("api_port")
+                .and_then(|v| v.parse::<u16>().ok())
+                .unwrap_or(DEFAULT_PORT),
+            token: read("api_token").unwrap_or_default().trim().to_string(),
+        }
+    }
+}
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+fn generate_token() -> Result<String, String> {
+    use std::fmt::Write;
+
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate token: {e}"))?;
+    Ok(bytes.iter().fold(String::with_capacity(32), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    }))
+}
+
+fn store_token(conn: &Connection, token: &str) -> Result<(), String> {
+    conn.execute(
+        "UPDATE user_settings SET value = ?1 WHERE key = 'api_token'",
+        [token],
+    )
+    .map_err(|e| format!("Failed to save API token: {e}"))?;
+    Ok(())
+}
+
+/// Compare without short-circuiting so response timing does not reveal
+/// how much of a guessed token was right.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn write_connection_file(path: &Path, port: u16, token: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", json!({ "port": port, "token": token }))
+}
+
+// ── Requests ────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct StartParams {
+    interval_type: Option<IntervalType>,
+}
+
+#[derive(Deserialize)]
+struct ExtendParams {
+    seconds: u32,
+}
+
+#[derive(Deserialize)]
+struct DayParams {
+    day_date: String,
+}
+
+#[derive(Deserialize)]
+struct DailyReportParams {
+    day_date: String,
+    #[serde(default)]
+    include_cancelled: bool,
+}
+
+#[derive(Deserialize)]
+struct IdParams {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct CreateTaskParams {
+    title: String,
+    day_date: String,
+    parent_task_id: Option<i64>,
+    jira_key: Option<String>,
+    tag: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdateTaskParams {
+    id: i64,
+    title: Option<String>,
+    jira_key: Option<String>,
+    tag: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CompleteTaskParams {
+    id: i64,
+    pomodoro_number: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct WeekParams {
+    week_start: String,
+}
+
+#[derive(Deserialize)]
+struct MonthParams {
+    month_start: String,
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, String> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| format!("Invalid params: {e}"))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("Failed to serialize result: {e}"))
+}
+
+fn control<R: Runtime>(app: &AppHandle<R>, action: TimerAction) -> Result<Value, String> {
+    to_value(timer::dispatch_action(app, action)?)
+}
+
+/// Answer with a task this request changed, letting the webview know.
+fn changed_task<R: Runtime>(app: &AppHandle<R>, task: Task) -> Result<Value, String> {
+    tasks::emit_changed(app, &task.day_date);
+    to_value(task)
+}
+
+/// Run one API method. Timer control goes through the same path as the
+/// tray and shortcuts, so the webview sees every change.
+fn handle_request<R: Runtime>(
+    app: &AppHandle<R>,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    let state = app.state::<AppState>();
+    let db_path = &state.db_path;
+
+    match method {
+        "timer.status" => {
+            let timer = state.timer.lock().map_err(|e| format!("Lock error: {e}"))?;
+            to_value(timer.status())
+        }
+        "timer.start" => {
+            let p: StartParams = parse(params)?;
+            control(
+                app,
+                p.interval_type
+                    .map_or(TimerAction::StartNext, TimerAction::Start),
+            )
+        }
+        "timer.pause" => control(app, TimerAction::Pause),
+        "timer.resume" => control(app, TimerAction::Resume),
+        "timer.toggle" => control(app, TimerAction::Toggle),
+        "timer.skip" => control(app, TimerAction::Skip),
+        "timer.cancel" => control(app, TimerAction::Cancel),
+        "timer.extend" => {
+            let p: ExtendParams = parse(params)?;
+            control(app, TimerAction::Extend(p.seconds))
+        }
+        "tasks.list" => {
+            let p: DayParams = parse(params)?;
+            to_value(tasks::list_by_date(&open_db(db_path)?, &p.day_date)?)
+        }
+        "tasks.create" => {
+            let p: CreateTaskParams = parse(params)?;
+            let task = tasks::create(
+                &open_db(db_path)?,
+                &p.title,
+                &p.day_date,
+                p.parent_task_id,
+                p.jira_key.as_deref(),
+                p.tag.as_deref(),
+            )?;
+            changed_task(app, task)
+        }
+        "tasks.update" => {
+            let p: UpdateTaskParams = parse(params)?;
+            let task = tasks::update(
+                &open_db(db_path)?,
+                p.id,
+                p.title.as_deref(),
+                p.jira_key.as_deref(),
+                p.tag.as_deref(),
+            )?;
+            changed_task(app, task)
+        }
+        "tasks.delete" => {
+            let p: IdParams = parse(params)?;
+            let task = tasks::delete(&open_db(db_path)?, p.id)?;
+            tasks::emit_changed(app, &task.day_date);
+            Ok(Value::Null)
+        }
+        "tasks.complete" => {
+            let p: CompleteTaskParams = parse(params)?;
+            let task = tasks::complete(&open_db(db_path)?, p.id, p.pomodoro_number)?;
+            hooks::fire(
+                db_path,
+                HookEvent::TaskCompleted,
+                HookSubject::Task(Box::new(task.clone())),
+            );
+            changed_task(app, task)
+        }
+        "tasks.abandon" => {
+            let p: IdParams = parse(params)?;
+            changed_task(app, tasks::abandon(&open_db(db_path)?, p.id)?)
+        }
+        "tasks.reopen" => {
+            let p: IdParams = parse(params)?;
+            changed_task(app, tasks::reopen(&open_db(db_path)?, p.id)?)
+        }
+        "reports.daily" => {
+            let p: DailyReportParams = parse(params)?;
+            to_value(reports::daily_summary(
+                &open_db(db_path)?,
+                &p.day_date,
+                p.include_cancelled,
+            )?)
+        }
+        "reports.weekly" => {
+            let p: WeekParams = parse(params)?;
+            to_value(reports::weekly_summary(&open_db(db_path)?, &p.week_start)?)
+        }
+        "reports.monthly" => {
+            let p: MonthParams = parse(params)?;
+            to_value(reports::monthly_summary(
+                &open_db(db_path)?,
+                &p.month_start,
+            )?)
+        }
+        other => Err(format!("Unknown method '{other}'")),
+    }
+}
+
+// ── Connections ─────────────────────────────────────────────
+
+fn encode<T: Serialize>(message: &T) -> String {
+    let mut line = serde_json::to_string(message).unwrap_or_else(|e| {
+        json!({ "ok": false, "error": format!("Failed to serialize: {e}") }).to_string()
+    });
+    line.push('\n');
+    line
+}
+
+/// Relay app events to one subscribed connection until it goes away.
+fn spawn_forwarder(
+    mut events: broadcast::Receiver<ApiEvent>,
+    out: mpsc::Sender<String>,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => match out.try_send(encode(&event)) {
+                    Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => {}
+                    Err(mpsc::error::TrySendError::Closed(_)) => break,
+                },
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+async fn serve_connection<R: Runtime>(
+    app: AppHandle<R>,
+    stream: TcpStream,
+    token: Arc<str>,
+    mut shutdown: watch::Receiver<()>,
+) {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut reader = BufReader::new(read_half);
+    let (out, mut outgoing) = mpsc::channel::<String>(OUTGOING_BUFFER);
+    let writer = tauri::async_runtime::spawn(async move {
+        while let Some(line) = outgoing.recv().await {
+            if write_half.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+    let mut forwarder = None;
+
+    loop {
+        let mut line = String::new();
+        let mut limited = (&mut reader).take(MAX_REQUEST_BYTES);
+        let read = tokio::select! {
+            read = limited.read_line(&mut line) => read,
+            _ = shutdown.changed() => break,
+        };
+        match read {
+            Ok(0) | Err(_) => break,
+            Ok(_) if !line.ends_with('\n') && line.len() as u64 >= MAX_REQUEST_BYTES => {
+                let _ = out
+                    .send(encode(&Response::from_result(
+                        Value::Null,
+                        Err("Request too large".into()),
+                    )))
+                    .await;
+                break;
+            }
+            Ok(_) => {}
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(line) {
+            Err(e) => Response::from_result(Value::Null, Err(format!("Invalid request: {e}"))),
+            Ok(request) if !token_matches(&request.token, &token) => {
+                Response::from_result(request.id, Err("Invalid token".into()))
+            }
+            Ok(request) if request.method == "subscribe" => {
+                if forwarder.is_none() {
+                    let events = app.state::<ApiServer>().events.subscribe();
+                    forwarder = Some(spawn_forwarder(events, out.clone()));
+                }
+                Response::from_result(request.id, Ok(json!({ "events": FORWARDED_EVENTS })))
+            }
+            Ok(request) => {
+                // Requests touch SQLite and the timer lock; keep them off
+                // the async workers
+                let handle = app.clone();
+                let Request {
+                    id, method, params, ..
+                } = request;
+                let result = tauri::async_runtime::spawn_blocking(move || {
+                    handle_request(&handle, &method, params)
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Request failed: {e}")));
+                Response::from_result(id, result)
+            }
+        };
+        if out.send(encode(&response)).await.is_err() {
+            break;
+        }
+    }
+
+    if let Some(forwarder) = forwarder {
+        forwarder.abort();
+    }
+    drop(out);
+    let _ = writer.await;
+}
+
+async fn accept_loop<R: Runtime>(
+    app: AppHandle<R>,
+    listener: TcpListener,
+    token: Arc<str>,
+    mut shutdown: watch::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(serve_connection(
+                        app.clone(),
+                        stream,
+                        Arc::clone(&token),
+                        shutdown.clone(),
+                    ));
+                }
+                Err(e) => {
+                    let _ = app.emit(
+                        "api-error",
+                        format!("Control API failed to accept a connection: {e}"),
+                    );
+                }
+            },
+            _ = shutdown.changed() => break,
+        }
+    }
+}
+
+// ── Lifecycle ───────────────────────────────────────────────
+
+fn bind(port: u16) -> Result<std::net::TcpListener, String> {
+    let mut attempt = 0;
+    loop {
+        match std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+            Ok(listener) => return Ok(listener),
+            // A restart on the same port races the old listener shutting down
+            Err(e) if e.kind() == ErrorKind::AddrInUse && attempt < REBIND_ATTEMPTS => {
+                attempt += 1;
+                std::thread::sleep(REBIND_DELAY);
+            }
+            Err(e) => return Err(format!("Failed to listen on 127.0.0.1:{port}: {e}")),
+        }
+    }
+}
+
+fn start<R: Runtime>(app: &AppHandle<R>, server: &ApiServer) -> Result<ApiReport, String> {
+    let db_path = app.state::<AppState>().db_path.clone();
+    let conn = open_db(&db_path)?;
+    let settings = ApiSettings::load(&conn);
+    if !settings.enabled {
+        return Ok(ApiReport::default());
+    }
+
+    let token = if settings.token.is_empty() {
+        let token = generate_token()?;
+        store_token(&conn, &token)?;
+        token
+    } else {
+        settings.token
+    };
+
+    let listener = bind(settings.port)?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure listener: {e}"))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read listener address: {e}"))?
+        .port();
+
+    let listener = {
+        // Tokio adopts the socket into whichever runtime is entered
+        let _runtime = tauri::async_runtime::handle().inner().enter();
+        TcpListener::from_std(listener).map_err(|e| format!("Failed to start listener: {e}"))?
+    };
+
+    // The server still runs; only scripts that read the file miss out
+    let error = write_connection_file(&db_path.with_file_name(CONNECTION_FILE), port, &token)
+        .err()
+        .map(|e| format!("Failed to write connection file: {e}"));
+
+    let (stop, stopped) = watch::channel(());
+    let shared_token: Arc<str> = Arc::from(token.as_str());
+    tauri::async_runtime::spawn(accept_loop(app.clone(), listener, shared_token, stopped));
+    if let Ok(mut shutdown) = server.shutdown.lock() {
+        *shutdown = Some(stop);
+    }
+
+    Ok(ApiReport {
+        enabled: true,
+        running: true,
+        port: Some(port),
+        token: Some(token),
+        error,
+    })
+}
+
+/// Stop any running server and start a new one if enabled in settings.
+pub fn reload<R: Runtime>(app: &AppHandle<R>) -> ApiReport {
+    let Some(server) = app.try_state::<ApiServer>() else {
+        return ApiReport::default();
+    };
+    if let Ok(mut shutdown) = server.shutdown.lock() {
+        shutdown.take();
+    }
+    let db_path = app.state::<AppState>().db_path.clone();
+    let _ = std::fs::remove_file(db_path.with_file_name(CONNECTION_FILE));
+
+    let report = start(app, &server).unwrap_or_else(|e| ApiReport {
+        enabled: true,
+        error: Some(e),
+        ..ApiReport::default()
+    });
+    if let Ok(mut last) = server.report.lock() {
+        *last = report.clone();
+    }
+    report
+}
+
+/// Relay timer events to subscribers and start the server if enabled.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    for name in FORWARDED_EVENTS {
+        let events = events.clone();
+        app.listen_any(name, move |event| {
+            let payload = serde_json::from_str(event.payload()).unwrap_or(Value::Null);
+            // No subscribers is not an error
+            let _ = events.send(ApiEvent {
+                event: name,
+                payload,
+            });
+        });
+    }
+    app.manage(ApiServer {
+        report: Mutex::new(ApiReport::default()),
+        shutdown: Mutex::new(None),
+        events,
+    });
+
+    // Failures are kept in the report for the settings UI
+    reload(app);
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+/// Restart the server after the API settings changed.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn reload_api<R: Runtime>(app: AppHandle<R>) -> ApiReport {
+    reload(&app)
+}
+
+/// Replace the token, disconnecting every client that used the old one.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn regenerate_api_token<R: Runtime>(app: AppHandle<R>) -> Result<ApiReport, String> {
+    let conn = open_db(&app.state::<AppState>().db_path)?;
+    store_token(&conn, &generate_token()?)?;
+    Ok(reload(&app))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_api_report(server: tauri::State<'_, ApiServer>) -> Result<ApiReport, String> {
+    server
+        .report
+        .lock()
+        .map(|report| report.clone())
+        .map_err(|e| format!("Lock error: {e}"))
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::io::{BufRead, BufReader as StdBufReader, Write};
+    use std::net::TcpStream as StdTcpStream;
+    use std::path::PathBuf;
+
+    /// A fresh database in its own directory, so each test gets its own
+    /// connection file.
+    fn setup_test_db_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pomo-api-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("pomo.db");
+        crate::database::initialize(&path).unwrap();
+        path
+    }
+
+    fn enable_api(db_path: &Path) {
+        let conn = Connection::open(db_path).unwrap();
+        conn.execute(
+            "UPDATE user_settings SET value = 'true' WHERE key = 'api_enabled'",
+            [],
+        )
+        .unwrap();
+        // Any free port, so parallel tests do not collide
+        conn.execute(
+            "UPDATE user_settings SET value = '0' WHERE key = 'api_port'",
+            [],
+        )
+        .unwrap();
+    }
+
+    struct Client {
+        writer: StdTcpStream,
+        reader: StdBufReader<StdTcpStream>,
+        /// Events that arrived while waiting for a response.
+        events: VecDeque<Value>,
+    }
+
+    impl Client {
+        fn connect(port: u16) -> Self {
+            let stream = StdTcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            Self {
+                reader: StdBufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+                events: VecDeque::new(),
+            }
+        }
+
+        fn read(&mut self) -> Value {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        fn call(&mut self, request: &Value) -> Value {
+            writeln!(self.writer, "{request}").unwrap();
+            loop {
+                let message = self.read();
+                // Set aside events interleaved with the response
+                if message.get("event").is_none() {
+                    return message;
+                }
+                self.events.push_back(message);
+            }
+        }
+
+        fn event(&mut self) -> Value {
+            match self.events.pop_front() {
+                Some(event) => event,
+                None => self.read(),
+            }
+        }
+    }
+
+    #[test]
+    fn token_comparison() {
+        assert!(token_matches("abc123", "abc123"));
+        assert!(!token_matches("abc124", "abc123"));
+        assert!(!token_matches("abc", "abc123"));
+        assert!(!token_matches("", "abc123"));
+    }
+
+    #[test]
+    fn api_is_off_by_default() {
+        let app = tauri::test::mock_app();
+        let db_path = setup_test_db_file("disabled");
+        app.manage(AppState::new(db_path.clone()));
+        init(app.handle());
+
+        let report = app.state::<ApiServer>().report.lock().unwrap().clone();
+        assert_eq!(report, ApiReport::default());
+        assert!(!db_path.with_file_name(CONNECTION_FILE).exists());
+        let settings = ApiSettings::load(&Connection::open(&db_path).unwrap());
+        assert!(settings.token.is_empty());
+    }
+
+    #[test]
+    fn serves_requests_and_streams_events() {
+        let app = tauri::test::mock_app();
+        let db_path = setup_test_db_file("serve");
+        enable_api(&db_path);
+        app.manage(AppState::new(db_path.clone()));
+        init(app.handle());
+
+        let report = app.state::<ApiServer>().report.lock().unwrap().clone();
+        assert!(report.running, "{report:?}");
+        let port = report.port.unwrap();
+        let token = report.token.unwrap();
+        assert_eq!(token.len(), 32);
+        assert_eq!(
+            ApiSettings::load(&Connection::open(&db_path).unwrap()).token,
+            token
+        );
+
+        let mut client = Client::connect(port);
+
+        let denied = client.call(&json!({ "id": 1, "token": "nope", "method": "timer.status" }));
+        assert_eq!(denied["ok"], false);
+        assert_eq!(denied["error"], "Invalid token");
+
+        let unknown = client.call(&json!({ "id": 2, "token": token, "method": "timer.fly" }));
+        assert_eq!(unknown["error"], "Unknown method 'timer.fly'");
+
+        let created = client.call(&json!({
+            "id": 3,
+            "token": token,
+            "method": "tasks.create",
+            "params": { "title": "From script", "day_date": "2026-03-04", "jira_key": "PROJ-9" },
+        }));
+        assert_eq!(created["ok"], true, "{created}");
+        assert_eq!(created["id"], 3);
+        let listed = client.call(&json!({
+            "id": 4,
+            "token": token,
+            "method": "tasks.list",
+            "params": { "day_date": "2026-03-04" },
+        }));
+        assert_eq!(listed["result"][0]["title"], "From script");
+
+        let bad_params = client.call(&json!({ "id": 5, "token": token, "method": "tasks.list" }));
+        assert!(bad_params["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid params"));
+
+        let subscribed = client.call(&json!({ "id": 6, "token": token, "method": "subscribe" }));
+        assert_eq!(subscribed["ok"], true);
+
+        let started = client.call(&json!({
+            "id": 7,
+            "token": token,
+            "method": "timer.start",
+            "params": { "interval_type": "short_break" },
+        }));
+        assert_eq!(started["result"]["state"], "running", "{started}");
+        assert_eq!(started["result"]["interval_type"], "short_break");
+        assert_eq!(started["result"]["planned_duration_seconds"], 300);
+
+        // The state change is streamed to the subscriber as well
+        let event = loop {
+            let message = client.event();
+            if message["event"] == "timer-state-changed" {
+                break message;
+            }
+        };
+        assert_eq!(event["payload"]["state"], "running");
+
+        let cancelled = client.call(&json!({ "id": 8, "token": token, "method": "timer.cancel" }));
+        assert_eq!(cancelled["result"]["state"], "idle");
+
+        // Disabling stops the server and removes the connection file
+        let connection_file = db_path.with_file_name(CONNECTION_FILE);
+        assert!(connection_file.exists());
+        Connection::open(&db_path)
+            .unwrap()
+            .execute(
+                "UPDATE user_settings SET value = 'false' WHERE key = 'api_enabled'",
+                [],
+            )
+            .unwrap();
+        assert!(!reload(app.handle()).running);
+        assert!(!connection_file.exists());
+    }
+
+    #[test]
+    fn connection_file_failure_is_reported_without_stopping_the_server() {
+        let app = tauri::test::mock_app();
+        let db_path = setup_test_db_file("no-connection-file");
+        enable_api(&db_path);
+        // A directory in the way makes the write fail
+        std::fs::create_dir(db_path.with_file_name(CONNECTION_FILE)).unwrap();
+        app.manage(AppState::new(db_path));
+        init(app.handle());
+
+        let report = app.state::<ApiServer>().report.lock().unwrap().clone();
+        assert!(report.running, "{report:?}");
+        assert!(report
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Failed to write connection file"));
+
+        let mut client = Client::connect(report.port.unwrap());
+        let status = client.call(&json!({
+            "id": 1,
+            "token": report.token.unwrap(),
+            "method": "timer.status",
+        }));
+        assert_eq!(status["ok"], true, "{status}");
+    }
+
+    #[test]
+    fn task_changes_reach_the_webview() {
+        let app = tauri::test::mock_app();
+        let db_path = setup_test_db_file("tasks-changed");
+        app.manage(AppState::new(db_path));
+        let (tx, changed) = std::sync::mpsc::channel();
+        app.listen_any("tasks-changed", move |event| {
+            let payload: Value = serde_json::from_str(event.payload()).unwrap();
+            let _ = tx.send(payload["day_date"].clone());
+        });
+
+        let created = handle_request(
+            app.handle(),
+            "tasks.create",
+            json!({ "title": "From script", "day_date": "2026-03-04" }),
+        )
+        .unwrap();
+        let id = &created["id"];
+        handle_request(
+            app.handle(),
+            "tasks.update",
+            json!({ "id": id, "title": "Renamed" }),
+        )
+        .unwrap();
+        handle_request(app.handle(), "tasks.complete", json!({ "id": id })).unwrap();
+        handle_request(app.handle(), "tasks.reopen", json!({ "id": id })).unwrap();
+        handle_request(app.handle(), "tasks.abandon", json!({ "id": id })).unwrap();
+        handle_request(app.handle(), "tasks.reopen", json!({ "id": id })).unwrap();
+        handle_request(app.handle(), "tasks.delete", json!({ "id": id })).unwrap();
+        // Reads change nothing
+        handle_request(
+            app.handle(),
+            "tasks.list",
+            json!({ "day_date": "2026-03-04" }),
+        )
+        .unwrap();
+
+        let days: Vec<Value> = changed.try_iter().collect();
+        assert_eq!(days, vec![json!("2026-03-04"); 7]);
+    }
+
+    #[test]
+    fn slow_subscribers_drop_events_instead_of_queueing() {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        let (out, mut outgoing) = mpsc::channel(2);
+        let forwarder = spawn_forwarder(events.subscribe(), out);
+        for n in 0..10 {
+            events
+                .send(ApiEvent {
+                    event: "timer-tick",
+                    payload: json!(n),
+                })
+                .unwrap();
+        }
+        drop(events);
+        tauri::async_runtime::block_on(forwarder).unwrap();
+
+        let mut queued = Vec::new();
+        while let Ok(line) = outgoing.try_recv() {
+            queued.push(line);
+        }
+        assert_eq!(queued.len(), 2);
+        assert!(queued[0].contains("\"payload\":0"), "{queued:?}");
+    }
+}
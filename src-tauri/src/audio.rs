@@ -1,53 +1,956 @@
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 use std::io::Cursor;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::timer::{AppState, IntervalType, TimerState, TimerStatus};
 
 /// Embedded chime WAV file (generated by scripts/generate-chime.mjs).
 const CHIME_WAV: &[u8] = include_bytes!("../../public/sounds/chime.wav");
 
-/// Play the alarm chime sequence in a background thread.
-/// Plays the chime `repetitions` times with 1-second gaps.
-/// Returns immediately — audio plays asynchronously.
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn play_alarm(volume: f32, repetitions: u32) -> Result<(), String> {
-    let vol = volume.clamp(0.0, 1.0);
-    let reps = repetitions.clamp(1, 5);
+/// File extensions accepted by `import_sound`, matching the enabled rodio decoders.
+const SUPPORTED_EXTENSIONS: [&str; 4] = ["wav", "ogg", "mp3", "flac"];
+
+/// Largest sound file accepted by `import_sound`.
+const MAX_SOUND_BYTES: u64 = 10 * 1024 * 1024;
+
+// ── Types ────────────────────────────────────────────────────
 
-    std::thread::spawn(move || {
-        if let Err(e) = play_chime_sequence(vol, reps) {
-            eprintln!("Alarm playback error: {e}");
+/// Which alarm a sound is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundSlot {
+    WorkEnd,
+    BreakEnd,
+    OvertimeReminder,
+    /// Looped under work intervals when `ambient_sound` is `custom`.
+    Ambient,
+}
+
+impl SoundSlot {
+    const ALL: [Self; 4] = [
+        Self::WorkEnd,
+        Self::BreakEnd,
+        Self::OvertimeReminder,
+        Self::Ambient,
+    ];
+
+    fn setting_key(self) -> &'static str {
+        match self {
+            Self::WorkEnd => "sound_work_end",
+            Self::BreakEnd => "sound_break_end",
+            Self::OvertimeReminder => "sound_overtime_reminder",
+            Self::Ambient => "sound_ambient",
         }
-    });
+    }
+
+    fn file_stem(self) -> &'static str {
+        match self {
+            Self::WorkEnd => "work_end",
+            Self::BreakEnd => "break_end",
+            Self::OvertimeReminder => "overtime_reminder",
+            Self::Ambient => "ambient",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SoundInfo {
+    pub slot: SoundSlot,
+    /// File name inside the sounds directory, or `None` for the built-in chime.
+    pub file_name: Option<String>,
+}
+
+// ── Sound files ─────────────────────────────────────────────
+
+/// Imported sounds live in `sounds/` next to the database in the app data dir.
+fn sounds_dir(db_path: &Path) -> PathBuf {
+    db_path.with_file_name("sounds")
+}
 
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))
+}
+
+fn read_sound_setting(conn: &Connection, slot: SoundSlot) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM user_settings WHERE key = ?1",
+        [slot.setting_key()],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .filter(|v| !v.is_empty())
+}
+
+fn write_sound_setting(
+    conn: &Connection,
+    slot: SoundSlot,
+    file_name: Option<&str>,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO user_settings (key, value, type) VALUES (?1, ?2, 'string') \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        rusqlite::params![slot.setting_key(), file_name.unwrap_or("")],
+    )
+    .map_err(|e| format!("Failed to save sound setting: {e}"))?;
     Ok(())
 }
 
-fn play_chime_sequence(volume: f32, repetitions: u32) -> Result<(), String> {
-    let (_stream, handle) =
-        rodio::OutputStream::try_default().map_err(|e| format!("No audio output device: {e}"))?;
+/// Check that `bytes` decode to at least one sample with the enabled codecs.
+fn validate_sound(bytes: Vec<u8>) -> Result<(), String> {
+    let mut decoder = rodio::Decoder::new(Cursor::new(bytes))
+        .map_err(|e| format!("Unsupported or corrupt audio file: {e}"))?;
+    if decoder.next().is_none() {
+        return Err("Audio file contains no samples".into());
+    }
+    Ok(())
+}
+
+/// Validate `source` and copy it into the sounds directory as the sound
+/// for `slot`. Returns the stored file name.
+fn import_sound_file(dir: &Path, slot: SoundSlot, source: &Path) -> Result<String, String> {
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .filter(|e| SUPPORTED_EXTENSIONS.contains(&e.as_str()))
+        .ok_or_else(|| {
+            format!(
+                "Unsupported sound file type; expected one of: {}",
+                SUPPORTED_EXTENSIONS.join(", ")
+            )
+        })?;
+
+    let size = std::fs::metadata(source)
+        .map_err(|e| format!("Failed to read sound file: {e}"))?
+        .len();
+    if size > MAX_SOUND_BYTES {
+        return Err("Sound files are limited to 10 MB".into());
+    }
+    let bytes = std::fs::read(source).map_err(|e| format!("Failed to read sound file: {e}"))?;
+    validate_sound(bytes.clone())?;
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create sounds directory: {e}"))?;
+    let file_name = format!("{}.{extension}", slot.file_stem());
+    std::fs::write(dir.join(&file_name), bytes)
+        .map_err(|e| format!("Failed to store sound file: {e}"))?;
+
+    // Drop copies with another extension left by earlier imports
+    for other in SUPPORTED_EXTENSIONS.iter().filter(|e| **e != extension) {
+        let _ = std::fs::remove_file(dir.join(format!("{}.{other}", slot.file_stem())));
+    }
+    Ok(file_name)
+}
+
+/// Bytes of the sound configured for `slot`, falling back to the built-in
+/// chime when none is set or the file has gone missing.
+fn load_sound(db_path: &Path, slot: Option<SoundSlot>) -> Arc<[u8]> {
+    let custom = slot.and_then(|slot| {
+        let file_name = read_sound_setting(&open_db(db_path).ok()?, slot)?;
+        std::fs::read(sounds_dir(db_path).join(file_name)).ok()
+    });
+    custom.map_or_else(|| Arc::from(CHIME_WAV), Arc::from)
+}
+
+// ── Audio service ───────────────────────────────────────────
 
-    for i in 0..repetitions {
-        let cursor = Cursor::new(CHIME_WAV);
-        let source = rodio::Decoder::new(cursor)
-            .map_err(|e| format!("Failed to decode chime: {e}"))?;
+/// How often the audio thread checks on a playing alarm.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Silence between repetitions of a fixed alarm.
+const REPEAT_GAP: Duration = Duration::from_secs(1);
+
+/// How long ambient sound takes to fade out when a work interval ends.
+const AMBIENT_FADE: Duration = Duration::from_secs(2);
+
+/// Requests handled by the audio thread, in order.
+enum AudioCommand {
+    /// Play `sound` following `pattern`, replacing any alarm in flight.
+    PlayAlarm {
+        sound: Arc<[u8]>,
+        pattern: AlarmPattern,
+        /// When false, the alarm is dropped if another one is still sounding.
+        replace: bool,
+    },
+    StopAlarm,
+    /// Bring the ambient track in line with the timer.
+    SetAmbient(AmbientTarget),
+}
+
+/// Payload of the `audio-error` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioErrorPayload {
+    pub message: String,
+}
+
+type ErrorHandler = Arc<dyn Fn(AudioErrorPayload) + Send + Sync>;
+
+/// Where playback errors go. Until a handler is set, the most recent
+/// error is kept so it can be delivered once one is.
+#[derive(Default)]
+struct ErrorSink {
+    handler: Option<ErrorHandler>,
+    pending: Option<AudioErrorPayload>,
+}
+
+/// Long-lived audio player. Owns a single output stream on a dedicated
+/// thread (rodio streams cannot move between threads) and is driven over
+/// a channel, so alarms never overlap and can be stopped.
+#[derive(Default)]
+pub struct AudioService {
+    /// Started on first use; replaced if the thread has gone away.
+    sender: Mutex<Option<mpsc::Sender<AudioCommand>>>,
+    on_error: Arc<Mutex<ErrorSink>>,
+}
+
+impl AudioService {
+    /// Route playback errors to `handler`. An error raised before this
+    /// is called is delivered right away.
+    pub fn set_error_handler(&self, handler: impl Fn(AudioErrorPayload) + Send + Sync + 'static) {
+        let handler: ErrorHandler = Arc::new(handler);
+        let Ok(mut sink) = self.on_error.lock() else {
+            return;
+        };
+        sink.handler = Some(Arc::clone(&handler));
+        let pending = sink.pending.take();
+        drop(sink);
+        if let Some(payload) = pending {
+            handler(payload);
+        }
+    }
+
+    /// Play an alarm, cutting off whatever alarm is currently sounding.
+    pub fn play_alarm(&self, sound: Arc<[u8]>, volume: f32, repetitions: u32) {
+        self.play_alarm_pattern(sound, AlarmPattern::fixed(volume, repetitions), true);
+    }
+
+    fn play_alarm_pattern(&self, sound: Arc<[u8]>, pattern: AlarmPattern, replace: bool) {
+        self.send(AudioCommand::PlayAlarm {
+            sound,
+            pattern,
+            replace,
+        });
+    }
+
+    /// Silence the current alarm, if any.
+    pub fn stop_alarm(&self) {
+        self.send_if_started(AudioCommand::StopAlarm);
+    }
+
+    fn set_ambient(&self, target: AmbientTarget) {
+        match target {
+            AmbientTarget::Play { .. } => self.send(AudioCommand::SetAmbient(target)),
+            AmbientTarget::Pause | AmbientTarget::Off => {
+                self.send_if_started(AudioCommand::SetAmbient(target));
+            }
+        }
+    }
+
+    /// Send a command that only affects sound already playing. Nothing can
+    /// be playing before the thread exists, so it is not started for this.
+    fn send_if_started(&self, command: AudioCommand) {
+        if self.sender.lock().is_ok_and(|sender| sender.is_some()) {
+            self.send(command);
+        }
+    }
+
+    fn send(&self, command: AudioCommand) {
+        let Ok(mut sender) = self.sender.lock() else {
+            return;
+        };
+        let command = match sender.as_ref() {
+            Some(tx) => match tx.send(command) {
+                Ok(()) => return,
+                // The thread has exited; start a new one below
+                Err(mpsc::SendError(command)) => command,
+            },
+            None => command,
+        };
+        let (tx, rx) = mpsc::channel();
+        let on_error = Arc::clone(&self.on_error);
+        let spawned = std::thread::Builder::new()
+            .name("pomo-audio".into())
+            .spawn(move || run_audio_thread(&rx, &on_error));
+        match spawned {
+            Ok(_) => {
+                let _ = tx.send(command);
+                *sender = Some(tx);
+            }
+            Err(e) => report_error(&self.on_error, format!("Failed to start audio thread: {e}")),
+        }
+    }
+}
+
+fn report_error(on_error: &Mutex<ErrorSink>, message: String) {
+    let payload = AudioErrorPayload { message };
+    let Ok(mut sink) = on_error.lock() else {
+        return;
+    };
+    let Some(handler) = sink.handler.clone() else {
+        // No app to tell yet; hold on to it until there is
+        sink.pending = Some(payload);
+        return;
+    };
+    drop(sink);
+    handler(payload);
+}
+
+fn run_audio_thread(rx: &mpsc::Receiver<AudioCommand>, on_error: &Mutex<ErrorSink>) {
+    let mut player = Player::default();
+    loop {
+        let command = if player.is_idle() {
+            match rx.recv() {
+                Ok(command) => Some(command),
+                Err(_) => return,
+            }
+        } else {
+            match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(command) => Some(command),
+                Err(mpsc::RecvTimeoutError::Timeout) => None,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            }
+        };
+
+        if let Some(command) = command {
+            if let Err(message) = player.handle(command) {
+                report_error(on_error, message);
+            }
+        }
+        if let Err(message) = player.poll(Instant::now()) {
+            report_error(on_error, message);
+        }
+    }
+}
+
+/// How an alarm repeats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AlarmPattern {
+    volume: f32,
+    /// Total plays, or `None` to repeat until the alarm is acknowledged
+    /// or replaced.
+    plays: Option<u32>,
+    gap: Duration,
+    /// Added to the volume on every repetition, up to full volume.
+    volume_step: f32,
+}
+
+impl AlarmPattern {
+    /// A few plays at a steady volume, one second apart.
+    fn fixed(volume: f32, repetitions: u32) -> Self {
+        Self {
+            volume: volume.clamp(0.0, 1.0),
+            plays: Some(repetitions.clamp(1, 5)),
+            gap: REPEAT_GAP,
+            volume_step: 0.0,
+        }
+    }
+}
+
+/// Progress through an alarm's pattern.
+#[derive(Debug, Clone, PartialEq)]
+struct Repeat {
+    /// `None` repeats forever.
+    plays_left: Option<u32>,
+    gap: Duration,
+    volume: f32,
+    volume_step: f32,
+    gap_until: Option<Instant>,
+}
+
+impl Repeat {
+    /// State after the first play of `pattern` has started.
+    fn new(pattern: AlarmPattern) -> Self {
+        Self {
+            plays_left: pattern.plays.map(|plays| plays.saturating_sub(1)),
+            gap: pattern.gap,
+            volume: pattern.volume,
+            volume_step: pattern.volume_step,
+            gap_until: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RepeatStep {
+    Wait,
+    PlayAgain,
+    Done,
+}
+
+impl Repeat {
+    /// Decide what to do once the previous play has finished. Before
+    /// playing again the volume steps up.
+    fn after_play(&mut self, now: Instant) -> RepeatStep {
+        if self.plays_left == Some(0) {
+            return RepeatStep::Done;
+        }
+        match self.gap_until {
+            None => {
+                self.gap_until = Some(now + self.gap);
+                RepeatStep::Wait
+            }
+            Some(until) if now < until => RepeatStep::Wait,
+            Some(_) => {
+                self.plays_left = self.plays_left.map(|plays| plays - 1);
+                self.volume = (self.volume + self.volume_step).min(1.0);
+                self.gap_until = None;
+                RepeatStep::PlayAgain
+            }
+        }
+    }
+}
+
+struct Alarm {
+    sink: rodio::Sink,
+    sound: Arc<[u8]>,
+    repeat: Repeat,
+}
+
+struct Ambient {
+    sink: rodio::Sink,
+    source: AmbientSource,
+    /// When the fade-out started and the volume it started from.
+    fade: Option<(Instant, f32)>,
+}
+
+/// State owned by the audio thread.
+#[derive(Default)]
+struct Player {
+    output: Option<(rodio::OutputStream, rodio::OutputStreamHandle)>,
+    alarm: Option<Alarm>,
+    ambient: Option<Ambient>,
+}
+
+impl Player {
+    /// Nothing needs polling: no alarm to repeat and no fade in progress.
+    fn is_idle(&self) -> bool {
+        self.alarm.is_none() && self.ambient.as_ref().is_none_or(|a| a.fade.is_none())
+    }
+
+    fn handle(&mut self, command: AudioCommand) -> Result<(), String> {
+        match command {
+            AudioCommand::PlayAlarm {
+                sound,
+                pattern,
+                replace,
+            } => {
+                if !replace && self.alarm.is_some() {
+                    return Ok(());
+                }
+                self.stop_alarm();
+                let sink = self.new_sink()?;
+                sink.set_volume(pattern.volume);
+                append_sound(&sink, &sound)?;
+                self.alarm = Some(Alarm {
+                    sink,
+                    sound,
+                    repeat: Repeat::new(pattern),
+                });
+                Ok(())
+            }
+            AudioCommand::StopAlarm => {
+                self.stop_alarm();
+                Ok(())
+            }
+            AudioCommand::SetAmbient(target) => self.set_ambient(target, Instant::now()),
+        }
+    }
+
+    fn stop_alarm(&mut self) {
+        if let Some(alarm) = self.alarm.take() {
+            alarm.sink.stop();
+        }
+    }
+
+    fn set_ambient(&mut self, target: AmbientTarget, now: Instant) -> Result<(), String> {
+        match target {
+            AmbientTarget::Play { source, volume } => {
+                if let Some(ambient) = self.ambient.as_mut().filter(|a| a.source == source) {
+                    // Same track: resume where it left off
+                    ambient.fade = None;
+                    ambient.sink.set_volume(volume);
+                    ambient.sink.play();
+                    return Ok(());
+                }
+                if let Some(ambient) = self.ambient.take() {
+                    ambient.sink.stop();
+                }
+                let sink = self.new_sink()?;
+                sink.set_volume(volume);
+                match &source {
+                    AmbientSource::Noise(kind) => sink.append(Noise::new(*kind)),
+                    AmbientSource::File(bytes) => sink.append(
+                        rodio::Decoder::new_looped(Cursor::new(Arc::clone(bytes)))
+                            .map_err(|e| format!("Failed to decode ambient sound: {e}"))?,
+                    ),
+                }
+                self.ambient = Some(Ambient {
+                    sink,
+                    source,
+                    fade: None,
+                });
+            }
+            AmbientTarget::Pause => {
+                if let Some(ambient) = &self.ambient {
+                    ambient.sink.pause();
+                }
+            }
+            AmbientTarget::Off => match self.ambient.as_mut() {
+                // Nothing audible to fade
+                Some(ambient) if ambient.sink.is_paused() => {
+                    ambient.sink.stop();
+                    self.ambient = None;
+                }
+                Some(ambient) if ambient.fade.is_none() => {
+                    ambient.fade = Some((now, ambient.sink.volume()));
+                }
+                _ => {}
+            },
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, now: Instant) -> Result<(), String> {
+        if let Some(ambient) = &self.ambient {
+            if let Some((started, from)) = ambient.fade {
+                if let Some(volume) = fade_volume(from, now.duration_since(started)) {
+                    ambient.sink.set_volume(volume);
+                } else {
+                    ambient.sink.stop();
+                    self.ambient = None;
+                }
+            }
+        }
+
+        let Some(alarm) = self.alarm.as_mut() else {
+            return Ok(());
+        };
+        if !alarm.sink.empty() {
+            return Ok(());
+        }
+        match alarm.repeat.after_play(now) {
+            RepeatStep::Wait => Ok(()),
+            RepeatStep::PlayAgain => {
+                alarm.sink.set_volume(alarm.repeat.volume);
+                append_sound(&alarm.sink, &alarm.sound)
+            }
+            RepeatStep::Done => {
+                self.alarm = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Create a sink on the shared output, opening the device on first use
+    /// and reopening it if it went away.
+    fn new_sink(&mut self) -> Result<rodio::Sink, String> {
+        if let Some((_, handle)) = &self.output {
+            if let Ok(sink) = rodio::Sink::try_new(handle) {
+                return Ok(sink);
+            }
+        }
+        self.output = None;
+        let (stream, handle) = rodio::OutputStream::try_default()
+            .map_err(|e| format!("No audio output device: {e}"))?;
         let sink = rodio::Sink::try_new(&handle)
             .map_err(|e| format!("Failed to create audio sink: {e}"))?;
-        sink.set_volume(volume);
-        sink.append(source);
-        sink.sleep_until_end();
+        self.output = Some((stream, handle));
+        Ok(sink)
+    }
+}
+
+fn append_sound(sink: &rodio::Sink, sound: &Arc<[u8]>) -> Result<(), String> {
+    let source = rodio::Decoder::new(Cursor::new(Arc::clone(sound)))
+        .map_err(|e| format!("Failed to decode sound: {e}"))?;
+    sink.append(source);
+    Ok(())
+}
+
+/// Volume `elapsed` into a fade-out from `from`, or `None` once it is over.
+fn fade_volume(from: f32, elapsed: Duration) -> Option<f32> {
+    (elapsed < AMBIENT_FADE)
+        .then(|| from * (1.0 - elapsed.as_secs_f32() / AMBIENT_FADE.as_secs_f32()))
+}
+
+// ── Ambient sound ───────────────────────────────────────────
+
+const NOISE_SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NoiseKind {
+    White,
+    Brown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AmbientSource {
+    Noise(NoiseKind),
+    File(Arc<[u8]>),
+}
+
+/// What the ambient track should be doing.
+#[derive(Debug, Clone, PartialEq)]
+enum AmbientTarget {
+    Play {
+        source: AmbientSource,
+        volume: f32,
+    },
+    Pause,
+    /// Fade out and stop.
+    Off,
+}
 
-        if i < repetitions - 1 {
-            std::thread::sleep(Duration::from_secs(1));
+/// Endless generated noise, mono.
+struct Noise {
+    kind: NoiseKind,
+    /// xorshift32 state; never zero.
+    rng: u32,
+    /// Running integral for brown noise.
+    brown: f32,
+}
+
+impl Noise {
+    fn new(kind: NoiseKind) -> Self {
+        Self {
+            kind,
+            rng: 0x9E37_79B9,
+            brown: 0.0,
         }
     }
 
+    /// Uniform sample in [-1, 1].
+    #[allow(clippy::cast_precision_loss)]
+    fn white(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng as f32 / u32::MAX as f32).mul_add(2.0, -1.0)
+    }
+}
+
+impl Iterator for Noise {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let white = self.white();
+        Some(match self.kind {
+            NoiseKind::White => white * 0.5,
+            NoiseKind::Brown => {
+                // Leaky integrator; the gain brings it back to a similar loudness
+                self.brown = 0.02f32.mul_add(white, self.brown) / 1.02;
+                (self.brown * 3.5).clamp(-1.0, 1.0)
+            }
+        })
+    }
+}
+
+impl rodio::Source for Noise {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        NOISE_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AmbientSettings {
+    /// `off`, `white_noise`, `brown_noise` or `custom` (the ambient sound slot).
+    sound: String,
+    volume: f32,
+}
+
+impl AmbientSettings {
+    fn load(conn: &Connection) -> Self {
+        let read = |key: &str| {
+            conn.query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                [key],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        };
+        Self {
+            sound: read("ambient_sound").unwrap_or_else(|| "off".into()),
+            volume: read("ambient_volume")
+                .and_then(|v| v.parse::<f32>().ok())
+                .map_or(0.3, |v| v.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// Ambient playback only accompanies a running (or paused) work interval.
+fn wants_ambient(status: &TimerStatus) -> Option<TimerState> {
+    (status.interval_type == IntervalType::Work && !status.overtime)
+        .then_some(status.state)
+        .filter(|state| *state != TimerState::Idle)
+}
+
+fn ambient_target(db_path: &Path, status: &TimerStatus) -> AmbientTarget {
+    match wants_ambient(status) {
+        Some(TimerState::Paused) => AmbientTarget::Pause,
+        Some(TimerState::Running) => {
+            let Ok(conn) = open_db(db_path) else {
+                return AmbientTarget::Off;
+            };
+            let settings = AmbientSettings::load(&conn);
+            let source = match settings.sound.as_str() {
+                "white_noise" => AmbientSource::Noise(NoiseKind::White),
+                "brown_noise" => AmbientSource::Noise(NoiseKind::Brown),
+                "custom" => match read_sound_setting(&conn, SoundSlot::Ambient)
+                    .and_then(|file| std::fs::read(sounds_dir(db_path).join(file)).ok())
+                {
+                    Some(bytes) => AmbientSource::File(Arc::from(bytes)),
+                    None => return AmbientTarget::Off,
+                },
+                _ => return AmbientTarget::Off,
+            };
+            AmbientTarget::Play {
+                source,
+                volume: settings.volume,
+            }
+        }
+        _ => AmbientTarget::Off,
+    }
+}
+
+/// Start, pause, resume or fade out ambient sound to match a timer
+/// transition. Called with every `timer-state-changed` status.
+pub(crate) fn sync_ambient(state: &AppState, status: &TimerStatus) {
+    state
+        .audio
+        .set_ambient(ambient_target(&state.db_path, status));
+}
+
+// ── Alarm policy ────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+struct AlarmSettings {
+    volume: f32,
+    /// Repeat until `acknowledge_alarm` instead of a fixed few plays.
+    until_acknowledged: bool,
+    repeat_seconds: u32,
+    volume_step: f32,
+    /// Minutes of overtime between reminder chimes; 0 disables them.
+    overtime_reminder_minutes: u32,
+}
+
+impl AlarmSettings {
+    fn load(conn: &Connection) -> Self {
+        let read = |key: &str| {
+            conn.query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                [key],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        };
+        let fraction = |key: &str, default: f32| {
+            read(key)
+                .and_then(|v| v.parse::<f32>().ok())
+                .map_or(default, |v| v.clamp(0.0, 1.0))
+        };
+        let integer = |key: &str, default: u32| {
+            read(key)
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            volume: fraction("alarm_volume", 0.6),
+            until_acknowledged: read("alarm_mode").as_deref() == Some("until_acknowledged"),
+            repeat_seconds: integer("alarm_repeat_seconds", 30).max(1),
+            volume_step: fraction("alarm_volume_step", 0.1),
+            overtime_reminder_minutes: integer("overtime_reminder_minutes", 5),
+        }
+    }
+
+    fn until_acknowledged_pattern(&self) -> AlarmPattern {
+        AlarmPattern {
+            volume: self.volume,
+            plays: None,
+            gap: Duration::from_secs(u64::from(self.repeat_seconds)),
+            volume_step: self.volume_step,
+        }
+    }
+}
+
+/// Ring the completion alarm when it is set to repeat until acknowledged.
+/// Fixed alarms are played by the frontend.
+pub(crate) fn ring_completion_alarm(state: &AppState, interval_type: IntervalType) {
+    let Ok(conn) = open_db(&state.db_path) else {
+        return;
+    };
+    let settings = AlarmSettings::load(&conn);
+    if !settings.until_acknowledged {
+        return;
+    }
+    let slot = match interval_type {
+        IntervalType::Work => SoundSlot::WorkEnd,
+        IntervalType::ShortBreak | IntervalType::LongBreak => SoundSlot::BreakEnd,
+    };
+    state.audio.play_alarm_pattern(
+        load_sound(&state.db_path, Some(slot)),
+        settings.until_acknowledged_pattern(),
+        true,
+    );
+}
+
+/// Whether overtime crossed a multiple of `every_ms` since the last tick.
+/// Without a previous tick (the tick task just started) nothing is due.
+fn reminder_due(previous_ms: Option<u64>, overtime_ms: u64, every_ms: u64) -> bool {
+    every_ms > 0 && previous_ms.is_some_and(|previous| previous / every_ms < overtime_ms / every_ms)
+}
+
+/// Chime once each time overtime passes another `overtime_reminder_minutes`.
+/// `previous_ms` is the overtime reported by the previous tick. A reminder
+/// never cuts off an alarm that is still sounding.
+pub(crate) fn overtime_reminder(state: &AppState, previous_ms: Option<u64>, overtime_ms: u64) {
+    // Reminders fall on whole minutes; skip the DB unless one was crossed
+    if !reminder_due(previous_ms, overtime_ms, 60_000) {
+        return;
+    }
+    let Ok(conn) = open_db(&state.db_path) else {
+        return;
+    };
+    let settings = AlarmSettings::load(&conn);
+    let every_ms = u64::from(settings.overtime_reminder_minutes) * 60_000;
+    if reminder_due(previous_ms, overtime_ms, every_ms) {
+        state.audio.play_alarm_pattern(
+            load_sound(&state.db_path, Some(SoundSlot::OvertimeReminder)),
+            AlarmPattern::fixed(settings.volume, 1),
+            false,
+        );
+    }
+}
+
+/// Send audio errors to the frontend as `audio-error` events.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let handle = app.clone();
+    app.state::<AppState>()
+        .audio
+        .set_error_handler(move |payload| {
+            let _ = handle.emit("audio-error", payload);
+        });
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+/// Play the alarm sequence for `sound` (the built-in chime when omitted).
+/// Plays the sound `repetitions` times with 1-second gaps, replacing any
+/// alarm already sounding. Returns immediately — audio plays asynchronously.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn play_alarm(
+    state: tauri::State<'_, AppState>,
+    volume: f32,
+    repetitions: u32,
+    sound: Option<SoundSlot>,
+) -> Result<(), String> {
+    state
+        .audio
+        .play_alarm(load_sound(&state.db_path, sound), volume, repetitions);
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn stop_alarm(state: tauri::State<'_, AppState>) {
+    state.audio.stop_alarm();
+}
+
+/// Silence a repeating alarm and tell every window it was acknowledged.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn acknowledge_alarm<R: Runtime>(state: tauri::State<'_, AppState>, app: AppHandle<R>) {
+    state.audio.stop_alarm();
+    let _ = app.emit("alarm-acknowledged", ());
+}
+
+/// Play the sound configured for `slot` once.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn preview_sound(
+    state: tauri::State<'_, AppState>,
+    slot: SoundSlot,
+    volume: f32,
+) -> Result<(), String> {
+    state
+        .audio
+        .play_alarm(load_sound(&state.db_path, Some(slot)), volume, 1);
     Ok(())
 }
 
+/// Import a user sound file for `slot`. The file is decoded up front so a
+/// bad file is rejected here rather than when the alarm fires.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn import_sound(
+    state: tauri::State<'_, AppState>,
+    slot: SoundSlot,
+    path: String,
+) -> Result<SoundInfo, String> {
+    let file_name = import_sound_file(&sounds_dir(&state.db_path), slot, Path::new(&path))?;
+    write_sound_setting(&open_db(&state.db_path)?, slot, Some(&file_name))?;
+    Ok(SoundInfo {
+        slot,
+        file_name: Some(file_name),
+    })
+}
+
+/// Go back to the built-in chime for `slot`.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn reset_sound(
+    state: tauri::State<'_, AppState>,
+    slot: SoundSlot,
+) -> Result<SoundInfo, String> {
+    let conn = open_db(&state.db_path)?;
+    if let Some(file_name) = read_sound_setting(&conn, slot) {
+        let _ = std::fs::remove_file(sounds_dir(&state.db_path).join(file_name));
+    }
+    write_sound_setting(&conn, slot, None)?;
+    Ok(SoundInfo {
+        slot,
+        file_name: None,
+    })
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_sounds(state: tauri::State<'_, AppState>) -> Result<Vec<SoundInfo>, String> {
+    let conn = open_db(&state.db_path)?;
+    Ok(SoundSlot::ALL
+        .iter()
+        .map(|&slot| SoundInfo {
+            slot,
+            file_name: read_sound_setting(&conn, slot),
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pomo-audio-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
     #[test]
     fn chime_wav_is_valid() {
         // Verify the embedded WAV starts with RIFF header
@@ -58,10 +961,331 @@ mod tests {
 
     #[test]
     fn volume_is_clamped() {
-        // Just verify the function doesn't panic with edge values
+        // Just verify the service doesn't panic with edge values
         // Actual audio playback not tested (requires audio device)
-        let result = play_alarm(1.5, 1);
-        // May fail in CI without audio device, which is OK
-        assert!(result.is_ok());
+        let service = AudioService::default();
+        service.play_alarm(Arc::from(CHIME_WAV), 1.5, 1);
+        service.play_alarm(Arc::from(CHIME_WAV), -1.0, 0);
+        service.stop_alarm();
+    }
+
+    #[test]
+    fn stop_before_any_alarm_does_not_start_the_thread() {
+        let service = AudioService::default();
+        service.stop_alarm();
+        assert!(service.sender.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn playback_errors_reach_the_handler() {
+        let service = AudioService::default();
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        service.set_error_handler(move |payload| {
+            let _ = tx.lock().unwrap().send(payload.message);
+        });
+
+        // Undecodable data fails on the audio thread, whether or not a
+        // device is available in the test environment
+        service.play_alarm(Arc::from(&b"not audio"[..]), 0.5, 1);
+        let message = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(
+            message.starts_with("Failed to decode sound")
+                || message.starts_with("No audio output device"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn errors_before_a_handler_is_set_are_delivered_later() {
+        let service = AudioService::default();
+        report_error(&service.on_error, "first".to_string());
+        report_error(&service.on_error, "second".to_string());
+
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        service.set_error_handler(move |payload| {
+            let _ = tx.lock().unwrap().send(payload.message);
+        });
+
+        assert_eq!(rx.try_recv().unwrap(), "second");
+        assert!(rx.try_recv().is_err());
+        assert!(service.on_error.lock().unwrap().pending.is_none());
+    }
+
+    #[test]
+    fn repeat_waits_a_gap_between_plays() {
+        let start = Instant::now();
+        let mut repeat = Repeat::new(AlarmPattern::fixed(0.5, 3));
+
+        assert_eq!(repeat.after_play(start), RepeatStep::Wait);
+        assert_eq!(
+            repeat.after_play(start + Duration::from_millis(500)),
+            RepeatStep::Wait
+        );
+        assert_eq!(repeat.after_play(start + REPEAT_GAP), RepeatStep::PlayAgain);
+        assert_eq!(repeat.plays_left, Some(1));
+        assert!((repeat.volume - 0.5).abs() < f32::EPSILON);
+
+        let later = start + Duration::from_secs(5);
+        assert_eq!(repeat.after_play(later), RepeatStep::Wait);
+        assert_eq!(repeat.after_play(later + REPEAT_GAP), RepeatStep::PlayAgain);
+        assert_eq!(repeat.after_play(later + REPEAT_GAP * 3), RepeatStep::Done);
+    }
+
+    #[test]
+    fn chime_decodes() {
+        assert!(validate_sound(CHIME_WAV.to_vec()).is_ok());
+    }
+
+    #[test]
+    fn garbage_is_rejected_at_import() {
+        let dir = temp_dir("garbage");
+        let source = dir.join("noise.ogg");
+        std::fs::write(&source, b"definitely not audio").unwrap();
+
+        let err = import_sound_file(&dir.join("sounds"), SoundSlot::WorkEnd, &source).unwrap_err();
+        assert!(
+            err.starts_with("Unsupported or corrupt audio file"),
+            "{err}"
+        );
+        assert!(!dir.join("sounds").join("work_end.ogg").exists());
+    }
+
+    #[test]
+    fn unknown_extension_is_rejected() {
+        let dir = temp_dir("extension");
+        let source = dir.join("chime.aiff");
+        std::fs::write(&source, CHIME_WAV).unwrap();
+
+        let err = import_sound_file(&dir, SoundSlot::BreakEnd, &source).unwrap_err();
+        assert!(err.starts_with("Unsupported sound file type"), "{err}");
+    }
+
+    #[test]
+    fn import_replaces_previous_file_for_slot() {
+        let dir = temp_dir("replace");
+        let sounds = dir.join("sounds");
+        std::fs::create_dir_all(&sounds).unwrap();
+        std::fs::write(sounds.join("break_end.mp3"), b"old").unwrap();
+        let source = dir.join("Bell.WAV");
+        std::fs::write(&source, CHIME_WAV).unwrap();
+
+        let file_name = import_sound_file(&sounds, SoundSlot::BreakEnd, &source).unwrap();
+        assert_eq!(file_name, "break_end.wav");
+        assert_eq!(
+            std::fs::read(sounds.join("break_end.wav")).unwrap(),
+            CHIME_WAV
+        );
+        assert!(!sounds.join("break_end.mp3").exists());
+    }
+
+    #[test]
+    fn load_sound_uses_slot_setting_or_falls_back() {
+        let dir = temp_dir("load");
+        let db_path = dir.join("pomo.db");
+        crate::database::initialize(&db_path).unwrap();
+        let custom = dir.join("custom.wav");
+        // Same data as the chime but with a distinguishable tail
+        let mut bytes = CHIME_WAV.to_vec();
+        bytes.extend_from_slice(b"tail");
+        std::fs::write(&custom, &bytes).unwrap();
+
+        let file_name =
+            import_sound_file(&sounds_dir(&db_path), SoundSlot::WorkEnd, &custom).unwrap();
+        write_sound_setting(
+            &open_db(&db_path).unwrap(),
+            SoundSlot::WorkEnd,
+            Some(&file_name),
+        )
+        .unwrap();
+
+        assert_eq!(&*load_sound(&db_path, Some(SoundSlot::WorkEnd)), &bytes[..]);
+        assert_eq!(&*load_sound(&db_path, Some(SoundSlot::BreakEnd)), CHIME_WAV);
+        assert_eq!(&*load_sound(&db_path, None), CHIME_WAV);
+
+        // A missing file falls back to the chime instead of going silent
+        std::fs::remove_file(sounds_dir(&db_path).join(&file_name)).unwrap();
+        assert_eq!(&*load_sound(&db_path, Some(SoundSlot::WorkEnd)), CHIME_WAV);
+    }
+
+    #[test]
+    fn acknowledged_pattern_repeats_with_rising_volume() {
+        let settings = AlarmSettings {
+            volume: 0.6,
+            until_acknowledged: true,
+            repeat_seconds: 30,
+            volume_step: 0.15,
+            overtime_reminder_minutes: 5,
+        };
+        let mut repeat = Repeat::new(settings.until_acknowledged_pattern());
+        let mut now = Instant::now();
+        let mut volumes = Vec::new();
+        for _ in 0..4 {
+            assert_eq!(repeat.after_play(now), RepeatStep::Wait);
+            now += Duration::from_secs(30);
+            assert_eq!(repeat.after_play(now), RepeatStep::PlayAgain);
+            volumes.push(repeat.volume);
+        }
+        assert_eq!(repeat.plays_left, None);
+        let expected = [0.75, 0.9, 1.0, 1.0];
+        assert!(
+            volumes
+                .iter()
+                .zip(expected)
+                .all(|(v, e)| (v - e).abs() < 1e-5),
+            "{volumes:?}"
+        );
+    }
+
+    #[test]
+    fn alarm_settings_default_to_fixed_mode() {
+        let dir = temp_dir("alarm-settings");
+        let db_path = dir.join("pomo.db");
+        crate::database::initialize(&db_path).unwrap();
+        let conn = open_db(&db_path).unwrap();
+        assert_eq!(
+            AlarmSettings::load(&conn),
+            AlarmSettings {
+                volume: 0.6,
+                until_acknowledged: false,
+                repeat_seconds: 30,
+                volume_step: 0.1,
+                overtime_reminder_minutes: 5,
+            }
+        );
+
+        conn.execute(
+            "UPDATE user_settings SET value = 'until_acknowledged' WHERE key = 'alarm_mode'",
+            [],
+        )
+        .unwrap();
+        assert!(AlarmSettings::load(&conn).until_acknowledged);
+    }
+
+    #[test]
+    fn overtime_reminders_fall_on_interval_boundaries() {
+        let every = 5 * 60_000;
+        assert!(!reminder_due(None, 300_000, every));
+        assert!(!reminder_due(Some(299_000), 299_750, every));
+        assert!(reminder_due(Some(299_750), 300_000, every));
+        assert!(!reminder_due(Some(300_000), 300_250, every));
+        assert!(reminder_due(Some(599_900), 600_150, every));
+        assert!(!reminder_due(Some(0), 900_000, 0));
+    }
+
+    #[test]
+    fn noise_stays_in_range_and_brown_is_smoother() {
+        let white: Vec<f32> = Noise::new(NoiseKind::White).take(10_000).collect();
+        let brown: Vec<f32> = Noise::new(NoiseKind::Brown).take(10_000).collect();
+        assert!(white.iter().chain(&brown).all(|s| (-1.0..=1.0).contains(s)));
+
+        // Same length, so total movement compares the average step size
+        let total_step = |s: &[f32]| s.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f32>();
+        assert!(total_step(&brown) * 5.0 < total_step(&white));
+    }
+
+    #[test]
+    fn fade_ramps_down_to_silence() {
+        assert_eq!(fade_volume(0.8, Duration::ZERO), Some(0.8));
+        let half = fade_volume(0.8, AMBIENT_FADE / 2).unwrap();
+        assert!((half - 0.4).abs() < 1e-4, "{half}");
+        assert_eq!(fade_volume(0.8, AMBIENT_FADE), None);
+    }
+
+    fn status(state: TimerState, interval_type: IntervalType, overtime: bool) -> TimerStatus {
+        TimerStatus {
+            state,
+            interval_type,
+            remaining_ms: 0,
+            planned_duration_seconds: 1500,
+            interval_id: Some(1),
+            completed_work_count: 0,
+            overtime,
+            overtime_ms: 0,
+            idle_paused: false,
+            seq: 1,
+        }
+    }
+
+    #[test]
+    fn ambient_follows_work_intervals_only() {
+        let dir = temp_dir("ambient");
+        let db_path = dir.join("pomo.db");
+        crate::database::initialize(&db_path).unwrap();
+        let running_work = status(TimerState::Running, IntervalType::Work, false);
+
+        // Off by default
+        assert_eq!(ambient_target(&db_path, &running_work), AmbientTarget::Off);
+
+        let conn = open_db(&db_path).unwrap();
+        conn.execute(
+            "UPDATE user_settings SET value = 'brown_noise' WHERE key = 'ambient_sound'",
+            [],
+        )
+        .unwrap();
+        assert_eq!(
+            ambient_target(&db_path, &running_work),
+            AmbientTarget::Play {
+                source: AmbientSource::Noise(NoiseKind::Brown),
+                volume: 0.3,
+            }
+        );
+        assert_eq!(
+            ambient_target(
+                &db_path,
+                &status(TimerState::Paused, IntervalType::Work, false)
+            ),
+            AmbientTarget::Pause
+        );
+        for other in [
+            status(TimerState::Idle, IntervalType::Work, false),
+            status(TimerState::Running, IntervalType::ShortBreak, false),
+            status(TimerState::Running, IntervalType::LongBreak, true),
+        ] {
+            assert_eq!(ambient_target(&db_path, &other), AmbientTarget::Off);
+        }
+    }
+
+    #[test]
+    fn custom_ambient_needs_an_imported_file() {
+        let dir = temp_dir("ambient-custom");
+        let db_path = dir.join("pomo.db");
+        crate::database::initialize(&db_path).unwrap();
+        let conn = open_db(&db_path).unwrap();
+        conn.execute(
+            "UPDATE user_settings SET value = 'custom' WHERE key = 'ambient_sound'",
+            [],
+        )
+        .unwrap();
+        let running_work = status(TimerState::Running, IntervalType::Work, false);
+        assert_eq!(ambient_target(&db_path, &running_work), AmbientTarget::Off);
+
+        let source = dir.join("rain.wav");
+        std::fs::write(&source, CHIME_WAV).unwrap();
+        let file_name =
+            import_sound_file(&sounds_dir(&db_path), SoundSlot::Ambient, &source).unwrap();
+        write_sound_setting(&conn, SoundSlot::Ambient, Some(&file_name)).unwrap();
+        assert_eq!(
+            ambient_target(&db_path, &running_work),
+            AmbientTarget::Play {
+                source: AmbientSource::File(Arc::from(CHIME_WAV)),
+                volume: 0.3,
+            }
+        );
+    }
+
+    #[test]
+    fn pausing_without_ambient_does_not_start_the_thread() {
+        let service = AudioService::default();
+        service.set_ambient(AmbientTarget::Pause);
+        service.set_ambient(AmbientTarget::Off);
+        assert!(service.sender.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn sound_slot_serializes_snake_case() {
+        let json = serde_json::to_string(&SoundSlot::OvertimeReminder).unwrap();
+        assert_eq!(json, "\"overtime_reminder\"");
     }
 }
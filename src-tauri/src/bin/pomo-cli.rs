@@ -0,0 +1,6 @@
+//! Headless command-line access to the Pomo database, safe to use while
+//! the app is running. Run `pomo-cli --help` for usage.
+
+fn main() -> std::process::ExitCode {
+    pomo_lib::cli::main()
+}
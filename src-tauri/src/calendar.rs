@@ -0,0 +1,520 @@
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::export::{EARLIEST_DATE, LATEST_DATE};
+use crate::intervals::{self, Interval};
+use crate::timer::{AppState, IntervalType};
+
+/// RFC 5545 recommends folding content lines longer than 75 octets.
+const MAX_LINE_OCTETS: usize = 75;
+const UID_DOMAIN: &str = "pomo.app";
+
+// ── Types ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+struct LinkedTask {
+    title: String,
+    jira_key: Option<String>,
+}
+
+impl LinkedTask {
+    fn label(&self) -> String {
+        match &self.jira_key {
+            Some(key) => format!("{key} {}", self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+fn read_setting(conn: &Connection, key: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM user_settings WHERE key = ?1",
+        [key],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+/// Tasks linked to each interval that started within `[start_date, end_date]`.
+fn query_linked_tasks(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<HashMap<i64, Vec<LinkedTask>>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT til.interval_id, t.title, t.jira_key
+             FROM task_interval_links til
+             JOIN tasks t ON t.id = til.task_id
+             JOIN timer_intervals ti ON ti.id = til.interval_id
+             WHERE date(ti.start_time) BETWEEN ?1 AND ?2
+             ORDER BY til.interval_id, t.position, t.id",
+        )
+        .map_err(|e| format!("Failed to prepare linked task query: {e}"))?;
+
+    let rows = stmt
+        .query_map([start_date, end_date], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                LinkedTask {
+                    title: row.get(1)?,
+                    jira_key: row.get(2)?,
+                },
+            ))
+        })
+        .map_err(|e| format!("Failed to query linked tasks: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read linked tasks: {e}"))?;
+
+    let mut linked: HashMap<i64, Vec<LinkedTask>> = HashMap::new();
+    for (interval_id, task) in rows {
+        linked.entry(interval_id).or_default().push(task);
+    }
+    Ok(linked)
+}
+
+// ── iCalendar formatting ────────────────────────────────────
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append a content line, folded at 75 octets without splitting a
+/// UTF-8 sequence, and terminated with CRLF.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn format_utc(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn parse_utc(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn summary(interval: &Interval, tasks: &[LinkedTask]) -> String {
+    match interval.interval_type.as_str() {
+        "short_break" => "Short break".into(),
+        "long_break" => "Long break".into(),
+        _ if tasks.is_empty() => "Focus".into(),
+        _ => {
+            let labels: Vec<String> = tasks.iter().map(LinkedTask::label).collect();
+            format!("Focus: {}", labels.join(", "))
+        }
+    }
+}
+
+fn description(interval: &Interval, actual_seconds: i64, tasks: &[LinkedTask]) -> String {
+    let mut text = String::new();
+    for task in tasks {
+        let _ = writeln!(text, "- {}", task.label());
+    }
+    if !tasks.is_empty() {
+        text.push('\n');
+    }
+    let _ = write!(
+        text,
+        "{} of {} planned minutes",
+        actual_seconds / 60,
+        interval.planned_duration_seconds / 60
+    );
+    text
+}
+
+/// Stable across exports so calendars update events instead of duplicating them.
+fn event_uid(interval_id: i64) -> String {
+    format!("pomo-interval-{interval_id}@{UID_DOMAIN}")
+}
+
+fn push_event(out: &mut String, interval: &Interval, tasks: &[LinkedTask]) {
+    let Some(start) = parse_utc(&interval.start_time) else {
+        return;
+    };
+    let actual_seconds = interval
+        .duration_seconds
+        .unwrap_or(interval.planned_duration_seconds);
+    let end = interval
+        .end_time
+        .as_deref()
+        .and_then(parse_utc)
+        .unwrap_or_else(|| start + Duration::seconds(actual_seconds));
+    // Derived from the row, not the export time, so re-exports are identical
+    let stamp = parse_utc(&interval.created_at).unwrap_or(start);
+    let category = if interval.interval_type == "work" {
+        "Focus"
+    } else {
+        "Break"
+    };
+
+    push_line(out, "BEGIN:VEVENT");
+    push_line(out, &format!("UID:{}", event_uid(interval.id)));
+    push_line(out, &format!("DTSTAMP:{}", format_utc(stamp)));
+    push_line(out, &format!("DTSTART:{}", format_utc(start)));
+    push_line(out, &format!("DTEND:{}", format_utc(end)));
+    push_line(
+        out,
+        &format!("SUMMARY:{}", escape_text(&summary(interval, tasks))),
+    );
+    push_line(
+        out,
+        &format!(
+            "DESCRIPTION:{}",
+            escape_text(&description(interval, actual_seconds, tasks))
+        ),
+    );
+    push_line(out, &format!("CATEGORIES:{category}"));
+    push_line(out, "TRANSP:OPAQUE");
+    push_line(out, "END:VEVENT");
+}
+
+/// Render completed intervals that started within `[from, to]` as an
+/// iCalendar document. An empty `interval_types` exports every type.
+pub fn export_ics(
+    conn: &Connection,
+    from: Option<&str>,
+    to: Option<&str>,
+    interval_types: &[IntervalType],
+) -> Result<String, String> {
+    let start = from.unwrap_or(EARLIEST_DATE);
+    let end = to.unwrap_or(LATEST_DATE);
+    let linked = query_linked_tasks(conn, start, end)?;
+
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//Pomo//Focus Sessions//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+    push_line(&mut out, "X-WR-CALNAME:Pomo");
+
+    for interval in intervals::list_between(conn, start, end)? {
+        if interval.status != "completed" {
+            continue;
+        }
+        if !interval_types.is_empty()
+            && !interval_types
+                .iter()
+                .any(|t| t.as_db_str() == interval.interval_type)
+        {
+            continue;
+        }
+        let tasks = linked.get(&interval.id).map_or(&[][..], Vec::as_slice);
+        push_event(&mut out, &interval, tasks);
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    Ok(out)
+}
+
+// ── Calendar feed ───────────────────────────────────────────
+
+/// Rewrite the `.ics` feed configured in `calendar_feed_path`, if any, with
+/// the full history. Returns the path written.
+///
+/// The file is replaced atomically so a calendar polling it never sees a
+/// partial write, and on Unix it is left read-only since edits would be
+/// overwritten on the next refresh.
+pub(crate) fn refresh_feed(db_path: &Path) -> Result<Option<PathBuf>, String> {
+    let conn = open_db(db_path)?;
+    let Some(path) = read_setting(&conn, "calendar_feed_path")
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+    else {
+        return Ok(None);
+    };
+    let include_breaks =
+        read_setting(&conn, "calendar_feed_include_breaks").is_some_and(|v| v == "true");
+    let types: &[IntervalType] = if include_breaks {
+        &[]
+    } else {
+        &[IntervalType::Work]
+    };
+    let ics = export_ics(&conn, None, None, types)?;
+
+    let tmp_path = path.with_extension("ics.tmp");
+    let write = || -> std::io::Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o444);
+        }
+        let _ = std::fs::remove_file(&tmp_path);
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(ics.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, &path)
+    };
+    write().map_err(|e| format!("Failed to write calendar feed {}: {e}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// Refresh the feed at startup so it reflects a changed setting or
+/// history edited while the app was closed.
+pub fn init(db_path: &Path) {
+    if let Err(e) = refresh_feed(db_path) {
+        eprintln!("{e}");
+    }
+}
+
+/// Rewrite the feed in the background after an interval ends. Failures are
+/// emitted as `calendar-feed-error` so the UI can surface them.
+pub(crate) fn spawn_feed_refresh<R: Runtime>(app: &AppHandle<R>, db_path: &Path) {
+    let app = app.clone();
+    let db_path = db_path.to_path_buf();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = refresh_feed(&db_path) {
+            eprintln!("{e}");
+            let _ = app.emit("calendar-feed-error", e);
+        }
+    });
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn export_calendar(
+    state: tauri::State<'_, AppState>,
+    from: Option<String>,
+    to: Option<String>,
+    interval_types: Option<Vec<IntervalType>>,
+) -> Result<String, String> {
+    export_ics(
+        &open_db(&state.db_path)?,
+        from.as_deref(),
+        to.as_deref(),
+        interval_types.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Rewrite the calendar feed now, e.g. after its settings change.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn refresh_calendar_feed(state: tauri::State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(refresh_feed(&state.db_path)?.map(|p| p.display().to_string()))
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database;
+    use crate::tasks;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        database::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn insert_interval(conn: &Connection, interval_type: &str, start: &str, status: &str) -> i64 {
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds, planned_duration_seconds, status, created_at)
+             VALUES (?1, ?2, strftime('%Y-%m-%dT%H:%M:%SZ', ?2, '+25 minutes'), 1500, 1500, ?3, ?2)",
+            [interval_type, start, status],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    /// Undo line folding and split into content lines.
+    fn unfolded(ics: &str) -> Vec<String> {
+        ics.replace("\r\n ", "")
+            .split("\r\n")
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(
+            escape_text("a,b;c\\d\r\ne"),
+            "a\\,b\\;c\\\\d\\ne".to_string()
+        );
+    }
+
+    #[test]
+    fn folds_long_lines_on_char_boundaries() {
+        let mut out = String::new();
+        let line = format!("SUMMARY:{}", "é".repeat(80));
+        push_line(&mut out, &line);
+
+        for physical in out.split("\r\n").filter(|l| !l.is_empty()) {
+            assert!(physical.len() <= MAX_LINE_OCTETS, "{physical}");
+        }
+        assert_eq!(unfolded(&out), vec![line]);
+    }
+
+    #[test]
+    fn exports_completed_intervals_with_linked_tasks() {
+        let conn = setup_test_db();
+        let work = insert_interval(&conn, "work", "2026-03-02T09:00:00Z", "completed");
+        insert_interval(&conn, "work", "2026-03-02T10:00:00Z", "cancelled");
+        insert_interval(&conn, "short_break", "2026-03-02T09:25:00Z", "completed");
+        let task = tasks::create(
+            &conn,
+            "Fix login, again",
+            "2026-03-02",
+            None,
+            Some("PROJ-7"),
+            None,
+        )
+        .unwrap();
+        tasks::link_to_interval(&conn, &[task.id], work).unwrap();
+
+        let ics = export_ics(&conn, None, None, &[]).unwrap();
+        let lines = unfolded(&ics);
+
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(lines.iter().filter(|l| *l == "BEGIN:VEVENT").count(), 2);
+        assert!(lines.contains(&format!("UID:pomo-interval-{work}@pomo.app")));
+        assert!(lines.contains(&"DTSTART:20260302T090000Z".to_string()));
+        assert!(lines.contains(&"DTEND:20260302T092500Z".to_string()));
+        assert!(lines.contains(&"SUMMARY:Focus: PROJ-7 Fix login\\, again".to_string()));
+        assert!(lines.contains(
+            &"DESCRIPTION:- PROJ-7 Fix login\\, again\\n\\n25 of 25 planned minutes".to_string()
+        ));
+        assert!(lines.contains(&"SUMMARY:Short break".to_string()));
+    }
+
+    #[test]
+    fn filters_by_range_and_type() {
+        let conn = setup_test_db();
+        insert_interval(&conn, "work", "2026-03-01T09:00:00Z", "completed");
+        let kept = insert_interval(&conn, "work", "2026-03-03T09:00:00Z", "completed");
+        insert_interval(&conn, "long_break", "2026-03-03T10:00:00Z", "completed");
+
+        let ics = export_ics(
+            &conn,
+            Some("2026-03-02"),
+            Some("2026-03-04"),
+            &[IntervalType::Work],
+        )
+        .unwrap();
+        let uids: Vec<String> = unfolded(&ics)
+            .into_iter()
+            .filter(|l| l.starts_with("UID:"))
+            .collect();
+
+        assert_eq!(uids, vec![format!("UID:{}", event_uid(kept))]);
+    }
+
+    #[test]
+    fn repeated_exports_are_identical() {
+        let conn = setup_test_db();
+        insert_interval(&conn, "work", "2026-03-01T09:00:00Z", "completed");
+
+        let first = export_ics(&conn, None, None, &[]).unwrap();
+        let second = export_ics(&conn, None, None, &[]).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn feed_is_written_only_when_configured() {
+        let dir = std::env::temp_dir().join(format!("pomo-calendar-{}-feed", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db_path = dir.join("pomo.db");
+        database::initialize(&db_path).unwrap();
+        let conn = open_db(&db_path).unwrap();
+        insert_interval(&conn, "work", "2026-03-01T09:00:00Z", "completed");
+        insert_interval(&conn, "short_break", "2026-03-01T09:25:00Z", "completed");
+
+        assert_eq!(refresh_feed(&db_path).unwrap(), None);
+
+        let feed = dir.join("focus.ics");
+        conn.execute(
+            "UPDATE user_settings SET value = ?1 WHERE key = 'calendar_feed_path'",
+            [feed.to_str().unwrap()],
+        )
+        .unwrap();
+        assert_eq!(refresh_feed(&db_path).unwrap(), Some(feed.clone()));
+        let ics = std::fs::read_to_string(&feed).unwrap();
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+
+        // Rewriting replaces the read-only file
+        conn.execute(
+            "UPDATE user_settings SET value = 'true' WHERE key = 'calendar_feed_include_breaks'",
+            [],
+        )
+        .unwrap();
+        refresh_feed(&db_path).unwrap();
+        let ics = std::fs::read_to_string(&feed).unwrap();
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(!dir.join("focus.ics.tmp").exists());
+    }
+
+    #[test]
+    fn background_refresh_failures_are_emitted() {
+        use tauri::Listener;
+
+        let dir =
+            std::env::temp_dir().join(format!("pomo-calendar-{}-feed-error", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db_path = dir.join("pomo.db");
+        database::initialize(&db_path).unwrap();
+        open_db(&db_path)
+            .unwrap()
+            .execute(
+                "UPDATE user_settings SET value = ?1 WHERE key = 'calendar_feed_path'",
+                [dir.join("missing").join("focus.ics").to_str().unwrap()],
+            )
+            .unwrap();
+
+        let app = tauri::test::mock_app();
+        let (sender, errors) = std::sync::mpsc::channel();
+        app.listen_any("calendar-feed-error", move |event| {
+            let _ = sender.send(event.payload().to_string());
+        });
+        spawn_feed_refresh(app.handle(), &db_path);
+
+        let error = errors
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap();
+        assert!(error.contains("focus.ics"), "{error}");
+    }
+}
@@ -0,0 +1,652 @@
+use chrono::{Datelike, Local, NaiveDate};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::env;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::database;
+use crate::export;
+use crate::reports::{self, DailySummary, MonthlySummary, WeeklySummary};
+use crate::tasks::{self, Task};
+
+/// Bundle identifier from `tauri.conf.json`; the app keeps its data in a
+/// directory of this name.
+const APP_IDENTIFIER: &str = "com.pomo.app";
+
+const USAGE: &str = "\
+Usage: pomo-cli [--db <path>] [--json] <command>
+
+Commands:
+  tasks list [--date <day>]
+      List the tasks for a day (default: today).
+  task add <title> [--date <day>] [--jira <key>] [--tag <tag>] [--parent <id>]
+      Add a task to the end of a day's list (default: today).
+  report day|week|month [--date <day>]
+      Summarise the day, week or month containing a day.
+  export [--from <day>] [--to <day>] [--out <file>]
+      Write tasks and timer history as JSON.
+
+Days are written as YYYY-MM-DD.
+
+Options:
+  --db <path>  Database to use (default: $POMO_DB, then the app's data directory)
+  --json       Print JSON instead of text
+  -h, --help   Show this help
+";
+
+/// Options that take a value. `--db` is accepted by every command.
+const VALUE_OPTIONS: &[&str] = &["db", "date", "jira", "tag", "parent", "from", "to", "out"];
+
+// ── Arguments ───────────────────────────────────────────────
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Args {
+    positionals: Vec<String>,
+    options: Vec<(String, String)>,
+    json: bool,
+    help: bool,
+}
+
+impl Args {
+    /// Last value given for an option, so later flags override earlier ones.
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn date(&self, name: &str) -> Result<Option<NaiveDate>, String> {
+        self.option(name)
+            .map(|value| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| format!("Invalid --{name} '{value}', expected YYYY-MM-DD"))
+            })
+            .transpose()
+    }
+
+    /// Reject options the command does not understand.
+    fn allow_only(&self, allowed: &[&str]) -> Result<(), String> {
+        match self
+            .options
+            .iter()
+            .find(|(n, _)| n != "db" && !allowed.contains(&n.as_str()))
+        {
+            Some((name, _)) => Err(format!("Option --{name} does not apply to this command")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => parsed.json = true,
+            "-h" | "--help" => parsed.help = true,
+            "--" => parsed.positionals.extend(iter.by_ref().cloned()),
+            flag if flag.starts_with("--") => {
+                let (name, inline) = match flag[2..].split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (&flag[2..], None),
+                };
+                if !VALUE_OPTIONS.contains(&name) {
+                    return Err(format!("Unknown option --{name}"));
+                }
+                let value = match inline {
+                    Some(value) => value,
+                    None => iter
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| format!("Missing value for --{name}"))?,
+                };
+                parsed.options.push((name.to_string(), value));
+            }
+            _ => parsed.positionals.push(arg.clone()),
+        }
+    }
+
+    Ok(parsed)
+}
+
+// ── Commands ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Period {
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    ListTasks {
+        date: NaiveDate,
+    },
+    AddTask {
+        title: String,
+        date: NaiveDate,
+        jira_key: Option<String>,
+        tag: Option<String>,
+        parent_task_id: Option<i64>,
+    },
+    Report {
+        period: Period,
+        date: NaiveDate,
+    },
+    Export {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        out: Option<PathBuf>,
+    },
+}
+
+fn parse_command(args: &Args, today: NaiveDate) -> Result<Command, String> {
+    let words: Vec<&str> = args.positionals.iter().map(String::as_str).collect();
+
+    match words.as_slice() {
+        ["tasks", "list"] => {
+            args.allow_only(&["date"])?;
+            Ok(Command::ListTasks {
+                date: args.date("date")?.unwrap_or(today),
+            })
+        }
+        ["task", "add", title @ ..] if !title.is_empty() => {
+            args.allow_only(&["date", "jira", "tag", "parent"])?;
+            let parent_task_id = args
+                .option("parent")
+                .map(|value| {
+                    value
+                        .parse::<i64>()
+                        .map_err(|_| format!("Invalid --parent '{value}', expected a task id"))
+                })
+                .transpose()?;
+            Ok(Command::AddTask {
+                title: title.join(" "),
+                date: args.date("date")?.unwrap_or(today),
+                jira_key: args.option("jira").map(str::to_string),
+                tag: args.option("tag").map(str::to_string),
+                parent_task_id,
+            })
+        }
+        ["task", "add"] => Err("Missing task title".into()),
+        ["report", period] => {
+            args.allow_only(&["date"])?;
+            let period = match *period {
+                "day" => Period::Day,
+                "week" => Period::Week,
+                "month" => Period::Month,
+                other => return Err(format!("Unknown report period '{other}'")),
+            };
+            Ok(Command::Report {
+                period,
+                date: args.date("date")?.unwrap_or(today),
+            })
+        }
+        ["export"] => {
+            args.allow_only(&["from", "to", "out"])?;
+            Ok(Command::Export {
+                from: args.date("from")?,
+                to: args.date("to")?,
+                out: args.option("out").map(PathBuf::from),
+            })
+        }
+        [] => Err("Missing command, see --help".into()),
+        _ => Err(format!(
+            "Unknown command '{}', see --help",
+            args.positionals.join(" ")
+        )),
+    }
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn day_string(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+// ── Output ──────────────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+fn write_error(e: std::io::Error) -> String {
+    format!("Failed to write output: {e}")
+}
+
+fn write_json<T: Serialize>(out: &mut dyn Write, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize output: {e}"))?;
+    writeln!(out, "{json}").map_err(write_error)
+}
+
+fn task_line(task: &Task) -> String {
+    let marker = match task.status.as_str() {
+        "completed" => "[x]",
+        "abandoned" => "[-]",
+        _ => "[ ]",
+    };
+    let mut line = format!("{marker} #{} {}", task.id, task.title);
+    if let Some(key) = task.jira_key.as_deref().filter(|k| !k.is_empty()) {
+        line.push_str("  ");
+        line.push_str(key);
+    }
+    if let Some(tag) = task.tag.as_deref().filter(|t| !t.is_empty()) {
+        line.push_str("  (");
+        line.push_str(tag);
+        line.push(')');
+    }
+    line
+}
+
+fn write_tasks(out: &mut dyn Write, date: &str, tasks: &[Task]) -> Result<(), String> {
+    if tasks.is_empty() {
+        return writeln!(out, "No tasks on {date}").map_err(write_error);
+    }
+    writeln!(out, "Tasks on {date}").map_err(write_error)?;
+    for task in tasks.iter().filter(|t| t.parent_task_id.is_none()) {
+        writeln!(out, "  {}", task_line(task)).map_err(write_error)?;
+        for sub in tasks.iter().filter(|t| t.parent_task_id == Some(task.id)) {
+            writeln!(out, "      {}", task_line(sub)).map_err(write_error)?;
+        }
+    }
+    Ok(())
+}
+
+fn stat_line(
+    label: &str,
+    pomodoros: i64,
+    focus_minutes: i64,
+    tasks_completed: i64,
+    interruptions: i64,
+) -> String {
+    format!(
+        "  {label:<26}{pomodoros:>4} pomodoros {focus_minutes:>5} min {tasks_completed:>4} tasks {interruptions:>4} interruptions"
+    )
+}
+
+fn write_daily(out: &mut dyn Write, summary: &DailySummary) -> Result<(), String> {
+    writeln!(out, "Day {}", summary.date).map_err(write_error)?;
+    writeln!(
+        out,
+        "  Pomodoros      {} ({} min focus, {} interrupted)",
+        summary.pomodoro_count, summary.total_focus_minutes, summary.interrupted_pomodoros
+    )
+    .map_err(write_error)?;
+    writeln!(
+        out,
+        "  Tasks          {}/{} completed",
+        summary.tasks_completed, summary.tasks_total
+    )
+    .map_err(write_error)?;
+    writeln!(
+        out,
+        "  Interruptions  {} internal, {} external",
+        summary.internal_interruptions, summary.external_interruptions
+    )
+    .map_err(write_error)
+}
+
+fn write_weekly(out: &mut dyn Write, summary: &WeeklySummary) -> Result<(), String> {
+    writeln!(out, "Week {} to {}", summary.week_start, summary.week_end).map_err(write_error)?;
+    for day in &summary.daily_stats {
+        let label = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").map_or_else(
+            |_| day.date.clone(),
+            |d| d.format("%a %Y-%m-%d").to_string(),
+        );
+        writeln!(
+            out,
+            "{}",
+            stat_line(
+                &label,
+                day.pomodoro_count,
+                day.focus_minutes,
+                day.tasks_completed,
+                day.interruption_count,
+            )
+        )
+        .map_err(write_error)?;
+    }
+    writeln!(
+        out,
+        "{}",
+        stat_line(
+            "Total",
+            summary.total_pomodoros,
+            summary.total_focus_minutes,
+            summary.total_tasks_completed,
+            summary.total_interruptions,
+        )
+    )
+    .map_err(write_error)
+}
+
+fn write_monthly(out: &mut dyn Write, summary: &MonthlySummary) -> Result<(), String> {
+    writeln!(
+        out,
+        "Month {} to {}",
+        summary.month_start, summary.month_end
+    )
+    .map_err(write_error)?;
+    for week in &summary.weekly_stats {
+        writeln!(
+            out,
+            "{}",
+            stat_line(
+                &format!("{} to {}", week.week_start, week.week_end),
+                week.pomodoro_count,
+                week.focus_minutes,
+                week.tasks_completed,
+                week.interruption_count,
+            )
+        )
+        .map_err(write_error)?;
+    }
+    writeln!(
+        out,
+        "{}",
+        stat_line(
+            "Total",
+            summary.total_pomodoros,
+            summary.total_focus_minutes,
+            summary.total_tasks_completed,
+            summary.total_interruptions,
+        )
+    )
+    .map_err(write_error)
+}
+
+fn execute(
+    conn: &Connection,
+    command: &Command,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    match command {
+        Command::ListTasks { date } => {
+            let day = day_string(*date);
+            let tasks = tasks::list_by_date(conn, &day)?;
+            if json {
+                write_json(out, &tasks)
+            } else {
+                write_tasks(out, &day, &tasks)
+            }
+        }
+        Command::AddTask {
+            title,
+            date,
+            jira_key,
+            tag,
+            parent_task_id,
+        } => {
+            let task = tasks::create(
+                conn,
+                title,
+                &day_string(*date),
+                *parent_task_id,
+                jira_key.as_deref(),
+                tag.as_deref(),
+            )?;
+            if json {
+                write_json(out, &task)
+            } else {
+                writeln!(
+                    out,
+                    "Added task #{} on {}: {}",
+                    task.id, task.day_date, task.title
+                )
+                .map_err(write_error)
+            }
+        }
+        Command::Report { period, date } => match period {
+            Period::Day => {
+                let summary = reports::daily_summary(conn, &day_string(*date), false)?;
+                if json {
+                    write_json(out, &summary)
+                } else {
+                    write_daily(out, &summary)
+                }
+            }
+            Period::Week => {
+                let summary = reports::weekly_summary(conn, &day_string(*date))?;
+                if json {
+                    write_json(out, &summary)
+                } else {
+                    write_weekly(out, &summary)
+                }
+            }
+            Period::Month => {
+                let summary = reports::monthly_summary(conn, &day_string(month_start(*date)))?;
+                if json {
+                    write_json(out, &summary)
+                } else {
+                    write_monthly(out, &summary)
+                }
+            }
+        },
+        Command::Export {
+            from,
+            to,
+            out: path,
+        } => {
+            let from = from.map(day_string);
+            let to = to.map(day_string);
+            let export = export::export(conn, from.as_deref(), to.as_deref())?;
+            match path {
+                Some(path) => {
+                    let json = serde_json::to_string_pretty(&export)
+                        .map_err(|e| format!("Failed to serialize export: {e}"))?;
+                    std::fs::write(path, json)
+                        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+                    writeln!(
+                        out,
+                        "Exported {} tasks and {} intervals to {}",
+                        export.tasks.len(),
+                        export.intervals.len(),
+                        path.display()
+                    )
+                    .map_err(write_error)
+                }
+                None => write_json(out, &export),
+            }
+        }
+    }
+}
+
+// ── Entry point ─────────────────────────────────────────────
+
+/// Per-user data directory, as Tauri resolves it for `app_data_dir`.
+fn data_dir() -> Option<PathBuf> {
+    let non_empty = |key: &str| {
+        env::var_os(key)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if cfg!(target_os = "windows") {
+        non_empty("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        non_empty("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        non_empty("XDG_DATA_HOME")
+            .or_else(|| non_empty("HOME").map(|home| home.join(".local").join("share")))
+    }
+}
+
+/// The database the app itself uses, unless `POMO_DB` points elsewhere.
+fn default_db_path() -> Option<PathBuf> {
+    env::var_os("POMO_DB")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| data_dir().map(|dir| dir.join(APP_IDENTIFIER).join(database::DB_FILE_NAME)))
+}
+
+/// Run the CLI with the given arguments (without the program name),
+/// writing results to `out`.
+pub fn run(args: &[String], out: &mut dyn Write) -> Result<(), String> {
+    let args = parse_args(args)?;
+    if args.help {
+        return write!(out, "{USAGE}").map_err(write_error);
+    }
+
+    let command = parse_command(&args, Local::now().date_naive())?;
+    let db_path = match args.option("db") {
+        Some(path) => PathBuf::from(path),
+        None => {
+            default_db_path().ok_or("Could not locate the app data directory, pass --db <path>")?
+        }
+    };
+    let conn = database::open_existing(&db_path)?;
+
+    execute(&conn, &command, args.json, out)
+}
+
+/// Entry point for the `pomo-cli` binary.
+pub fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let stdout = std::io::stdout();
+    match run(&args, &mut stdout.lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("pomo-cli: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn command(line: &str) -> Result<Command, String> {
+        parse_command(&parse_args(&args(line))?, date("2026-03-04"))
+    }
+
+    fn temp_db(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("pomo-cli-{}-{name}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        database::initialize(&path).unwrap();
+        path
+    }
+
+    fn run_with_db(db: &Path, line: &str) -> Result<String, String> {
+        let mut full = vec!["--db".to_string(), db.display().to_string()];
+        full.extend(args(line));
+        let mut out = Vec::new();
+        run(&full, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn parses_task_add_with_options() {
+        assert_eq!(
+            command("task add Write the report --jira PROJ-7 --date=2026-03-10 --parent 3"),
+            Ok(Command::AddTask {
+                title: "Write the report".into(),
+                date: date("2026-03-10"),
+                jira_key: Some("PROJ-7".into()),
+                tag: None,
+                parent_task_id: Some(3),
+            })
+        );
+    }
+
+    #[test]
+    fn dates_default_to_today() {
+        assert_eq!(
+            command("tasks list"),
+            Ok(Command::ListTasks {
+                date: date("2026-03-04")
+            })
+        );
+        assert_eq!(
+            command("report week --json"),
+            Ok(Command::Report {
+                period: Period::Week,
+                date: date("2026-03-04")
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(command("").is_err());
+        assert!(command("task add").is_err());
+        assert!(command("tasks list --date 03/04/2026").is_err());
+        assert!(command("tasks list --jira X").is_err());
+        assert!(command("report year").is_err());
+        assert!(command("export --verbose").is_err());
+        assert!(command("tasks list --date").is_err());
+        assert!(command("task add x --parent abc").is_err());
+    }
+
+    #[test]
+    fn report_months_start_on_the_first() {
+        assert_eq!(month_start(date("2026-03-31")), date("2026-03-01"));
+    }
+
+    #[test]
+    fn open_existing_refuses_missing_database() {
+        let path = std::env::temp_dir().join(format!("pomo-cli-{}-missing.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let err = run_with_db(&path, "tasks list").unwrap_err();
+        assert!(err.contains("No database found"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn add_list_report_and_export_round_trip() {
+        let db = temp_db("round-trip");
+
+        let added = run_with_db(&db, "task add Review PR --date 2026-03-04 --jira PROJ-1").unwrap();
+        assert_eq!(added, "Added task #1 on 2026-03-04: Review PR\n");
+        run_with_db(&db, "task add Notes --date 2026-03-04 --parent 1").unwrap();
+
+        let listed = run_with_db(&db, "tasks list --date 2026-03-04").unwrap();
+        assert_eq!(
+            listed,
+            "Tasks on 2026-03-04\n  [ ] #1 Review PR  PROJ-1\n      [ ] #2 Notes\n"
+        );
+
+        let json = run_with_db(&db, "--json tasks list --date 2026-03-04").unwrap();
+        let tasks: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(tasks.as_array().unwrap().len(), 2);
+
+        let report = run_with_db(&db, "--json report week --date 2026-03-04").unwrap();
+        let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(report["week_start"], "2026-03-02");
+        assert_eq!(report["daily_stats"].as_array().unwrap().len(), 7);
+
+        let text = run_with_db(&db, "report week --date 2026-03-04").unwrap();
+        assert!(text.starts_with("Week 2026-03-02 to 2026-03-08\n"));
+        assert!(text.contains("Wed 2026-03-04"));
+
+        let out = db.with_extension("json");
+        let message = run_with_db(
+            &db,
+            &format!("export --from 2026-03-01 --out {}", out.display()),
+        )
+        .unwrap();
+        assert!(message.starts_with("Exported 2 tasks and 0 intervals"));
+        let export: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(export["tasks"][0]["jira_key"], "PROJ-1");
+        let _ = std::fs::remove_file(out);
+    }
+}
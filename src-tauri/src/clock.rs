@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ── Clock ───────────────────────────────────────────────────
+
+/// Source of time for the timer state machine.
+///
+/// `now` drives countdowns and must be monotonic; `utc_now` is used for
+/// the timestamps written to the database.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    fn utc_now(&self) -> DateTime<Utc>;
+
+    /// Current UTC time formatted the way timestamps are stored in the DB.
+    fn timestamp(&self) -> String {
+        self.utc_now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+    }
+}
+
+/// The real wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to, for driving the timer at
+/// simulated speed.
+pub struct ManualClock {
+    base_instant: Instant,
+    base_utc: DateTime<Utc>,
+    elapsed: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Create a clock frozen at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            base_instant: Instant::now(),
+            base_utc: start,
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().expect("clock lock poisoned") += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().expect("clock lock poisoned")
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.base_instant + self.elapsed()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        self.base_utc
+            + chrono::Duration::from_std(self.elapsed())
+                .unwrap_or_else(|_| chrono::Duration::zero())
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn manual_clock_is_frozen_until_advanced() {
+        let start = Utc.with_ymd_and_hms(2026, 2, 14, 9, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let before = clock.now();
+
+        assert_eq!(clock.now(), before);
+        assert_eq!(clock.utc_now(), start);
+        assert_eq!(clock.timestamp(), "2026-02-14T09:00:00Z");
+    }
+
+    #[test]
+    fn manual_clock_advances_both_clocks() {
+        let start = Utc.with_ymd_and_hms(2026, 2, 14, 9, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let before = clock.now();
+
+        clock.advance(Duration::from_secs(90));
+
+        assert_eq!(clock.now().duration_since(before), Duration::from_secs(90));
+        assert_eq!(clock.timestamp(), "2026-02-14T09:01:30Z");
+    }
+}
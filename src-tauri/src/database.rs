@@ -1,5 +1,6 @@
 use rusqlite::{Connection, Result as SqliteResult};
 use std::path::Path;
+use std::time::Duration;
 
 /// Schema v1: all 4 tables, trigger, indexes, and default settings.
 const MIGRATION_V1: &str = r"
@@ -73,6 +74,196 @@ CREATE INDEX idx_task_interval_links_task ON task_interval_links (task_id);
 CREATE INDEX idx_task_interval_links_interval ON task_interval_links (interval_id);
 ";
 
+/// Schema v4: pause history per interval.
+const MIGRATION_V4: &str = r"
+CREATE TABLE interval_pauses (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    interval_id      INTEGER NOT NULL REFERENCES timer_intervals(id) ON DELETE CASCADE,
+    paused_at        TEXT NOT NULL,
+    resumed_at       TEXT,
+    duration_seconds INTEGER,
+    created_at       TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX idx_interval_pauses_interval ON interval_pauses (interval_id);
+";
+
+/// Schema v5: interruptions logged against intervals, plus the void policy setting.
+const MIGRATION_V5: &str = r"
+CREATE TABLE interruptions (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    interval_id INTEGER NOT NULL REFERENCES timer_intervals(id) ON DELETE CASCADE,
+    kind        TEXT NOT NULL CHECK (kind IN ('internal', 'external')),
+    note        TEXT,
+    occurred_at TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX idx_interruptions_interval ON interruptions (interval_id);
+
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES ('interruption_void_threshold', '0', 'integer');
+";
+
+/// Schema v6: distinguish timer-recorded intervals from manual entries.
+const MIGRATION_V6: &str = r"
+ALTER TABLE timer_intervals ADD COLUMN source TEXT NOT NULL DEFAULT 'timer'
+    CHECK (source IN ('timer', 'manual'));
+";
+
+/// Schema v7: idle auto-pause settings.
+const MIGRATION_V7: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('idle_pause_enabled',     'false', 'boolean'),
+    ('idle_threshold_minutes', '5',     'integer');
+";
+
+/// Schema v8: global shortcut bindings (disabled until opted in).
+const MIGRATION_V8: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('global_shortcuts_enabled', 'false',                    'boolean'),
+    ('shortcut_toggle',          'CommandOrControl+Alt+P',   'string'),
+    ('shortcut_cancel',          'CommandOrControl+Alt+X',   'string'),
+    ('shortcut_skip',            'CommandOrControl+Alt+S',   'string'),
+    ('shortcut_extend',          'CommandOrControl+Alt+E',   'string');
+";
+
+/// Schema v9: native notification settings.
+const MIGRATION_V9: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('notifications_enabled',       'true',                                                        'boolean'),
+    ('notification_work_template',  'Work done — {cycle_position}/{long_break_frequency} until long break', 'string'),
+    ('notification_break_template', '{interval} over — time to focus',                              'string'),
+    ('notification_snooze_minutes', '5',                                                           'integer');
+";
+
+/// Schema v10: per-alarm sound files (empty means the built-in chime).
+const MIGRATION_V10: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('sound_work_end',          '', 'string'),
+    ('sound_break_end',         '', 'string'),
+    ('sound_overtime_reminder', '', 'string');
+";
+
+/// Schema v11: ambient focus sound (off until chosen) and its own volume.
+const MIGRATION_V11: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('ambient_sound',  'off', 'string'),
+    ('ambient_volume', '0.3', 'real'),
+    ('sound_ambient',  '',    'string');
+";
+
+/// Schema v12: repeating alarm and overtime reminder settings.
+const MIGRATION_V12: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('alarm_mode',                'fixed', 'string'),
+    ('alarm_repeat_seconds',      '30',    'integer'),
+    ('alarm_volume_step',         '0.1',   'real'),
+    ('overtime_reminder_minutes', '5',     'integer');
+";
+
+/// Schema v13: local control API settings (off by default).
+const MIGRATION_V13: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('api_enabled', 'false', 'boolean'),
+    ('api_port',    '27183', 'integer'),
+    ('api_token',   '',      'string');
+";
+
+/// Schema v14: user-defined event hooks and their delivery log.
+const MIGRATION_V14: &str = r"
+CREATE TABLE hooks (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    name            TEXT NOT NULL,
+    event           TEXT NOT NULL
+                    CHECK (event IN ('interval_started', 'interval_paused', 'interval_resumed',
+                                     'interval_completed', 'interval_cancelled', 'task_completed')),
+    kind            TEXT NOT NULL CHECK (kind IN ('command', 'http')),
+    target          TEXT NOT NULL,
+    enabled         INTEGER NOT NULL DEFAULT 1,
+    timeout_seconds INTEGER NOT NULL DEFAULT 10,
+    created_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at      TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX idx_hooks_event ON hooks (event);
+
+CREATE TABLE hook_deliveries (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    hook_id      INTEGER NOT NULL REFERENCES hooks(id) ON DELETE CASCADE,
+    event        TEXT NOT NULL,
+    payload      TEXT NOT NULL,
+    success      INTEGER NOT NULL,
+    status_code  INTEGER,
+    error        TEXT,
+    duration_ms  INTEGER NOT NULL,
+    delivered_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_hook_deliveries_hook ON hook_deliveries (hook_id);
+";
+
+/// Schema v15: settings for the auto-refreshed .ics calendar feed.
+const MIGRATION_V15: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('calendar_feed_path',           '',      'string'),
+    ('calendar_feed_include_breaks', 'false', 'boolean');
+";
+
+/// Schema v16: imported calendar events and the working hours they are planned around.
+const MIGRATION_V16: &str = r"
+CREATE TABLE calendar_sources (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    location         TEXT NOT NULL UNIQUE,
+    name             TEXT,
+    last_imported_at TEXT,
+    last_error       TEXT,
+    created_at       TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE TABLE calendar_events (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_id  INTEGER NOT NULL REFERENCES calendar_sources (id) ON DELETE CASCADE,
+    uid        TEXT NOT NULL,
+    summary    TEXT NOT NULL DEFAULT '',
+    start_time TEXT NOT NULL,
+    end_time   TEXT NOT NULL,
+    all_day    INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (source_id, uid, start_time)
+);
+
+CREATE INDEX idx_calendar_events_start ON calendar_events (start_time);
+
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('workday_start',           '09:00', 'string'),
+    ('workday_end',             '17:00', 'string'),
+    ('meeting_warning_enabled', 'false', 'boolean');
+";
+
+/// Schema v17: daily goals, with optional per-weekday targets.
+const MIGRATION_V17: &str = r"
+CREATE TABLE daily_goal_overrides (
+    weekday       INTEGER PRIMARY KEY CHECK (weekday BETWEEN 1 AND 7),
+    pomodoros     INTEGER CHECK (pomodoros >= 0),
+    focus_minutes INTEGER CHECK (focus_minutes >= 0)
+);
+
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('daily_goal_pomodoros',     '8',         'integer'),
+    ('daily_goal_focus_minutes', '0',         'integer'),
+    ('working_days',             '1,2,3,4,5', 'string');
+";
+
+/// Schema v18: the day weeks start on, as an ISO weekday.
+const MIGRATION_V18: &str = r"
+INSERT OR IGNORE INTO user_settings (key, value, type) VALUES
+    ('week_start_day', '1', 'integer');
+";
+
+/// Schema v19: TZIDs a calendar import could not resolve.
+const MIGRATION_V19: &str = r"
+ALTER TABLE calendar_sources ADD COLUMN unknown_time_zones TEXT NOT NULL DEFAULT '[]';
+";
+
 /// Default settings seeded on first run.
 const SEED_DEFAULT_SETTINGS: &str = r"
 INSERT INTO user_settings (key, value, type) VALUES
@@ -84,6 +275,13 @@ INSERT INTO user_settings (key, value, type) VALUES
     ('jira_api_enabled',             'false', 'boolean');
 ";
 
+/// File name of the database inside the app data directory.
+pub const DB_FILE_NAME: &str = "pomo.db";
+
+/// How long a connection from [`open_existing`] waits for another
+/// process's write lock before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Detect whether a path is inside a cloud-synced directory
 /// (`OneDrive`, `Dropbox`, `Google Drive`, `iCloud`).
 pub fn is_cloud_synced_path(path: &Path) -> bool {
@@ -109,7 +307,7 @@ fn set_pragmas(conn: &Connection, db_path: &Path) -> SqliteResult<()> {
 }
 
 /// Read the current schema version from `PRAGMA user_version`.
-fn get_user_version(conn: &Connection) -> SqliteResult<u32> {
+pub(crate) fn get_user_version(conn: &Connection) -> SqliteResult<u32> {
     conn.pragma_query_value(None, "user_version", |row| row.get(0))
 }
 
@@ -118,6 +316,22 @@ fn set_user_version(conn: &Connection, version: u32) -> SqliteResult<()> {
     conn.pragma_update(None, "user_version", version)
 }
 
+/// Apply a single migration in a transaction and bump `user_version`.
+/// If the migration fails, the database stays at the previous version.
+fn apply_migration(conn: &Connection, version: u32, sql: &str) -> SqliteResult<()> {
+    conn.execute_batch("BEGIN;")?;
+    match conn.execute_batch(sql) {
+        Ok(()) => {
+            set_user_version(conn, version)?;
+            conn.execute_batch("COMMIT;")
+        }
+        Err(e) => {
+            let _ = conn.execute_batch("ROLLBACK;");
+            Err(e)
+        }
+    }
+}
+
 /// Run all pending migrations in order, tracked by `PRAGMA user_version`.
 /// Each migration runs in a transaction. If a migration fails, the database
 /// stays at the previous version.
@@ -125,56 +339,88 @@ pub fn run_migrations(conn: &Connection) -> SqliteResult<()> {
     let current = get_user_version(conn)?;
 
     if current < 1 {
-        conn.execute_batch("BEGIN;")?;
-        match conn.execute_batch(MIGRATION_V1) {
-            Ok(()) => match conn.execute_batch(SEED_DEFAULT_SETTINGS) {
-                Ok(()) => {
-                    set_user_version(conn, 1)?;
-                    conn.execute_batch("COMMIT;")?;
-                }
-                Err(e) => {
-                    let _ = conn.execute_batch("ROLLBACK;");
-                    return Err(e);
-                }
-            },
-            Err(e) => {
-                let _ = conn.execute_batch("ROLLBACK;");
-                return Err(e);
-            }
-        }
+        apply_migration(conn, 1, &format!("{MIGRATION_V1}{SEED_DEFAULT_SETTINGS}"))?;
     }
 
     if current < 2 {
-        conn.execute_batch("BEGIN;")?;
-        match conn.execute_batch(
+        apply_migration(
+            conn,
+            2,
             "ALTER TABLE tasks ADD COLUMN completed_in_pomodoro INTEGER;\n\
              INSERT OR IGNORE INTO user_settings (key, value, type) VALUES ('break_overtime_enabled', 'false', 'boolean');",
-        ) {
-            Ok(()) => {
-                set_user_version(conn, 2)?;
-                conn.execute_batch("COMMIT;")?;
-            }
-            Err(e) => {
-                let _ = conn.execute_batch("ROLLBACK;");
-                return Err(e);
-            }
-        }
+        )?;
     }
 
     if current < 3 {
-        conn.execute_batch("BEGIN;")?;
-        match conn.execute_batch(
+        apply_migration(
+            conn,
+            3,
             "INSERT OR IGNORE INTO user_settings (key, value, type) VALUES ('alarm_volume', '0.6', 'real');",
-        ) {
-            Ok(()) => {
-                set_user_version(conn, 3)?;
-                conn.execute_batch("COMMIT;")?;
-            }
-            Err(e) => {
-                let _ = conn.execute_batch("ROLLBACK;");
-                return Err(e);
-            }
-        }
+        )?;
+    }
+
+    if current < 4 {
+        apply_migration(conn, 4, MIGRATION_V4)?;
+    }
+
+    if current < 5 {
+        apply_migration(conn, 5, MIGRATION_V5)?;
+    }
+
+    if current < 6 {
+        apply_migration(conn, 6, MIGRATION_V6)?;
+    }
+
+    if current < 7 {
+        apply_migration(conn, 7, MIGRATION_V7)?;
+    }
+
+    if current < 8 {
+        apply_migration(conn, 8, MIGRATION_V8)?;
+    }
+
+    if current < 9 {
+        apply_migration(conn, 9, MIGRATION_V9)?;
+    }
+
+    if current < 10 {
+        apply_migration(conn, 10, MIGRATION_V10)?;
+    }
+
+    if current < 11 {
+        apply_migration(conn, 11, MIGRATION_V11)?;
+    }
+
+    if current < 12 {
+        apply_migration(conn, 12, MIGRATION_V12)?;
+    }
+
+    if current < 13 {
+        apply_migration(conn, 13, MIGRATION_V13)?;
+    }
+
+    if current < 14 {
+        apply_migration(conn, 14, MIGRATION_V14)?;
+    }
+
+    if current < 15 {
+        apply_migration(conn, 15, MIGRATION_V15)?;
+    }
+
+    if current < 16 {
+        apply_migration(conn, 16, MIGRATION_V16)?;
+    }
+
+    if current < 17 {
+        apply_migration(conn, 17, MIGRATION_V17)?;
+    }
+
+    if current < 18 {
+        apply_migration(conn, 18, MIGRATION_V18)?;
+    }
+
+    if current < 19 {
+        apply_migration(conn, 19, MIGRATION_V19)?;
     }
 
     Ok(())
@@ -201,6 +447,30 @@ pub fn initialize(db_path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Open an existing database for use alongside a running app instance.
+///
+/// Unlike [`initialize`] this never creates the file. Writers wait on a
+/// busy database instead of failing, so short CLI edits interleave safely
+/// with the app's own connections.
+pub fn open_existing(db_path: &Path) -> Result<Connection, String> {
+    if !db_path.is_file() {
+        return Err(format!("No database found at {}", db_path.display()));
+    }
+
+    let conn = Connection::open(db_path)
+        .map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| format!("Failed to set busy timeout: {e}"))?;
+
+    set_pragmas(&conn, db_path)
+        .map_err(|e| format!("Failed to set database pragmas: {e}"))?;
+
+    run_migrations(&conn)
+        .map_err(|e| format!("Failed to run database migrations: {e}"))?;
+
+    Ok(conn)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,15 +496,15 @@ mod tests {
         let conn = Connection::open_in_memory().unwrap();
         run_migrations(&conn).unwrap();
         run_migrations(&conn).unwrap();
-        assert_eq!(get_user_version(&conn).unwrap(), 3);
+        assert_eq!(get_user_version(&conn).unwrap(), 19);
     }
 
     #[test]
-    fn user_version_is_set_to_3_after_migration() {
+    fn user_version_is_set_to_19_after_migration() {
         let conn = Connection::open_in_memory().unwrap();
         assert_eq!(get_user_version(&conn).unwrap(), 0);
         run_migrations(&conn).unwrap();
-        assert_eq!(get_user_version(&conn).unwrap(), 3);
+        assert_eq!(get_user_version(&conn).unwrap(), 19);
     }
 
     // ── Table existence tests ───────────────────────────────────
@@ -254,6 +524,13 @@ mod tests {
         assert!(tables.contains(&"timer_intervals".to_string()));
         assert!(tables.contains(&"tasks".to_string()));
         assert!(tables.contains(&"task_interval_links".to_string()));
+        assert!(tables.contains(&"interval_pauses".to_string()));
+        assert!(tables.contains(&"interruptions".to_string()));
+        assert!(tables.contains(&"hooks".to_string()));
+        assert!(tables.contains(&"hook_deliveries".to_string()));
+        assert!(tables.contains(&"calendar_sources".to_string()));
+        assert!(tables.contains(&"calendar_events".to_string()));
+        assert!(tables.contains(&"daily_goal_overrides".to_string()));
     }
 
     // ── Index existence tests ───────────────────────────────────
@@ -277,6 +554,11 @@ mod tests {
             "idx_tasks_jira_key",
             "idx_task_interval_links_task",
             "idx_task_interval_links_interval",
+            "idx_interval_pauses_interval",
+            "idx_interruptions_interval",
+            "idx_hooks_event",
+            "idx_hook_deliveries_hook",
+            "idx_calendar_events_start",
         ];
 
         for name in expected {
@@ -358,7 +640,7 @@ mod tests {
         let count: u32 = conn
             .query_row("SELECT COUNT(*) FROM user_settings", [], |row| row.get(0))
             .unwrap();
-        assert_eq!(count, 8, "Expected 8 default settings");
+        assert_eq!(count, 42, "Expected 42 default settings");
     }
 
     #[test]
@@ -739,7 +1021,7 @@ mod tests {
         conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
         run_migrations(&conn).unwrap();
         run_migrations(&conn).unwrap();
-        assert_eq!(get_user_version(&conn).unwrap(), 3);
+        assert_eq!(get_user_version(&conn).unwrap(), 19);
 
         // Column still exists and setting still present
         let count: u32 = conn
@@ -806,7 +1088,7 @@ mod tests {
         conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
         run_migrations(&conn).unwrap();
         run_migrations(&conn).unwrap();
-        assert_eq!(get_user_version(&conn).unwrap(), 3);
+        assert_eq!(get_user_version(&conn).unwrap(), 19);
 
         let count: u32 = conn
             .query_row(
@@ -817,4 +1099,350 @@ mod tests {
             .unwrap();
         assert_eq!(count, 1);
     }
+
+    // ── Migration v4 tests ────────────────────────────────────
+
+    #[test]
+    fn migration_v4_pause_cascades_with_interval() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, planned_duration_seconds) VALUES ('work', '2026-02-14T09:00:00Z', 1500)",
+            [],
+        )
+        .unwrap();
+        let interval_id: i64 = conn.last_insert_rowid();
+
+        conn.execute(
+            "INSERT INTO interval_pauses (interval_id, paused_at, resumed_at, duration_seconds) VALUES (?1, '2026-02-14T09:10:00Z', '2026-02-14T09:12:00Z', 120)",
+            [interval_id],
+        )
+        .unwrap();
+
+        conn.execute("DELETE FROM timer_intervals WHERE id = ?1", [interval_id])
+            .unwrap();
+
+        let count: u32 = conn
+            .query_row("SELECT COUNT(*) FROM interval_pauses", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0, "Pauses should be cascade-deleted with interval");
+    }
+
+    // ── Migration v5 tests ────────────────────────────────────
+
+    #[test]
+    fn migration_v5_seeds_interruption_void_threshold() {
+        let conn = setup_test_db();
+        let value: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'interruption_void_threshold'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(value, "0");
+    }
+
+    #[test]
+    fn interruption_kind_check_constraint() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, planned_duration_seconds) VALUES ('work', '2026-02-14T09:00:00Z', 1500)",
+            [],
+        )
+        .unwrap();
+        let interval_id: i64 = conn.last_insert_rowid();
+
+        let result = conn.execute(
+            "INSERT INTO interruptions (interval_id, kind, occurred_at) VALUES (?1, 'invalid', '2026-02-14T09:05:00Z')",
+            [interval_id],
+        );
+        assert!(result.is_err(), "Invalid interruption kind should be rejected");
+    }
+
+    // ── Migration v6 tests ────────────────────────────────────
+
+    #[test]
+    fn migration_v6_defaults_interval_source_to_timer() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, planned_duration_seconds) VALUES ('work', '2026-02-14T09:00:00Z', 1500)",
+            [],
+        )
+        .unwrap();
+        let source: String = conn
+            .query_row(
+                "SELECT source FROM timer_intervals WHERE id = ?1",
+                [conn.last_insert_rowid()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(source, "timer");
+    }
+
+    #[test]
+    fn interval_source_check_constraint() {
+        let conn = setup_test_db();
+        let result = conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, planned_duration_seconds, source) VALUES ('work', '2026-02-14T09:00:00Z', 1500, 'invalid')",
+            [],
+        );
+        assert!(result.is_err(), "Invalid interval source should be rejected");
+    }
+
+    // ── Migration v7 tests ────────────────────────────────────
+
+    #[test]
+    fn migration_v7_seeds_idle_settings() {
+        let conn = setup_test_db();
+        let enabled: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'idle_pause_enabled'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let threshold: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'idle_threshold_minutes'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(enabled, "false");
+        assert_eq!(threshold, "5");
+    }
+
+    #[test]
+    fn migration_v8_seeds_shortcut_settings() {
+        let conn = setup_test_db();
+        let enabled: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'global_shortcuts_enabled'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let bindings: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM user_settings WHERE key LIKE 'shortcut_%' AND value != ''",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(enabled, "false");
+        assert_eq!(bindings, 4);
+    }
+
+    #[test]
+    fn migration_v9_seeds_notification_settings() {
+        let conn = setup_test_db();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM user_settings WHERE key LIKE 'notification%'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn migration_v10_defaults_sounds_to_builtin_chime() {
+        let conn = setup_test_db();
+        let custom: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM user_settings WHERE key LIKE 'sound_%' AND value != ''",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let slots: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM user_settings \
+                 WHERE key IN ('sound_work_end', 'sound_break_end', 'sound_overtime_reminder')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(slots, 3);
+        assert_eq!(custom, 0);
+    }
+
+    #[test]
+    fn migration_v11_seeds_ambient_settings_off() {
+        let conn = setup_test_db();
+        let sound: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'ambient_sound'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let volume_type: String = conn
+            .query_row(
+                "SELECT type FROM user_settings WHERE key = 'ambient_volume'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(sound, "off");
+        assert_eq!(volume_type, "real");
+    }
+
+    #[test]
+    fn migration_v12_keeps_fixed_alarm_mode() {
+        let conn = setup_test_db();
+        let mode: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'alarm_mode'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let reminder: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'overtime_reminder_minutes'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(mode, "fixed");
+        assert_eq!(reminder, "5");
+    }
+
+    #[test]
+    fn migration_v13_leaves_control_api_disabled() {
+        let conn = setup_test_db();
+        let enabled: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'api_enabled'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let token: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'api_token'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(enabled, "false");
+        assert!(token.is_empty());
+    }
+
+    #[test]
+    fn migration_v14_hook_deliveries_cascade_with_hook() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO hooks (name, event, kind, target) VALUES ('Log', 'interval_completed', 'command', 'true')",
+            [],
+        )
+        .unwrap();
+        let hook_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO hook_deliveries (hook_id, event, payload, success, duration_ms, delivered_at) \
+             VALUES (?1, 'interval_completed', '{}', 1, 5, '2026-02-14T09:00:00Z')",
+            [hook_id],
+        )
+        .unwrap();
+
+        let bad_event = conn.execute(
+            "INSERT INTO hooks (name, event, kind, target) VALUES ('Bad', 'lunch', 'command', 'true')",
+            [],
+        );
+        assert!(bad_event.is_err());
+
+        conn.execute("DELETE FROM hooks WHERE id = ?1", [hook_id])
+            .unwrap();
+        let count: u32 = conn
+            .query_row("SELECT COUNT(*) FROM hook_deliveries", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0, "Deliveries should be cascade-deleted with hook");
+    }
+
+    #[test]
+    fn migration_v15_leaves_calendar_feed_off() {
+        let conn = setup_test_db();
+        let path: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'calendar_feed_path'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn migration_v16_calendar_events_cascade_with_source() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO calendar_sources (location) VALUES ('/tmp/work.ics')",
+            [],
+        )
+        .unwrap();
+        let source_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO calendar_events (source_id, uid, summary, start_time, end_time)
+             VALUES (?1, 'standup', 'Standup', '2026-03-02T09:00:00Z', '2026-03-02T09:15:00Z')",
+            [source_id],
+        )
+        .unwrap();
+
+        conn.execute("DELETE FROM calendar_sources WHERE id = ?1", [source_id])
+            .unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM calendar_events", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn migration_v17_rejects_invalid_goal_weekday() {
+        let conn = setup_test_db();
+        assert!(conn
+            .execute(
+                "INSERT INTO daily_goal_overrides (weekday, pomodoros) VALUES (7, 4)",
+                [],
+            )
+            .is_ok());
+        assert!(conn
+            .execute(
+                "INSERT INTO daily_goal_overrides (weekday, pomodoros) VALUES (0, 4)",
+                [],
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn migration_v18_seeds_week_start_day() {
+        let conn = setup_test_db();
+        let value: String = conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = 'week_start_day'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(value, "1");
+    }
+
+    #[test]
+    fn migration_v19_defaults_unknown_time_zones_to_empty() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO calendar_sources (location) VALUES ('/tmp/work.ics')",
+            [],
+        )
+        .unwrap();
+        let zones: String = conn
+            .query_row(
+                "SELECT unknown_time_zones FROM calendar_sources",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(zones, "[]");
+    }
 }
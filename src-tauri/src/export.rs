@@ -0,0 +1,128 @@
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::database;
+use crate::intervals::{self, Interval};
+use crate::tasks::{self, Task};
+
+/// Bounds used when an export range is left open.
+pub(crate) const EARLIEST_DATE: &str = "0000-01-01";
+pub(crate) const LATEST_DATE: &str = "9999-12-31";
+
+// ── Types ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskIntervalLink {
+    pub task_id: i64,
+    pub interval_id: i64,
+}
+
+/// A self-contained snapshot of tasks and timer history.
+#[derive(Debug, Clone, Serialize)]
+pub struct Export {
+    pub exported_at: String,
+    pub schema_version: u32,
+    pub tasks: Vec<Task>,
+    pub intervals: Vec<Interval>,
+    pub task_interval_links: Vec<TaskIntervalLink>,
+}
+
+// ── Export ───────────────────────────────────────────────────
+
+fn query_links(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<TaskIntervalLink>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT til.task_id, til.interval_id
+             FROM task_interval_links til
+             JOIN tasks t ON t.id = til.task_id
+             WHERE t.day_date BETWEEN ?1 AND ?2
+             ORDER BY til.task_id, til.interval_id",
+        )
+        .map_err(|e| format!("Failed to prepare link query: {e}"))?;
+
+    let links = stmt
+        .query_map([start_date, end_date], |row| {
+            Ok(TaskIntervalLink {
+                task_id: row.get(0)?,
+                interval_id: row.get(1)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query links: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read links: {e}"))?;
+
+    Ok(links)
+}
+
+/// Collect tasks, intervals and their links for days in `[from, to]`.
+/// Either bound may be omitted to export everything on that side.
+pub fn export(conn: &Connection, from: Option<&str>, to: Option<&str>) -> Result<Export, String> {
+    let start = from.unwrap_or(EARLIEST_DATE);
+    let end = to.unwrap_or(LATEST_DATE);
+
+    Ok(Export {
+        exported_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        schema_version: database::get_user_version(conn)
+            .map_err(|e| format!("Failed to read schema version: {e}"))?,
+        tasks: tasks::list_between(conn, start, end)?,
+        intervals: intervals::list_between(conn, start, end)?,
+        task_interval_links: query_links(conn, start, end)?,
+    })
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        database::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn insert_interval(conn: &Connection, start_time: &str) -> i64 {
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds, planned_duration_seconds, status)
+             VALUES ('work', ?1, ?1, 1500, 1500, 'completed')",
+            [start_time],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn export_filters_by_day() {
+        let conn = setup_test_db();
+        let early = tasks::create(&conn, "Early", "2026-03-01", None, None, None).unwrap();
+        let late = tasks::create(&conn, "Late", "2026-03-05", None, Some("PROJ-1"), None).unwrap();
+        let early_interval = insert_interval(&conn, "2026-03-01T09:00:00Z");
+        let late_interval = insert_interval(&conn, "2026-03-05T09:00:00Z");
+        tasks::link_to_interval(&conn, &[early.id], early_interval).unwrap();
+        tasks::link_to_interval(&conn, &[late.id], late_interval).unwrap();
+
+        let all = export(&conn, None, None).unwrap();
+        assert_eq!(all.tasks.len(), 2);
+        assert_eq!(all.intervals.len(), 2);
+        assert_eq!(all.task_interval_links.len(), 2);
+        assert_eq!(
+            all.schema_version,
+            database::get_user_version(&conn).unwrap()
+        );
+
+        let late_only = export(&conn, Some("2026-03-02"), None).unwrap();
+        assert_eq!(late_only.tasks.len(), 1);
+        assert_eq!(late_only.tasks[0].jira_key.as_deref(), Some("PROJ-1"));
+        assert_eq!(late_only.intervals.len(), 1);
+        assert_eq!(late_only.intervals[0].id, late_interval);
+        assert_eq!(late_only.task_interval_links.len(), 1);
+        assert_eq!(late_only.task_interval_links[0].task_id, late.id);
+    }
+}
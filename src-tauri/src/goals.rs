@@ -0,0 +1,634 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::timer::AppState;
+
+const DEFAULT_WORKING_DAYS: [u32; 5] = [1, 2, 3, 4, 5];
+
+// ── Types ────────────────────────────────────────────────────
+
+/// Targets for one day. A target of 0 is not tracked; when both are
+/// tracked, both must be reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GoalTarget {
+    pub pomodoros: i64,
+    pub focus_minutes: i64,
+}
+
+impl GoalTarget {
+    fn is_set(self) -> bool {
+        self.pomodoros > 0 || self.focus_minutes > 0
+    }
+
+    fn is_met(self, pomodoros: i64, focus_minutes: i64) -> bool {
+        pomodoros >= self.pomodoros && focus_minutes >= self.focus_minutes
+    }
+}
+
+/// A weekday whose target differs from the default. `None` keeps the
+/// default for that part of the goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GoalOverride {
+    /// ISO weekday, 1 = Monday through 7 = Sunday.
+    pub weekday: u32,
+    pub pomodoros: Option<i64>,
+    pub focus_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GoalConfig {
+    pub default: GoalTarget,
+    /// ISO weekdays that have a goal. Other days never break a streak.
+    pub working_days: Vec<u32>,
+    pub overrides: Vec<GoalOverride>,
+    /// ISO weekday weeks start on, for weekly reports and week streaks.
+    pub week_start_day: u32,
+}
+
+/// How far a day got towards its goal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GoalProgress {
+    pub pomodoro_target: Option<i64>,
+    pub focus_minutes_target: Option<i64>,
+    pub pomodoros: i64,
+    pub focus_minutes: i64,
+    pub met: bool,
+    /// Progress towards the furthest-off target, capped at 100.
+    pub percent: i64,
+}
+
+/// Consecutive working days on which the goal was met, and consecutive
+/// weeks in which every goal was met.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct GoalStreaks {
+    pub current: i64,
+    pub longest: i64,
+    pub current_weeks: i64,
+    pub longest_weeks: i64,
+}
+
+/// Completed pomodoros and focus minutes per day.
+pub(crate) type DailyTotals = BTreeMap<NaiveDate, (i64, i64)>;
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+fn read_setting(conn: &Connection, key: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM user_settings WHERE key = ?1",
+        [key],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+fn parse_working_days(value: &str) -> Vec<u32> {
+    let mut days: Vec<u32> = value
+        .split(',')
+        .filter_map(|d| d.trim().parse().ok())
+        .filter(|d| (1..=7).contains(d))
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+/// The ISO weekday in `value`, falling back to Monday.
+fn parse_week_start_day(value: &str) -> u32 {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|d| (1..=7).contains(d))
+        .unwrap_or(1)
+}
+
+fn query_overrides(conn: &Connection) -> Result<Vec<GoalOverride>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT weekday, pomodoros, focus_minutes FROM daily_goal_overrides ORDER BY weekday",
+        )
+        .map_err(|e| format!("Failed to prepare goal query: {e}"))?;
+
+    let overrides = stmt
+        .query_map([], |row| {
+            Ok(GoalOverride {
+                weekday: row.get(0)?,
+                pomodoros: row.get(1)?,
+                focus_minutes: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query goals: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read goals: {e}"))?;
+
+    Ok(overrides)
+}
+
+/// Completed work per day for days in `[start, end]`.
+pub(crate) fn daily_totals(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+) -> Result<DailyTotals, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT date(start_time), COUNT(*), COALESCE(SUM(duration_seconds), 0)
+             FROM timer_intervals
+             WHERE status = 'completed' AND interval_type = 'work'
+               AND date(start_time) BETWEEN ?1 AND ?2
+             GROUP BY date(start_time)",
+        )
+        .map_err(|e| format!("Failed to prepare daily totals query: {e}"))?;
+
+    let rows = stmt
+        .query_map([start, end], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query daily totals: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read daily totals: {e}"))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(day, count, seconds)| {
+            let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?;
+            Some((day, (count, seconds / 60)))
+        })
+        .collect())
+}
+
+// ── Goals ───────────────────────────────────────────────────
+
+impl GoalConfig {
+    pub fn load(conn: &Connection) -> Result<Self, String> {
+        let read = |key: &str| {
+            read_setting(conn, key)
+                .and_then(|v| v.parse::<i64>().ok())
+                .map_or(0, |v| v.max(0))
+        };
+        Ok(Self {
+            default: GoalTarget {
+                pomodoros: read("daily_goal_pomodoros"),
+                focus_minutes: read("daily_goal_focus_minutes"),
+            },
+            working_days: read_setting(conn, "working_days")
+                .map_or_else(|| DEFAULT_WORKING_DAYS.to_vec(), |v| parse_working_days(&v)),
+            overrides: query_overrides(conn)?,
+            week_start_day: read_setting(conn, "week_start_day")
+                .map_or(1, |v| parse_week_start_day(&v)),
+        })
+    }
+
+    pub fn week_start(&self) -> Weekday {
+        (1..self.week_start_day).fold(Weekday::Mon, |day, _| day.succ())
+    }
+
+    /// First day of the week containing `day`.
+    pub fn start_of_week(&self, day: NaiveDate) -> NaiveDate {
+        day - Duration::days(i64::from(day.weekday().days_since(self.week_start())))
+    }
+
+    /// The goal for `day`, or `None` on a day off or a day without targets.
+    pub fn target_for(&self, day: NaiveDate) -> Option<GoalTarget> {
+        let weekday = day.weekday().number_from_monday();
+        if !self.working_days.contains(&weekday) {
+            return None;
+        }
+        let mut target = self.default;
+        if let Some(o) = self.overrides.iter().find(|o| o.weekday == weekday) {
+            target.pomodoros = o.pomodoros.unwrap_or(target.pomodoros);
+            target.focus_minutes = o.focus_minutes.unwrap_or(target.focus_minutes);
+        }
+        target.is_set().then_some(target)
+    }
+
+    /// Whether `day` met its goal; `None` if it had none.
+    pub(crate) fn met_on(&self, day: NaiveDate, totals: &DailyTotals) -> Option<bool> {
+        let (pomodoros, focus_minutes) = totals.get(&day).copied().unwrap_or_default();
+        self.target_for(day)
+            .map(|target| target.is_met(pomodoros, focus_minutes))
+    }
+
+    /// Goal-days and goals met for each day in `[start, end]`.
+    pub(crate) fn attainment(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        totals: &DailyTotals,
+    ) -> (i64, i64) {
+        start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter_map(|d| self.met_on(d, totals))
+            .fold((0, 0), |(days, met), m| (days + 1, met + i64::from(m)))
+    }
+}
+
+fn percent_of(done: i64, target: i64) -> i64 {
+    if target <= 0 {
+        100
+    } else {
+        (done * 100 / target).min(100)
+    }
+}
+
+pub fn progress(target: GoalTarget, pomodoros: i64, focus_minutes: i64) -> GoalProgress {
+    let tracked = |t: i64| (t > 0).then_some(t);
+    GoalProgress {
+        pomodoro_target: tracked(target.pomodoros),
+        focus_minutes_target: tracked(target.focus_minutes),
+        pomodoros,
+        focus_minutes,
+        met: target.is_met(pomodoros, focus_minutes),
+        percent: percent_of(pomodoros, target.pomodoros)
+            .min(percent_of(focus_minutes, target.focus_minutes)),
+    }
+}
+
+/// Streaks as of `as_of`. Days off and days without a goal are skipped
+/// rather than ending a streak, and an unmet `as_of` on or after `today`
+/// is still in progress, so it doesn't end one either. A week only adds
+/// to the week streak once it is over, but a miss ends it straight away.
+pub fn streaks(
+    conn: &Connection,
+    config: &GoalConfig,
+    as_of: NaiveDate,
+    today: NaiveDate,
+) -> Result<GoalStreaks, String> {
+    let end = as_of.format("%Y-%m-%d").to_string();
+    let totals = daily_totals(conn, "0000-01-01", &end)?;
+    let Some(first) = totals.keys().next().copied() else {
+        return Ok(GoalStreaks::default());
+    };
+
+    let week_start = config.week_start();
+    let mut streaks = GoalStreaks::default();
+    // Whether every goal so far this week was met; `None` before the first
+    let mut week: Option<bool> = None;
+    let mut day = config.start_of_week(first);
+    while day <= as_of {
+        match config.met_on(day, &totals) {
+            Some(true) => {
+                streaks.current += 1;
+                streaks.longest = streaks.longest.max(streaks.current);
+                week.get_or_insert(true);
+            }
+            Some(false) if day < as_of || as_of < today => {
+                streaks.current = 0;
+                week = Some(false);
+            }
+            _ => {}
+        }
+        day += Duration::days(1);
+        if day.weekday() == week_start {
+            match week.take() {
+                Some(true) => {
+                    streaks.current_weeks += 1;
+                    streaks.longest_weeks = streaks.longest_weeks.max(streaks.current_weeks);
+                }
+                Some(false) => streaks.current_weeks = 0,
+                None => {}
+            }
+        }
+    }
+    if week == Some(false) {
+        streaks.current_weeks = 0;
+    }
+    Ok(streaks)
+}
+
+fn validate_target(value: Option<i64>) -> Result<(), String> {
+    match value {
+        Some(v) if v < 0 => Err("Goal targets must not be negative".into()),
+        _ => Ok(()),
+    }
+}
+
+fn upsert_override(conn: &Connection, goal: GoalOverride) -> Result<GoalConfig, String> {
+    if !(1..=7).contains(&goal.weekday) {
+        return Err(format!(
+            "Weekday must be 1 (Monday) to 7 (Sunday), got {}",
+            goal.weekday
+        ));
+    }
+    validate_target(goal.pomodoros)?;
+    validate_target(goal.focus_minutes)?;
+    conn.execute(
+        "INSERT INTO daily_goal_overrides (weekday, pomodoros, focus_minutes) VALUES (?1, ?2, ?3)
+         ON CONFLICT (weekday) DO UPDATE SET pomodoros = excluded.pomodoros,
+                                             focus_minutes = excluded.focus_minutes",
+        rusqlite::params![goal.weekday, goal.pomodoros, goal.focus_minutes],
+    )
+    .map_err(|e| format!("Failed to save goal: {e}"))?;
+    GoalConfig::load(conn)
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_goal_config(state: tauri::State<'_, AppState>) -> Result<GoalConfig, String> {
+    GoalConfig::load(&open_db(&state.db_path)?)
+}
+
+/// Set a weekday-specific goal. The default goal and working days are
+/// ordinary settings.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn set_goal_override(
+    state: tauri::State<'_, AppState>,
+    weekday: u32,
+    pomodoros: Option<i64>,
+    focus_minutes: Option<i64>,
+) -> Result<GoalConfig, String> {
+    upsert_override(
+        &open_db(&state.db_path)?,
+        GoalOverride {
+            weekday,
+            pomodoros,
+            focus_minutes,
+        },
+    )
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn clear_goal_override(
+    state: tauri::State<'_, AppState>,
+    weekday: u32,
+) -> Result<GoalConfig, String> {
+    let conn = open_db(&state.db_path)?;
+    conn.execute(
+        "DELETE FROM daily_goal_overrides WHERE weekday = ?1",
+        [weekday],
+    )
+    .map_err(|e| format!("Failed to clear goal: {e}"))?;
+    GoalConfig::load(&conn)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        database::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn set(conn: &Connection, key: &str, value: &str) {
+        conn.execute(
+            "UPDATE user_settings SET value = ?2 WHERE key = ?1",
+            [key, value],
+        )
+        .unwrap();
+    }
+
+    /// Record `count` completed 25-minute pomodoros on `day`.
+    fn work(conn: &Connection, day: &str, count: i64) {
+        for n in 0..count {
+            conn.execute(
+                "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds, planned_duration_seconds, status)
+                 VALUES ('work', ?1, ?1, 1500, 1500, 'completed')",
+                [format!("{day}T{:02}:00:00Z", 8 + n)],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn defaults_are_eight_pomodoros_on_weekdays() {
+        let config = GoalConfig::load(&setup_test_db()).unwrap();
+        assert_eq!(
+            config.target_for(date("2026-03-02")),
+            Some(GoalTarget {
+                pomodoros: 8,
+                focus_minutes: 0
+            })
+        );
+        // Saturday
+        assert_eq!(config.target_for(date("2026-03-07")), None);
+    }
+
+    #[test]
+    fn weekday_overrides_replace_parts_of_the_goal() {
+        let conn = setup_test_db();
+        set(&conn, "daily_goal_focus_minutes", "120");
+        let config = upsert_override(
+            &conn,
+            GoalOverride {
+                weekday: 5,
+                pomodoros: Some(4),
+                focus_minutes: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.target_for(date("2026-03-06")),
+            Some(GoalTarget {
+                pomodoros: 4,
+                focus_minutes: 120
+            })
+        );
+        // A zero override switches the goal off for that weekday
+        let config = upsert_override(
+            &conn,
+            GoalOverride {
+                weekday: 3,
+                pomodoros: Some(0),
+                focus_minutes: Some(0),
+            },
+        )
+        .unwrap();
+        assert_eq!(config.target_for(date("2026-03-04")), None);
+
+        assert!(upsert_override(
+            &conn,
+            GoalOverride {
+                weekday: 8,
+                pomodoros: Some(1),
+                focus_minutes: None
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn progress_tracks_the_furthest_target() {
+        let target = GoalTarget {
+            pomodoros: 8,
+            focus_minutes: 100,
+        };
+        let p = progress(target, 6, 150);
+        assert!(!p.met);
+        assert_eq!(p.percent, 75);
+
+        let p = progress(
+            GoalTarget {
+                pomodoros: 0,
+                focus_minutes: 100,
+            },
+            1,
+            100,
+        );
+        assert!(p.met);
+        assert_eq!(p.pomodoro_target, None);
+        assert_eq!(p.percent, 100);
+    }
+
+    #[test]
+    fn weekends_do_not_break_streaks() {
+        let conn = setup_test_db();
+        set(&conn, "daily_goal_pomodoros", "2");
+        // Thu, Fri met; weekend off; Mon met; Tue missed; Wed, Thu met
+        work(&conn, "2026-02-26", 2);
+        work(&conn, "2026-02-27", 3);
+        work(&conn, "2026-02-28", 1);
+        work(&conn, "2026-03-02", 2);
+        work(&conn, "2026-03-03", 1);
+        work(&conn, "2026-03-04", 2);
+        work(&conn, "2026-03-05", 2);
+        let config = GoalConfig::load(&conn).unwrap();
+        let today = date("2026-03-20");
+
+        let monday = streaks(&conn, &config, date("2026-03-02"), today).unwrap();
+        assert_eq!(
+            monday,
+            GoalStreaks {
+                current: 3,
+                longest: 3,
+                current_weeks: 0,
+                longest_weeks: 0,
+            }
+        );
+        let tuesday = streaks(&conn, &config, date("2026-03-03"), today).unwrap();
+        assert_eq!(
+            tuesday,
+            GoalStreaks {
+                current: 0,
+                longest: 3,
+                current_weeks: 0,
+                longest_weeks: 0,
+            }
+        );
+        let thursday = streaks(&conn, &config, date("2026-03-05"), today).unwrap();
+        assert_eq!(
+            thursday,
+            GoalStreaks {
+                current: 2,
+                longest: 3,
+                current_weeks: 0,
+                longest_weeks: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn today_in_progress_keeps_the_streak() {
+        let conn = setup_test_db();
+        set(&conn, "daily_goal_pomodoros", "1");
+        work(&conn, "2026-03-02", 1);
+        work(&conn, "2026-03-03", 1);
+        let config = GoalConfig::load(&conn).unwrap();
+
+        let wednesday = date("2026-03-04");
+        assert_eq!(
+            streaks(&conn, &config, wednesday, wednesday)
+                .unwrap()
+                .current,
+            2
+        );
+        // Once the day is over, missing it ends the streak
+        assert_eq!(
+            streaks(&conn, &config, wednesday, date("2026-03-05"))
+                .unwrap()
+                .current,
+            0
+        );
+    }
+
+    #[test]
+    fn attainment_counts_goal_days() {
+        let conn = setup_test_db();
+        set(&conn, "daily_goal_pomodoros", "1");
+        set(&conn, "working_days", "1,2,3,4,5,6");
+        work(&conn, "2026-03-02", 1);
+        work(&conn, "2026-03-07", 1);
+        work(&conn, "2026-03-08", 1);
+        let config = GoalConfig::load(&conn).unwrap();
+        let totals = daily_totals(&conn, "2026-03-02", "2026-03-08").unwrap();
+
+        assert_eq!(
+            config.attainment(date("2026-03-02"), date("2026-03-08"), &totals),
+            (6, 2)
+        );
+    }
+
+    #[test]
+    fn week_streaks_follow_the_week_start_day() {
+        let conn = setup_test_db();
+        set(&conn, "daily_goal_pomodoros", "1");
+        set(&conn, "working_days", "1,2,3,4,5,6,7");
+        // Every day from Sunday Mar 1 to Saturday Mar 14
+        let first = date("2026-03-01");
+        for offset in 0..14 {
+            let day = first + Duration::days(offset);
+            work(&conn, &day.format("%Y-%m-%d").to_string(), 1);
+        }
+        let (as_of, today) = (date("2026-03-14"), date("2026-03-20"));
+
+        // Sunday weeks: Mar 1-7 and Mar 8-14 are both complete
+        set(&conn, "week_start_day", "7");
+        let config = GoalConfig::load(&conn).unwrap();
+        assert_eq!(config.week_start(), Weekday::Sun);
+        let sunday = streaks(&conn, &config, as_of, today).unwrap();
+        assert_eq!((sunday.current_weeks, sunday.longest_weeks), (2, 2));
+        assert_eq!(sunday.current, 14);
+
+        // Monday weeks: Feb 23-Mar 1 missed six days, Mar 9-15 isn't over
+        set(&conn, "week_start_day", "1");
+        let config = GoalConfig::load(&conn).unwrap();
+        let monday = streaks(&conn, &config, as_of, today).unwrap();
+        assert_eq!((monday.current_weeks, monday.longest_weeks), (1, 1));
+        assert_eq!(monday.current, 14);
+
+        // A miss ends the week streak before the week is over
+        let config = GoalConfig::load(&conn).unwrap();
+        let after = streaks(&conn, &config, date("2026-03-15"), today).unwrap();
+        assert_eq!((after.current_weeks, after.longest_weeks), (0, 1));
+    }
+
+    #[test]
+    fn week_start_day_falls_back_to_monday() {
+        let conn = setup_test_db();
+        for (value, expected) in [("3", Weekday::Wed), ("0", Weekday::Mon), ("x", Weekday::Mon)] {
+            set(&conn, "week_start_day", value);
+            assert_eq!(GoalConfig::load(&conn).unwrap().week_start(), expected);
+        }
+        let config = GoalConfig::load(&conn).unwrap();
+        assert_eq!(config.start_of_week(date("2026-01-01")), date("2025-12-29"));
+    }
+}
@@ -0,0 +1,945 @@
+use chrono::Utc;
+use rusqlite::types::Type;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::tasks::Task;
+use crate::timer::{AppState, IntervalType, TimerCompletePayload, TimerStatus};
+
+const DEFAULT_TIMEOUT_SECONDS: u32 = 10;
+const MAX_TIMEOUT_SECONDS: u32 = 300;
+/// Deliveries kept per hook; older entries are pruned as new ones arrive.
+const DELIVERY_LOG_LIMIT: i64 = 100;
+/// Longest error text stored for a failed delivery.
+const MAX_ERROR_CHARS: usize = 500;
+
+// ── Types ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    IntervalStarted,
+    IntervalPaused,
+    IntervalResumed,
+    IntervalCompleted,
+    IntervalCancelled,
+    TaskCompleted,
+}
+
+impl HookEvent {
+    const ALL: [Self; 6] = [
+        Self::IntervalStarted,
+        Self::IntervalPaused,
+        Self::IntervalResumed,
+        Self::IntervalCompleted,
+        Self::IntervalCancelled,
+        Self::TaskCompleted,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::IntervalStarted => "interval_started",
+            Self::IntervalPaused => "interval_paused",
+            Self::IntervalResumed => "interval_resumed",
+            Self::IntervalCompleted => "interval_completed",
+            Self::IntervalCancelled => "interval_cancelled",
+            Self::TaskCompleted => "task_completed",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookKind {
+    /// Run `target` through the system shell with the payload on stdin.
+    Command,
+    /// POST the payload as JSON to the `target` URL.
+    Http,
+}
+
+impl HookKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::Http => "http",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "command" => Some(Self::Command),
+            "http" => Some(Self::Http),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Hook {
+    pub id: i64,
+    pub name: String,
+    pub event: HookEvent,
+    pub kind: HookKind,
+    pub target: String,
+    pub enabled: bool,
+    pub timeout_seconds: u32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookDelivery {
+    pub id: i64,
+    pub hook_id: i64,
+    pub event: String,
+    pub payload: String,
+    pub success: bool,
+    /// HTTP status for URL hooks, exit code for commands.
+    pub status_code: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub delivered_at: String,
+}
+
+/// What an event is about: the interval, shaped like the `timer-complete`
+/// event, or the task.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookSubject {
+    Interval(TimerCompletePayload),
+    Task(Box<Task>),
+}
+
+/// The JSON document sent to every hook.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookPayload {
+    pub event: HookEvent,
+    pub occurred_at: String,
+    #[serde(flatten)]
+    pub subject: HookSubject,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Outcome {
+    success: bool,
+    status_code: Option<i64>,
+    error: Option<String>,
+}
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+const HOOK_COLUMNS: &str =
+    "id, name, event, kind, target, enabled, timeout_seconds, created_at, updated_at";
+
+fn row_to_hook(row: &rusqlite::Row<'_>) -> rusqlite::Result<Hook> {
+    let event: String = row.get(2)?;
+    let kind: String = row.get(3)?;
+    Ok(Hook {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        event: HookEvent::parse(&event).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(2, Type::Text, event.into())
+        })?,
+        kind: HookKind::parse(&kind)
+            .ok_or_else(|| rusqlite::Error::FromSqlConversionFailure(3, Type::Text, kind.into()))?,
+        target: row.get(4)?,
+        enabled: row.get(5)?,
+        timeout_seconds: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn get_hook(conn: &Connection, id: i64) -> Result<Hook, String> {
+    conn.query_row(
+        &format!("SELECT {HOOK_COLUMNS} FROM hooks WHERE id = ?1"),
+        [id],
+        row_to_hook,
+    )
+    .map_err(|e| format!("Hook not found: {e}"))
+}
+
+fn query_hooks(conn: &Connection) -> Result<Vec<Hook>, String> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT {HOOK_COLUMNS} FROM hooks ORDER BY id"))
+        .map_err(|e| format!("Failed to prepare hook query: {e}"))?;
+
+    let hooks = stmt
+        .query_map([], row_to_hook)
+        .map_err(|e| format!("Failed to query hooks: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read hooks: {e}"))?;
+
+    Ok(hooks)
+}
+
+fn query_enabled_hooks(conn: &Connection, event: HookEvent) -> Result<Vec<Hook>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {HOOK_COLUMNS} FROM hooks WHERE event = ?1 AND enabled = 1 ORDER BY id"
+        ))
+        .map_err(|e| format!("Failed to prepare hook query: {e}"))?;
+
+    let hooks = stmt
+        .query_map([event.as_str()], row_to_hook)
+        .map_err(|e| format!("Failed to query hooks: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read hooks: {e}"))?;
+
+    Ok(hooks)
+}
+
+fn validate_hook(
+    name: &str,
+    kind: HookKind,
+    target: &str,
+    timeout_seconds: u32,
+) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Hook name must not be empty".into());
+    }
+    if target.trim().is_empty() {
+        return Err("Hook target must not be empty".into());
+    }
+    if kind == HookKind::Http && !(target.starts_with("http://") || target.starts_with("https://"))
+    {
+        return Err("URL hooks need an http:// or https:// address".into());
+    }
+    if timeout_seconds == 0 || timeout_seconds > MAX_TIMEOUT_SECONDS {
+        return Err(format!(
+            "Timeout must be between 1 and {MAX_TIMEOUT_SECONDS} seconds"
+        ));
+    }
+    Ok(())
+}
+
+fn insert_hook(
+    conn: &Connection,
+    name: &str,
+    event: HookEvent,
+    kind: HookKind,
+    target: &str,
+    timeout_seconds: u32,
+) -> Result<Hook, String> {
+    validate_hook(name, kind, target, timeout_seconds)?;
+    conn.execute(
+        "INSERT INTO hooks (name, event, kind, target, timeout_seconds) VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![name.trim(), event.as_str(), kind.as_str(), target.trim(), timeout_seconds],
+    )
+    .map_err(|e| format!("Failed to create hook: {e}"))?;
+    get_hook(conn, conn.last_insert_rowid())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_hook_row(
+    conn: &Connection,
+    id: i64,
+    name: Option<&str>,
+    event: Option<HookEvent>,
+    kind: Option<HookKind>,
+    target: Option<&str>,
+    enabled: Option<bool>,
+    timeout_seconds: Option<u32>,
+) -> Result<Hook, String> {
+    let existing = get_hook(conn, id)?;
+    let name = name.unwrap_or(&existing.name);
+    let event = event.unwrap_or(existing.event);
+    let kind = kind.unwrap_or(existing.kind);
+    let target = target.unwrap_or(&existing.target);
+    let enabled = enabled.unwrap_or(existing.enabled);
+    let timeout_seconds = timeout_seconds.unwrap_or(existing.timeout_seconds);
+    validate_hook(name, kind, target, timeout_seconds)?;
+
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    conn.execute(
+        "UPDATE hooks SET name = ?1, event = ?2, kind = ?3, target = ?4, enabled = ?5, \
+         timeout_seconds = ?6, updated_at = ?7 WHERE id = ?8",
+        rusqlite::params![
+            name.trim(),
+            event.as_str(),
+            kind.as_str(),
+            target.trim(),
+            enabled,
+            timeout_seconds,
+            now,
+            id
+        ],
+    )
+    .map_err(|e| format!("Failed to update hook: {e}"))?;
+    get_hook(conn, id)
+}
+
+fn record_delivery(
+    conn: &Connection,
+    hook: &Hook,
+    payload: &str,
+    outcome: &Outcome,
+    duration_ms: i64,
+) -> Result<HookDelivery, String> {
+    let delivered_at = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    conn.execute(
+        "INSERT INTO hook_deliveries (hook_id, event, payload, success, status_code, error, duration_ms, delivered_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            hook.id,
+            hook.event.as_str(),
+            payload,
+            outcome.success,
+            outcome.status_code,
+            outcome.error,
+            duration_ms,
+            delivered_at
+        ],
+    )
+    .map_err(|e| format!("Failed to record hook delivery: {e}"))?;
+    let id = conn.last_insert_rowid();
+
+    conn.execute(
+        "DELETE FROM hook_deliveries WHERE hook_id = ?1 AND id NOT IN \
+         (SELECT id FROM hook_deliveries WHERE hook_id = ?1 ORDER BY id DESC LIMIT ?2)",
+        rusqlite::params![hook.id, DELIVERY_LOG_LIMIT],
+    )
+    .map_err(|e| format!("Failed to prune hook deliveries: {e}"))?;
+
+    Ok(HookDelivery {
+        id,
+        hook_id: hook.id,
+        event: hook.event.as_str().to_string(),
+        payload: payload.to_string(),
+        success: outcome.success,
+        status_code: outcome.status_code,
+        error: outcome.error.clone(),
+        duration_ms,
+        delivered_at,
+    })
+}
+
+fn query_deliveries(
+    conn: &Connection,
+    hook_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<HookDelivery>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, hook_id, event, payload, success, status_code, error, duration_ms, delivered_at \
+             FROM hook_deliveries WHERE ?1 IS NULL OR hook_id = ?1 \
+             ORDER BY id DESC LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to prepare delivery query: {e}"))?;
+
+    let deliveries = stmt
+        .query_map(rusqlite::params![hook_id, limit], |row| {
+            Ok(HookDelivery {
+                id: row.get(0)?,
+                hook_id: row.get(1)?,
+                event: row.get(2)?,
+                payload: row.get(3)?,
+                success: row.get(4)?,
+                status_code: row.get(5)?,
+                error: row.get(6)?,
+                duration_ms: row.get(7)?,
+                delivered_at: row.get(8)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query deliveries: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read deliveries: {e}"))?;
+
+    Ok(deliveries)
+}
+
+// ── Delivery ────────────────────────────────────────────────
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(MAX_ERROR_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn shell_command(script: &str) -> Command {
+    if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(script);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+}
+
+/// Run a command hook with the payload on stdin and in `POMO_PAYLOAD`.
+async fn run_command(hook: &Hook, payload: &str) -> Outcome {
+    let failed = |error: String| Outcome {
+        success: false,
+        status_code: None,
+        error: Some(error),
+    };
+
+    let mut child = match shell_command(&hook.target)
+        .env("POMO_EVENT", hook.event.as_str())
+        .env("POMO_PAYLOAD", payload)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return failed(format!("Failed to start command: {e}")),
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores stdin may exit before reading it
+        let _ = stdin.write_all(payload.as_bytes()).await;
+    }
+
+    let timeout = Duration::from_secs(u64::from(hook.timeout_seconds));
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => Outcome {
+            success: output.status.success(),
+            status_code: output.status.code().map(i64::from),
+            error: (!output.status.success())
+                .then(|| truncate(&String::from_utf8_lossy(&output.stderr)))
+                .filter(|e| !e.is_empty()),
+        },
+        Ok(Err(e)) => failed(format!("Command failed: {e}")),
+        Err(_) => failed(format!("Timed out after {}s", hook.timeout_seconds)),
+    }
+}
+
+/// POST the payload to a URL hook.
+async fn post_json(hook: &Hook, payload: &str) -> Outcome {
+    let client = reqwest::Client::new();
+    let request = client
+        .post(&hook.target)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Pomo-Event", hook.event.as_str())
+        .body(payload.to_string())
+        .timeout(Duration::from_secs(u64::from(hook.timeout_seconds)));
+
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status();
+            Outcome {
+                success: status.is_success(),
+                status_code: Some(i64::from(status.as_u16())),
+                error: (!status.is_success()).then(|| format!("HTTP {status}")),
+            }
+        }
+        Err(e) if e.is_timeout() => Outcome {
+            success: false,
+            status_code: None,
+            error: Some(format!("Timed out after {}s", hook.timeout_seconds)),
+        },
+        Err(e) => Outcome {
+            success: false,
+            status_code: None,
+            error: Some(truncate(&format!("Request failed: {e}"))),
+        },
+    }
+}
+
+async fn deliver(db_path: &Path, hook: &Hook, payload: &str) -> Result<HookDelivery, String> {
+    let started = Instant::now();
+    let outcome = match hook.kind {
+        HookKind::Command => run_command(hook, payload).await,
+        HookKind::Http => post_json(hook, payload).await,
+    };
+    let duration_ms = i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX);
+    record_delivery(&open_db(db_path)?, hook, payload, &outcome, duration_ms)
+}
+
+/// Deliver a payload to every enabled hook for its event, concurrently,
+/// and wait for all of them to finish or time out.
+async fn run_hooks(db_path: PathBuf, hooks: Vec<Hook>, payload: String) {
+    let deliveries = hooks.into_iter().map(|hook| {
+        let db_path = db_path.clone();
+        let payload = payload.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = deliver(&db_path, &hook, &payload).await {
+                eprintln!("Hook '{}' delivery not logged: {e}", hook.name);
+            }
+        })
+    });
+    for delivery in deliveries.collect::<Vec<_>>() {
+        let _ = delivery.await;
+    }
+}
+
+fn payload_json(event: HookEvent, subject: HookSubject) -> Result<String, String> {
+    serde_json::to_string(&HookPayload {
+        event,
+        occurred_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        subject,
+    })
+    .map_err(|e| format!("Failed to serialize hook payload: {e}"))
+}
+
+/// Run the hooks for an event in the background. Never blocks on delivery
+/// and never fails the caller; problems end up in the delivery log.
+pub(crate) fn fire(db_path: &Path, event: HookEvent, subject: HookSubject) {
+    let hooks = match open_db(db_path).and_then(|conn| query_enabled_hooks(&conn, event)) {
+        Ok(hooks) if hooks.is_empty() => return,
+        Ok(hooks) => hooks,
+        Err(e) => {
+            eprintln!("Failed to read hooks: {e}");
+            return;
+        }
+    };
+    match payload_json(event, subject) {
+        Ok(payload) => {
+            tauri::async_runtime::spawn(run_hooks(db_path.to_path_buf(), hooks, payload));
+        }
+        Err(e) => eprintln!("{e}"),
+    }
+}
+
+/// Fire an interval event from a timer status. `interval_id` is passed
+/// separately because a cancelled timer has already cleared its own.
+pub(crate) fn fire_interval(
+    db_path: &Path,
+    event: HookEvent,
+    interval_id: i64,
+    status: &TimerStatus,
+) {
+    fire(
+        db_path,
+        event,
+        HookSubject::Interval(TimerCompletePayload {
+            interval_id,
+            interval_type: status.interval_type,
+            completed_work_count: status.completed_work_count,
+            overtime: status.overtime,
+        }),
+    );
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn list_hooks(state: tauri::State<'_, AppState>) -> Result<Vec<Hook>, String> {
+    query_hooks(&open_db(&state.db_path)?)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn create_hook(
+    state: tauri::State<'_, AppState>,
+    name: String,
+    event: HookEvent,
+    kind: HookKind,
+    target: String,
+    timeout_seconds: Option<u32>,
+) -> Result<Hook, String> {
+    insert_hook(
+        &open_db(&state.db_path)?,
+        &name,
+        event,
+        kind,
+        &target,
+        timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS),
+    )
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+#[tauri::command]
+pub fn update_hook(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    name: Option<String>,
+    event: Option<HookEvent>,
+    kind: Option<HookKind>,
+    target: Option<String>,
+    enabled: Option<bool>,
+    timeout_seconds: Option<u32>,
+) -> Result<Hook, String> {
+    update_hook_row(
+        &open_db(&state.db_path)?,
+        id,
+        name.as_deref(),
+        event,
+        kind,
+        target.as_deref(),
+        enabled,
+        timeout_seconds,
+    )
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn delete_hook(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    open_db(&state.db_path)?
+        .execute("DELETE FROM hooks WHERE id = ?1", [id])
+        .map_err(|e| format!("Failed to delete hook: {e}"))?;
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_hook_deliveries(
+    state: tauri::State<'_, AppState>,
+    hook_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<HookDelivery>, String> {
+    query_deliveries(
+        &open_db(&state.db_path)?,
+        hook_id,
+        limit.unwrap_or(DELIVERY_LOG_LIMIT),
+    )
+}
+
+/// Send a sample payload to one hook, even if disabled, and return the
+/// logged delivery.
+#[tauri::command]
+pub async fn test_hook(state: tauri::State<'_, AppState>, id: i64) -> Result<HookDelivery, String> {
+    let db_path = state.db_path.clone();
+    let hook = get_hook(&open_db(&db_path)?, id)?;
+    let subject = match hook.event {
+        HookEvent::TaskCompleted => HookSubject::Task(Box::new(Task {
+            id: 0,
+            title: "Sample task".into(),
+            day_date: Utc::now().format("%Y-%m-%d").to_string(),
+            status: "completed".into(),
+            parent_task_id: None,
+            linked_from_task_id: None,
+            jira_key: None,
+            tag: None,
+            position: 0,
+            created_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            updated_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            completed_in_pomodoro: Some(1),
+        })),
+        _ => HookSubject::Interval(TimerCompletePayload {
+            interval_id: 0,
+            interval_type: IntervalType::Work,
+            completed_work_count: 1,
+            overtime: false,
+        }),
+    };
+    let payload = payload_json(hook.event, subject)?;
+    deliver(&db_path, &hook, &payload).await
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    fn setup_test_db_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pomo-hooks-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("pomo.db");
+        crate::database::initialize(&path).unwrap();
+        path
+    }
+
+    fn interval_subject() -> HookSubject {
+        HookSubject::Interval(TimerCompletePayload {
+            interval_id: 7,
+            interval_type: IntervalType::Work,
+            completed_work_count: 3,
+            overtime: false,
+        })
+    }
+
+    /// Serve one HTTP request, reply with `status`, and hand back the
+    /// request head and body.
+    fn http_listener(status: &'static str) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            )
+            .unwrap();
+            tx.send((head, String::from_utf8(body).unwrap())).unwrap();
+        });
+        (url, rx)
+    }
+
+    fn run(db_path: &Path, event: HookEvent, subject: HookSubject) -> Vec<HookDelivery> {
+        let conn = open_db(db_path).unwrap();
+        let hooks = query_enabled_hooks(&conn, event).unwrap();
+        let payload = payload_json(event, subject).unwrap();
+        tauri::async_runtime::block_on(run_hooks(db_path.to_path_buf(), hooks, payload));
+        query_deliveries(&conn, None, 100).unwrap()
+    }
+
+    #[test]
+    fn payload_flattens_subject() {
+        let payload: serde_json::Value = serde_json::from_str(
+            &payload_json(HookEvent::IntervalCompleted, interval_subject()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(payload["event"], "interval_completed");
+        assert_eq!(payload["interval"]["interval_id"], 7);
+        assert_eq!(payload["interval"]["interval_type"], "work");
+        assert_eq!(payload["interval"]["completed_work_count"], 3);
+        assert!(payload["occurred_at"].as_str().unwrap().ends_with('Z'));
+    }
+
+    #[test]
+    fn hook_validation() {
+        let conn = open_db(&setup_test_db_file("validation")).unwrap();
+        let event = HookEvent::IntervalStarted;
+        assert!(insert_hook(&conn, " ", event, HookKind::Command, "true", 10).is_err());
+        assert!(insert_hook(&conn, "Slack", event, HookKind::Http, "hooks.slack.com", 10).is_err());
+        assert!(insert_hook(&conn, "Slow", event, HookKind::Command, "true", 0).is_err());
+        assert!(insert_hook(&conn, "Slow", event, HookKind::Command, "true", 301).is_err());
+
+        let hook = insert_hook(&conn, "Log", event, HookKind::Command, " true ", 10).unwrap();
+        assert_eq!(hook.target, "true");
+        assert!(hook.enabled);
+
+        let hook =
+            update_hook_row(&conn, hook.id, None, None, None, None, Some(false), None).unwrap();
+        assert!(!hook.enabled);
+        assert!(query_enabled_hooks(&conn, event).unwrap().is_empty());
+        assert!(update_hook_row(
+            &conn,
+            hook.id,
+            None,
+            None,
+            Some(HookKind::Http),
+            None,
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn http_hook_posts_payload_and_logs_delivery() {
+        let db_path = setup_test_db_file("http");
+        let (url, received) = http_listener("200 OK");
+        let conn = open_db(&db_path).unwrap();
+        let hook = insert_hook(
+            &conn,
+            "Status",
+            HookEvent::IntervalCompleted,
+            HookKind::Http,
+            &url,
+            5,
+        )
+        .unwrap();
+        // Hooks for other events are not run
+        insert_hook(
+            &conn,
+            "Other",
+            HookEvent::IntervalStarted,
+            HookKind::Http,
+            &url,
+            5,
+        )
+        .unwrap();
+
+        let deliveries = run(&db_path, HookEvent::IntervalCompleted, interval_subject());
+
+        let (head, body) = received.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(head.starts_with("POST /hook HTTP/1.1"));
+        assert!(head
+            .to_ascii_lowercase()
+            .contains("content-type: application/json"));
+        assert!(head
+            .to_ascii_lowercase()
+            .contains("x-pomo-event: interval_completed"));
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["interval"]["interval_id"], 7);
+
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].hook_id, hook.id);
+        assert!(deliveries[0].success);
+        assert_eq!(deliveries[0].status_code, Some(200));
+        assert_eq!(deliveries[0].event, "interval_completed");
+    }
+
+    #[test]
+    fn http_error_status_is_a_failed_delivery() {
+        let db_path = setup_test_db_file("http-error");
+        let (url, _received) = http_listener("503 Service Unavailable");
+        let conn = open_db(&db_path).unwrap();
+        insert_hook(
+            &conn,
+            "Down",
+            HookEvent::TaskCompleted,
+            HookKind::Http,
+            &url,
+            5,
+        )
+        .unwrap();
+
+        let task = crate::tasks::create(&conn, "Ship it", "2026-03-04", None, None, None).unwrap();
+        let deliveries = run(
+            &db_path,
+            HookEvent::TaskCompleted,
+            HookSubject::Task(Box::new(task)),
+        );
+
+        assert!(!deliveries[0].success);
+        assert_eq!(deliveries[0].status_code, Some(503));
+        assert!(deliveries[0].payload.contains("\"title\":\"Ship it\""));
+    }
+
+    #[test]
+    fn unresponsive_url_times_out() {
+        let db_path = setup_test_db_file("http-timeout");
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let conn = open_db(&db_path).unwrap();
+        insert_hook(
+            &conn,
+            "Hung",
+            HookEvent::IntervalStarted,
+            HookKind::Http,
+            &url,
+            1,
+        )
+        .unwrap();
+
+        let started = Instant::now();
+        let deliveries = run(&db_path, HookEvent::IntervalStarted, interval_subject());
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!deliveries[0].success);
+        assert_eq!(deliveries[0].error.as_deref(), Some("Timed out after 1s"));
+        drop(listener);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_hook_receives_payload_and_exit_code() {
+        let db_path = setup_test_db_file("command");
+        let out = db_path.with_file_name("payload.json");
+        let conn = open_db(&db_path).unwrap();
+        insert_hook(
+            &conn,
+            "Save",
+            HookEvent::IntervalCancelled,
+            HookKind::Command,
+            &format!(
+                "cat > '{}' && test \"$POMO_EVENT\" = interval_cancelled",
+                out.display()
+            ),
+            5,
+        )
+        .unwrap();
+        insert_hook(
+            &conn,
+            "Fail",
+            HookEvent::IntervalCancelled,
+            HookKind::Command,
+            "echo nope >&2; exit 3",
+            5,
+        )
+        .unwrap();
+
+        let mut deliveries = run(&db_path, HookEvent::IntervalCancelled, interval_subject());
+        deliveries.sort_by_key(|d| d.hook_id);
+
+        assert!(deliveries[0].success, "{deliveries:?}");
+        assert_eq!(deliveries[0].status_code, Some(0));
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(written["event"], "interval_cancelled");
+
+        assert!(!deliveries[1].success);
+        assert_eq!(deliveries[1].status_code, Some(3));
+        assert_eq!(deliveries[1].error.as_deref(), Some("nope"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn slow_command_is_killed_at_timeout() {
+        let db_path = setup_test_db_file("command-timeout");
+        let conn = open_db(&db_path).unwrap();
+        insert_hook(
+            &conn,
+            "Sleepy",
+            HookEvent::IntervalPaused,
+            HookKind::Command,
+            "sleep 30",
+            1,
+        )
+        .unwrap();
+
+        let started = Instant::now();
+        let deliveries = run(&db_path, HookEvent::IntervalPaused, interval_subject());
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(deliveries[0].error.as_deref(), Some("Timed out after 1s"));
+    }
+
+    #[test]
+    fn delivery_log_is_pruned_per_hook() {
+        let conn = open_db(&setup_test_db_file("prune")).unwrap();
+        let hook = insert_hook(
+            &conn,
+            "Log",
+            HookEvent::IntervalStarted,
+            HookKind::Command,
+            "true",
+            5,
+        )
+        .unwrap();
+        let outcome = Outcome {
+            success: true,
+            status_code: Some(0),
+            error: None,
+        };
+        for _ in 0..DELIVERY_LOG_LIMIT + 5 {
+            record_delivery(&conn, &hook, "{}", &outcome, 1).unwrap();
+        }
+
+        let deliveries = query_deliveries(&conn, Some(hook.id), 1_000).unwrap();
+        assert_eq!(
+            deliveries.len(),
+            usize::try_from(DELIVERY_LOG_LIMIT).unwrap()
+        );
+        assert_eq!(deliveries[0].id, DELIVERY_LOG_LIMIT + 5);
+    }
+}
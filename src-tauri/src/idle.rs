@@ -0,0 +1,459 @@
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::timer::{self, AppState, TimerStatus};
+
+/// How often the watcher samples the idle source.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// ── Idle sources ────────────────────────────────────────────
+
+/// Reports how long the user has been inactive.
+pub trait IdleSource: Send + Sync {
+    /// Time since the last user input, or `None` if unavailable.
+    fn idle_duration(&self) -> Option<Duration>;
+}
+
+/// Fallback for platforms without an idle API; never reports idleness.
+pub struct UnsupportedIdleSource;
+
+impl IdleSource for UnsupportedIdleSource {
+    fn idle_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(windows)]
+pub struct WindowsIdleSource;
+
+#[cfg(windows)]
+impl IdleSource for WindowsIdleSource {
+    fn idle_duration(&self) -> Option<Duration> {
+        use windows_sys::Win32::System::SystemInformation::GetTickCount;
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: u32::try_from(std::mem::size_of::<LASTINPUTINFO>()).ok()?,
+            dwTime: 0,
+        };
+        // SAFETY: `info` is a writable LASTINPUTINFO with `cbSize` set.
+        if unsafe { GetLastInputInfo(&mut info) } == 0 {
+            return None;
+        }
+        // SAFETY: GetTickCount has no preconditions.
+        let now = unsafe { GetTickCount() };
+        Some(Duration::from_millis(u64::from(
+            now.wrapping_sub(info.dwTime),
+        )))
+    }
+}
+
+/// The idle source for the current platform.
+pub fn platform_source() -> Box<dyn IdleSource> {
+    #[cfg(windows)]
+    {
+        Box::new(WindowsIdleSource)
+    }
+    #[cfg(not(windows))]
+    {
+        Box::new(UnsupportedIdleSource)
+    }
+}
+
+// ── Event payloads ──────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimerIdlePayload {
+    pub interval_id: i64,
+    /// Idle time credited back on pause, or total time away on return.
+    pub idle_ms: u64,
+}
+
+#[derive(Debug)]
+enum IdleEvent {
+    Paused(TimerIdlePayload),
+    Returned(TimerIdlePayload),
+}
+
+// ── Settings ────────────────────────────────────────────────
+
+/// Read the idle threshold, or `None` when idle pausing is disabled.
+fn read_idle_threshold(db_path: &Path) -> Option<Duration> {
+    let conn = Connection::open(db_path).ok()?;
+    let read = |key: &str| {
+        conn.query_row(
+            "SELECT value FROM user_settings WHERE key = ?1",
+            [key],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+    };
+
+    if read("idle_pause_enabled").as_deref() != Some("true") {
+        return None;
+    }
+    let minutes: u64 = read("idle_threshold_minutes")?.parse().ok()?;
+    (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+}
+
+// ── Watcher ─────────────────────────────────────────────────
+
+/// Sample the idle source once and apply any resulting transition.
+fn poll_idle(state: &AppState) -> Result<Option<IdleEvent>, String> {
+    let needs_check = {
+        let timer = state.timer.lock().map_err(|e| format!("Lock error: {e}"))?;
+        timer.idle_candidate().is_some() || timer.idle_away().is_some()
+    };
+    if !needs_check {
+        return Ok(None);
+    }
+
+    let Some(threshold) = read_idle_threshold(&state.db_path) else {
+        return Ok(None);
+    };
+    let Some(idle) = state.idle_source.idle_duration() else {
+        return Ok(None);
+    };
+
+    if idle >= threshold {
+        let candidate = state
+            .timer
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?
+            .idle_candidate();
+        let Some(interval_id) = candidate else {
+            return Ok(None);
+        };
+        timer::idle_pause_active_interval(state, idle)?;
+
+        return Ok(Some(IdleEvent::Paused(TimerIdlePayload {
+            interval_id,
+            idle_ms: u64::try_from(idle.as_millis()).unwrap_or(u64::MAX),
+        })));
+    }
+
+    let mut timer = state.timer.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let Some(away) = timer.idle_away() else {
+        return Ok(None);
+    };
+    let Some((interval_id, _)) = timer.active_interval() else {
+        return Ok(None);
+    };
+    if !timer.mark_idle_return_notified() {
+        return Ok(None);
+    }
+    Ok(Some(IdleEvent::Returned(TimerIdlePayload {
+        interval_id,
+        idle_ms: u64::try_from(away.as_millis()).unwrap_or(u64::MAX),
+    })))
+}
+
+/// Watch for inactivity while a work interval runs. Emits
+/// `timer-idle-paused` on auto-pause and `timer-idle-returned` once the
+/// user is back, so the UI can ask whether to keep the time away. Failed
+/// polls are reported as `idle-error`.
+pub fn spawn_idle_watcher<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+
+            let state = app.state::<AppState>();
+            match poll_idle(&state) {
+                Ok(Some(IdleEvent::Paused(payload))) => {
+                    let _ = app.emit("timer-idle-paused", payload);
+                    if let Ok(timer) = state.timer.lock() {
+                        timer::emit_state_changed(&app, &timer.status());
+                    }
+                }
+                Ok(Some(IdleEvent::Returned(payload))) => {
+                    let _ = app.emit("timer-idle-returned", payload);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    let _ = app.emit("idle-error", e);
+                }
+            }
+        }
+    });
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+/// Resume after an idle pause, either keeping the time away as focus
+/// time or discarding it.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn resolve_idle<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+    keep: bool,
+) -> Result<TimerStatus, String> {
+    let status = timer::resolve_idle_interval(&state, keep)?;
+    timer::emit_state_changed(&app, &status);
+    timer::spawn_tick_task(app);
+    Ok(status)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{IntervalType, TimerState};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Test idle source whose reading is set by the test.
+    struct FakeIdleSource(Mutex<Option<Duration>>);
+
+    impl FakeIdleSource {
+        fn set(&self, idle: Option<Duration>) {
+            *self.0.lock().unwrap() = idle;
+        }
+    }
+
+    impl IdleSource for FakeIdleSource {
+        fn idle_duration(&self) -> Option<Duration> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn setup(name: &str) -> (AppState, Arc<FakeIdleSource>) {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("pomo-idle-{}-{name}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "UPDATE user_settings SET value = 'true' WHERE key = 'idle_pause_enabled';
+             UPDATE user_settings SET value = '5' WHERE key = 'idle_threshold_minutes';
+             INSERT INTO timer_intervals (id, interval_type, start_time, planned_duration_seconds)
+             VALUES (1, 'work', '2026-02-14T09:00:00Z', 1500);",
+        )
+        .unwrap();
+
+        let fake = Arc::new(FakeIdleSource(Mutex::new(None)));
+        let state = AppState::new(path).with_idle_source(fake.clone());
+        (state, fake)
+    }
+
+    fn pause_count(state: &AppState) -> i64 {
+        Connection::open(&state.db_path)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM interval_pauses", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn unsupported_source_reports_nothing() {
+        assert!(UnsupportedIdleSource.idle_duration().is_none());
+    }
+
+    #[test]
+    fn idle_threshold_disabled_by_default() {
+        let path =
+            std::env::temp_dir().join(format!("pomo-idle-{}-defaults.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+        assert!(read_idle_threshold(&path).is_none());
+    }
+
+    #[test]
+    fn below_threshold_keeps_running() {
+        let (state, fake) = setup("below");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::Work, 1500, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(1)));
+
+        assert!(poll_idle(&state).unwrap().is_none());
+        assert_eq!(
+            state.timer.lock().unwrap().status().state,
+            TimerState::Running
+        );
+    }
+
+    #[test]
+    fn threshold_changes_apply_on_the_next_poll() {
+        let (state, fake) = setup("threshold-change");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::Work, 1500, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(6)));
+        let set_threshold = |minutes: &str| {
+            Connection::open(&state.db_path)
+                .unwrap()
+                .execute(
+                    "UPDATE user_settings SET value = ?1 WHERE key = 'idle_threshold_minutes'",
+                    [minutes],
+                )
+                .unwrap();
+        };
+
+        set_threshold("10");
+        assert!(poll_idle(&state).unwrap().is_none());
+        set_threshold("5");
+        assert!(matches!(
+            poll_idle(&state).unwrap(),
+            Some(IdleEvent::Paused(_))
+        ));
+    }
+
+    #[test]
+    fn idle_past_threshold_pauses_and_credits_time() {
+        let (state, fake) = setup("pause");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::Work, 1500, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(6)));
+
+        let event = poll_idle(&state).unwrap();
+        assert!(matches!(event, Some(IdleEvent::Paused(ref p)) if p.idle_ms == 360_000));
+
+        let status = state.timer.lock().unwrap().status();
+        assert_eq!(status.state, TimerState::Paused);
+        assert!(status.idle_paused);
+        // Idle time is credited back, capped at the planned duration
+        assert_eq!(status.remaining_ms, 1_500_000);
+        assert_eq!(pause_count(&state), 1);
+    }
+
+    #[test]
+    fn failed_idle_pause_write_leaves_the_timer_running() {
+        let (state, fake) = setup("pause-fails");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::Work, 1500, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(6)));
+        Connection::open(&state.db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TRIGGER fail_pause BEFORE INSERT ON interval_pauses
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+            )
+            .unwrap();
+
+        assert!(poll_idle(&state).is_err());
+        assert_eq!(
+            state.timer.lock().unwrap().status().state,
+            TimerState::Running
+        );
+        assert_eq!(pause_count(&state), 0);
+    }
+
+    #[test]
+    fn failed_idle_resume_write_leaves_the_timer_paused() {
+        let (state, fake) = setup("resume-fails");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::Work, 1500, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(6)));
+        poll_idle(&state).unwrap();
+        Connection::open(&state.db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TRIGGER fail_end BEFORE UPDATE ON interval_pauses
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;
+                 CREATE TRIGGER fail_discard BEFORE DELETE ON interval_pauses
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+            )
+            .unwrap();
+
+        for keep in [true, false] {
+            assert!(timer::resolve_idle_interval(&state, keep).is_err());
+            let status = state.timer.lock().unwrap().status();
+            assert_eq!(status.state, TimerState::Paused);
+            assert!(status.idle_paused);
+        }
+        assert_eq!(pause_count(&state), 1);
+    }
+
+    #[test]
+    fn breaks_are_not_idle_paused() {
+        let (state, fake) = setup("break");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::ShortBreak, 300, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(6)));
+
+        assert!(poll_idle(&state).unwrap().is_none());
+        assert_eq!(
+            state.timer.lock().unwrap().status().state,
+            TimerState::Running
+        );
+    }
+
+    #[test]
+    fn return_is_reported_once() {
+        let (state, fake) = setup("return");
+        state
+            .timer
+            .lock()
+            .unwrap()
+            .start(IntervalType::Work, 1500, 1)
+            .unwrap();
+        fake.set(Some(Duration::from_mins(6)));
+        poll_idle(&state).unwrap();
+
+        fake.set(Some(Duration::from_secs(1)));
+        let event = poll_idle(&state).unwrap();
+        assert!(matches!(event, Some(IdleEvent::Returned(ref p)) if p.idle_ms >= 360_000));
+        assert!(poll_idle(&state).unwrap().is_none());
+    }
+
+    #[test]
+    fn resolve_idle_keep_counts_time_away() {
+        let mut timer = crate::timer::TimerInner::new();
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        timer.idle_pause(Duration::from_mins(10)).unwrap();
+        timer.resolve_idle(true).unwrap();
+
+        let status = timer.status();
+        assert_eq!(status.state, TimerState::Running);
+        assert!(!status.idle_paused);
+        assert!(status.remaining_ms <= 900_000);
+    }
+
+    #[test]
+    fn resolve_idle_discard_keeps_credited_time() {
+        let mut timer = crate::timer::TimerInner::new();
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        timer.idle_pause(Duration::from_mins(10)).unwrap();
+        timer.resolve_idle(false).unwrap();
+
+        let status = timer.status();
+        assert_eq!(status.state, TimerState::Running);
+        assert!(status.remaining_ms > 1_490_000);
+    }
+
+    #[test]
+    fn resolve_idle_requires_idle_pause() {
+        let mut timer = crate::timer::TimerInner::new();
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        timer.pause().unwrap();
+        assert!(timer.resolve_idle(true).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::goals::GoalConfig;
+use crate::timer::AppState;
+
+/// Width of the windows compared for `best_windows`.
+const WINDOW_HOURS: u32 = 2;
+/// How many non-overlapping windows to report.
+const MAX_WINDOWS: usize = 3;
+
+// ── Types ────────────────────────────────────────────────────
+
+/// Finished work intervals in some slice of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OutcomeCounts {
+    pub completed: i64,
+    pub cancelled: i64,
+    /// Completed share of finished work intervals, 0-100; `None` without any.
+    pub completion_rate: Option<i64>,
+    /// Time spent in completed intervals.
+    pub focus_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HourStat {
+    /// Local hour the intervals started in, 0-23.
+    pub hour: u32,
+    #[serde(flatten)]
+    pub counts: OutcomeCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekdayStat {
+    /// ISO weekday, 1 = Monday through 7 = Sunday.
+    pub weekday: u32,
+    #[serde(flatten)]
+    pub counts: OutcomeCounts,
+}
+
+/// A run of hours, `[start_hour, end_hour)`, ranked by completed pomodoros.
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusWindow {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    #[serde(flatten)]
+    pub counts: OutcomeCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Insights {
+    pub start: String,
+    pub end: String,
+    #[serde(flatten)]
+    pub totals: OutcomeCounts,
+    /// Averages over completed and cancelled work intervals.
+    pub average_seconds: Option<i64>,
+    pub average_planned_seconds: Option<i64>,
+    /// Median local start of each day's first pomodoro and end of its
+    /// last, as `HH:MM`.
+    pub typical_first_pomodoro: Option<String>,
+    pub typical_last_pomodoro: Option<String>,
+    pub by_hour: Vec<HourStat>,
+    /// In week order, starting on the `week_start_day` setting.
+    pub by_weekday: Vec<WeekdayStat>,
+    pub best_windows: Vec<FocusWindow>,
+}
+
+/// A finished work interval in local time.
+struct WorkInterval<Tz: TimeZone> {
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+    duration_seconds: i64,
+    planned_seconds: i64,
+    completed: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    completed: i64,
+    cancelled: i64,
+    focus_seconds: i64,
+}
+
+impl Tally {
+    fn add<Tz: TimeZone>(&mut self, interval: &WorkInterval<Tz>) {
+        if interval.completed {
+            self.completed += 1;
+            self.focus_seconds += interval.duration_seconds;
+        } else {
+            self.cancelled += 1;
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.completed += other.completed;
+        self.cancelled += other.cancelled;
+        self.focus_seconds += other.focus_seconds;
+    }
+
+    fn counts(self) -> OutcomeCounts {
+        let finished = self.completed + self.cancelled;
+        OutcomeCounts {
+            completed: self.completed,
+            cancelled: self.cancelled,
+            completion_rate: (finished > 0).then(|| self.completed * 100 / finished),
+            focus_minutes: self.focus_seconds / 60,
+        }
+    }
+}
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| format!("Invalid date '{value}': {e}"))
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Finished work intervals starting on local days `[start, end]`.
+fn query_work_intervals<Tz: TimeZone>(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    local: &Tz,
+) -> Result<Vec<WorkInterval<Tz>>, String> {
+    // A day either side covers every UTC offset; exact days are checked below
+    let from = format!("{}T00:00:00Z", start - Duration::days(1));
+    let to = format!("{}T00:00:00Z", end + Duration::days(2));
+    let mut stmt = conn
+        .prepare(
+            "SELECT start_time, end_time, duration_seconds, planned_duration_seconds, status
+             FROM timer_intervals
+             WHERE interval_type = 'work' AND status IN ('completed', 'cancelled')
+               AND end_time IS NOT NULL
+               AND start_time >= ?1 AND start_time < ?2
+             ORDER BY start_time",
+        )
+        .map_err(|e| format!("Failed to prepare insights query: {e}"))?;
+
+    let rows = stmt
+        .query_map([from, to], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query work intervals: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read work intervals: {e}"))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(start_time, end_time, duration, planned, status)| {
+            let interval = WorkInterval {
+                start: parse_timestamp(&start_time)?.with_timezone(local),
+                end: parse_timestamp(&end_time)?.with_timezone(local),
+                duration_seconds: duration,
+                planned_seconds: planned,
+                completed: status == "completed",
+            };
+            let day = interval.start.date_naive();
+            (start <= day && day <= end).then_some(interval)
+        })
+        .collect())
+}
+
+// ── Insights ────────────────────────────────────────────────
+
+fn average(values: impl Iterator<Item = i64>) -> Option<i64> {
+    let (sum, count) = values.fold((0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count)
+}
+
+/// Lower median of minutes past midnight, formatted as `HH:MM`.
+fn typical_time(mut minutes: Vec<i64>) -> Option<String> {
+    minutes.sort_unstable();
+    let median = *minutes.get(minutes.len().checked_sub(1)? / 2)?;
+    let minute = median.rem_euclid(24 * 60);
+    Some(format!("{:02}:{:02}", minute / 60, minute % 60))
+}
+
+/// Minutes from midnight of `day` to `at`, past 24:00 for a next-day end.
+fn minutes_into<Tz: TimeZone>(day: NaiveDate, at: &DateTime<Tz>) -> i64 {
+    (at.date_naive() - day).num_days() * 24 * 60 + i64::from(at.hour() * 60 + at.minute())
+}
+
+/// The best non-overlapping windows: most completed pomodoros first, then
+/// the higher completion rate, then the earlier hour.
+fn best_windows(hours: &[Tally; 24]) -> Vec<FocusWindow> {
+    let mut windows: Vec<(u32, Tally)> = (0..=24 - WINDOW_HOURS)
+        .map(|start| {
+            let mut tally = Tally::default();
+            for hour in start..start + WINDOW_HOURS {
+                tally.merge(hours[hour as usize]);
+            }
+            (start, tally)
+        })
+        .filter(|(_, tally)| tally.completed > 0)
+        .collect();
+    windows.sort_by_key(|(start, tally)| {
+        let rate = tally.counts().completion_rate.unwrap_or(0);
+        (-tally.completed, -rate, *start)
+    });
+
+    let mut chosen: Vec<FocusWindow> = Vec::new();
+    for (start, tally) in windows {
+        if chosen.len() == MAX_WINDOWS {
+            break;
+        }
+        let end = start + WINDOW_HOURS;
+        if chosen
+            .iter()
+            .all(|w| end <= w.start_hour || w.end_hour <= start)
+        {
+            chosen.push(FocusWindow {
+                start_hour: start,
+                end_hour: end,
+                counts: tally.counts(),
+            });
+        }
+    }
+    chosen
+}
+
+/// When work gets done over local days `[start, end]`.
+pub fn insights<Tz: TimeZone>(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+    local: &Tz,
+) -> Result<Insights, String> {
+    let (first, last) = (parse_date(start)?, parse_date(end)?);
+    if last < first {
+        return Err(format!("Range end {end} is before its start {start}"));
+    }
+    let intervals = query_work_intervals(conn, first, last, local)?;
+
+    let mut totals = Tally::default();
+    let mut hours = [Tally::default(); 24];
+    let mut weekdays = [Tally::default(); 7];
+    let mut days: BTreeMap<NaiveDate, (i64, i64)> = BTreeMap::new();
+    for interval in &intervals {
+        totals.add(interval);
+        hours[interval.start.hour() as usize].add(interval);
+        weekdays[interval.start.weekday().num_days_from_monday() as usize].add(interval);
+        if interval.completed {
+            let day = interval.start.date_naive();
+            let started = minutes_into(day, &interval.start);
+            let ended = minutes_into(day, &interval.end);
+            let span = days.entry(day).or_insert((started, ended));
+            *span = (span.0.min(started), span.1.max(ended));
+        }
+    }
+
+    let week_start = GoalConfig::load(conn)?.week_start();
+    let by_weekday = (0..7)
+        .map(|offset| {
+            let weekday = (0..offset).fold(week_start, |day, _| day.succ());
+            WeekdayStat {
+                weekday: weekday.number_from_monday(),
+                counts: weekdays[weekday.num_days_from_monday() as usize].counts(),
+            }
+        })
+        .collect();
+
+    Ok(Insights {
+        start: start.to_string(),
+        end: end.to_string(),
+        totals: totals.counts(),
+        average_seconds: average(intervals.iter().map(|i| i.duration_seconds)),
+        average_planned_seconds: average(intervals.iter().map(|i| i.planned_seconds)),
+        typical_first_pomodoro: typical_time(days.values().map(|(first, _)| *first).collect()),
+        typical_last_pomodoro: typical_time(days.values().map(|(_, last)| *last).collect()),
+        by_hour: (0u32..)
+            .zip(hours.iter())
+            .map(|(hour, tally)| HourStat {
+                hour,
+                counts: tally.counts(),
+            })
+            .collect(),
+        by_weekday,
+        best_windows: best_windows(&hours),
+    })
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_insights(
+    state: tauri::State<'_, AppState>,
+    start: String,
+    end: String,
+) -> Result<Insights, String> {
+    insights(&open_db(&state.db_path)?, &start, &end, &Local)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database;
+    use chrono_tz::America::New_York;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        database::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    /// Record a work interval starting at the UTC `start` and lasting
+    /// `seconds` of a 25-minute plan.
+    fn work(conn: &Connection, start: &str, seconds: i64, status: &str) {
+        let start_time = parse_timestamp(start).unwrap();
+        let end_time = start_time + Duration::seconds(seconds);
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds, planned_duration_seconds, status)
+             VALUES ('work', ?1, ?2, ?3, 1500, ?4)",
+            rusqlite::params![
+                start,
+                end_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                seconds,
+                status
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn empty_range_has_no_rates_or_times() {
+        let conn = setup_test_db();
+        let report = insights(&conn, "2026-03-01", "2026-03-31", &Utc).unwrap();
+        assert_eq!(report.totals.completion_rate, None);
+        assert_eq!(report.average_seconds, None);
+        assert_eq!(report.typical_first_pomodoro, None);
+        assert_eq!(report.by_hour.len(), 24);
+        assert_eq!(report.by_weekday.len(), 7);
+        assert!(report.best_windows.is_empty());
+        assert!(insights(&conn, "2026-03-31", "2026-03-01", &Utc).is_err());
+    }
+
+    #[test]
+    fn buckets_by_local_hour_and_weekday() {
+        let conn = setup_test_db();
+        // 13:30 UTC on Mon Mar 2 is 08:30 in New York
+        work(&conn, "2026-03-02T13:30:00Z", 1500, "completed");
+        work(&conn, "2026-03-02T14:00:00Z", 600, "cancelled");
+        // 02:00 UTC on Wed Mar 4 is still Tuesday evening in New York
+        work(&conn, "2026-03-04T02:00:00Z", 1500, "completed");
+        // Breaks and running intervals are ignored
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, duration_seconds, planned_duration_seconds, status)
+             VALUES ('work', '2026-03-02T15:00:00Z', 0, 1500, 'in_progress')",
+            [],
+        )
+        .unwrap();
+
+        let report = insights(&conn, "2026-03-02", "2026-03-03", &New_York).unwrap();
+        assert_eq!(report.totals.completed, 2);
+        assert_eq!(report.totals.cancelled, 1);
+        assert_eq!(report.totals.completion_rate, Some(66));
+        assert_eq!(report.totals.focus_minutes, 50);
+        assert_eq!(report.average_seconds, Some(1200));
+        assert_eq!(report.average_planned_seconds, Some(1500));
+
+        assert_eq!(report.by_hour[8].counts.completed, 1);
+        assert_eq!(report.by_hour[9].counts.cancelled, 1);
+        assert_eq!(report.by_hour[21].counts.completed, 1);
+        assert_eq!(report.by_weekday[0].weekday, 1);
+        assert_eq!(report.by_weekday[0].counts.completed, 1);
+        assert_eq!(report.by_weekday[1].counts.completed, 1);
+
+        // The same intervals in UTC fall on Monday and Wednesday
+        let utc = insights(&conn, "2026-03-02", "2026-03-03", &Utc).unwrap();
+        assert_eq!(utc.totals.completed, 1);
+    }
+
+    #[test]
+    fn typical_times_are_daily_medians() {
+        let conn = setup_test_db();
+        work(&conn, "2026-03-02T08:00:00Z", 1500, "completed");
+        work(&conn, "2026-03-02T16:00:00Z", 1500, "completed");
+        work(&conn, "2026-03-03T09:00:00Z", 1500, "completed");
+        work(&conn, "2026-03-03T07:00:00Z", 1500, "cancelled");
+        work(&conn, "2026-03-04T10:00:00Z", 1500, "completed");
+        // Ends after midnight
+        work(&conn, "2026-03-04T23:50:00Z", 1500, "completed");
+
+        let report = insights(&conn, "2026-03-02", "2026-03-04", &Utc).unwrap();
+        assert_eq!(report.typical_first_pomodoro.as_deref(), Some("09:00"));
+        // Last ends are 16:25, 09:25 and 00:15 the next day
+        assert_eq!(report.typical_last_pomodoro.as_deref(), Some("16:25"));
+    }
+
+    #[test]
+    fn best_windows_do_not_overlap() {
+        let conn = setup_test_db();
+        for day in 2..=6 {
+            work(
+                &conn,
+                &format!("2026-03-0{day}T09:00:00Z"),
+                1500,
+                "completed",
+            );
+            work(
+                &conn,
+                &format!("2026-03-0{day}T10:00:00Z"),
+                1500,
+                "completed",
+            );
+            work(
+                &conn,
+                &format!("2026-03-0{day}T11:00:00Z"),
+                1500,
+                "completed",
+            );
+        }
+        work(&conn, "2026-03-02T14:00:00Z", 1500, "completed");
+        work(&conn, "2026-03-02T14:30:00Z", 300, "cancelled");
+        work(&conn, "2026-03-03T16:00:00Z", 1500, "completed");
+
+        let report = insights(&conn, "2026-03-02", "2026-03-06", &Utc).unwrap();
+        let windows: Vec<(u32, u32, i64)> = report
+            .best_windows
+            .iter()
+            .map(|w| (w.start_hour, w.end_hour, w.counts.completed))
+            .collect();
+        // 09-11 beats 10-12 on the earlier hour; 15-17 beats 13-15 on rate
+        assert_eq!(windows, vec![(9, 11, 10), (11, 13, 5), (15, 17, 1)]);
+        assert_eq!(report.best_windows[0].counts.completion_rate, Some(100));
+    }
+
+    #[test]
+    fn weekdays_follow_the_week_start_day() {
+        let conn = setup_test_db();
+        conn.execute(
+            "UPDATE user_settings SET value = '7' WHERE key = 'week_start_day'",
+            [],
+        )
+        .unwrap();
+        work(&conn, "2026-03-01T09:00:00Z", 1500, "completed");
+
+        let report = insights(&conn, "2026-03-01", "2026-03-07", &Utc).unwrap();
+        let order: Vec<u32> = report.by_weekday.iter().map(|d| d.weekday).collect();
+        assert_eq!(order, vec![7, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(report.by_weekday[0].counts.completed, 1);
+    }
+}
@@ -0,0 +1,299 @@
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Runtime};
+
+use crate::timer::{self, AppState, IntervalType};
+
+// ── Types ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterruptionKind {
+    Internal,
+    External,
+}
+
+impl InterruptionKind {
+    fn as_db_str(self) -> &'static str {
+        match self {
+            Self::Internal => "internal",
+            Self::External => "external",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Interruption {
+    pub id: i64,
+    pub interval_id: i64,
+    pub kind: String,
+    pub note: Option<String>,
+    pub occurred_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoggedInterruption {
+    pub interruption: Interruption,
+    /// Total interruptions logged against the interval so far.
+    pub interval_interruption_count: i64,
+    /// True when this interruption reached `interruption_void_threshold`
+    /// and the pomodoro was cancelled.
+    pub interval_voided: bool,
+}
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+fn insert_interruption(
+    conn: &Connection,
+    interval_id: i64,
+    kind: InterruptionKind,
+    note: Option<&str>,
+    occurred_at: &str,
+) -> Result<Interruption, String> {
+    conn.execute(
+        "INSERT INTO interruptions (interval_id, kind, note, occurred_at) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![interval_id, kind.as_db_str(), note, occurred_at],
+    )
+    .map_err(|e| format!("Failed to log interruption: {e}"))?;
+
+    Ok(Interruption {
+        id: conn.last_insert_rowid(),
+        interval_id,
+        kind: kind.as_db_str().to_string(),
+        note: note.map(String::from),
+        occurred_at: occurred_at.to_string(),
+    })
+}
+
+fn count_interval_interruptions(conn: &Connection, interval_id: i64) -> Result<i64, String> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM interruptions WHERE interval_id = ?1",
+        [interval_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| format!("Failed to count interruptions: {e}"))
+}
+
+/// Read `interruption_void_threshold`. Zero (or a missing/invalid value) disables voiding.
+fn read_void_threshold(conn: &Connection) -> i64 {
+    conn.query_row(
+        "SELECT value FROM user_settings WHERE key = 'interruption_void_threshold'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|v| v.parse::<i64>().ok())
+    .unwrap_or(0)
+}
+
+fn should_void(count: i64, threshold: i64) -> bool {
+    threshold > 0 && count >= threshold
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn log_interruption<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+    kind: InterruptionKind,
+    note: Option<String>,
+) -> Result<LoggedInterruption, String> {
+    let active = state
+        .timer
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .active_interval();
+
+    let interval_id = match active {
+        Some((id, IntervalType::Work)) => id,
+        Some(_) => return Err("Interruptions can only be logged during a work interval".into()),
+        None => return Err("No active interval".into()),
+    };
+
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let occurred_at = state.clock.timestamp();
+
+    let conn = open_db(&state.db_path)?;
+    let interruption =
+        insert_interruption(&conn, interval_id, kind, note.as_deref(), &occurred_at)?;
+    let interval_interruption_count = count_interval_interruptions(&conn, interval_id)?;
+    let threshold = read_void_threshold(&conn);
+    drop(conn);
+
+    let mut interval_voided = false;
+    if should_void(interval_interruption_count, threshold) {
+        // Only void if the same interval is still active
+        let still_active = state
+            .timer
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?
+            .active_interval()
+            .is_some_and(|(id, _)| id == interval_id);
+        if still_active {
+            let status = timer::cancel_active_interval(&state)?;
+            timer::emit_state_changed(&app, &status);
+            interval_voided = true;
+        }
+    }
+
+    Ok(LoggedInterruption {
+        interruption,
+        interval_interruption_count,
+        interval_voided,
+    })
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        crate::database::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn insert_interval(conn: &Connection) -> i64 {
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, planned_duration_seconds) \
+             VALUES ('work', '2026-02-14T09:00:00Z', 1500)",
+            [],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn insert_interruption_returns_row() {
+        let conn = setup_test_db();
+        let interval_id = insert_interval(&conn);
+        let logged = insert_interruption(
+            &conn,
+            interval_id,
+            InterruptionKind::External,
+            Some("Phone call"),
+            "2026-02-14T09:05:00Z",
+        )
+        .unwrap();
+
+        assert_eq!(logged.interval_id, interval_id);
+        assert_eq!(logged.kind, "external");
+        assert_eq!(logged.note.as_deref(), Some("Phone call"));
+        assert_eq!(count_interval_interruptions(&conn, interval_id).unwrap(), 1);
+    }
+
+    #[test]
+    fn interruptions_are_counted_per_interval() {
+        let conn = setup_test_db();
+        let first = insert_interval(&conn);
+        let second = insert_interval(&conn);
+        for _ in 0..3 {
+            insert_interruption(
+                &conn,
+                first,
+                InterruptionKind::Internal,
+                None,
+                "2026-02-14T09:05:00Z",
+            )
+            .unwrap();
+        }
+        insert_interruption(
+            &conn,
+            second,
+            InterruptionKind::Internal,
+            None,
+            "2026-02-14T09:05:00Z",
+        )
+        .unwrap();
+
+        assert_eq!(count_interval_interruptions(&conn, first).unwrap(), 3);
+        assert_eq!(count_interval_interruptions(&conn, second).unwrap(), 1);
+    }
+
+    #[test]
+    fn void_threshold_defaults_to_disabled() {
+        let conn = setup_test_db();
+        assert_eq!(read_void_threshold(&conn), 0);
+        assert!(!should_void(10, 0));
+    }
+
+    #[test]
+    fn void_triggers_at_threshold() {
+        assert!(!should_void(2, 3));
+        assert!(should_void(3, 3));
+        assert!(should_void(4, 3));
+    }
+
+    #[test]
+    fn interruption_kind_serde_roundtrip() {
+        let json = serde_json::to_string(&InterruptionKind::Internal).unwrap();
+        assert_eq!(json, "\"internal\"");
+        let parsed: InterruptionKind = serde_json::from_str("\"external\"").unwrap();
+        assert_eq!(parsed, InterruptionKind::External);
+    }
+
+    #[test]
+    fn reaching_the_threshold_voids_the_active_interval() {
+        use crate::timer::TimerState;
+        use tauri::Manager;
+
+        let path =
+            std::env::temp_dir().join(format!("pomo-interruptions-{}-void.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+        open_db(&path)
+            .unwrap()
+            .execute(
+                "UPDATE user_settings SET value = '2' WHERE key = 'interruption_void_threshold'",
+                [],
+            )
+            .unwrap();
+        let app = tauri::test::mock_app();
+        app.manage(AppState::new(path.clone()));
+        let interval_id = timer::start_interval(&app.state(), IntervalType::Work, 1500)
+            .unwrap()
+            .interval_id
+            .unwrap();
+
+        let log = || {
+            log_interruption(
+                app.state(),
+                app.handle().clone(),
+                InterruptionKind::External,
+                None,
+            )
+            .unwrap()
+        };
+        let first = log();
+        assert_eq!(first.interval_interruption_count, 1);
+        assert!(!first.interval_voided);
+
+        let second = log();
+        assert_eq!(second.interval_interruption_count, 2);
+        assert!(second.interval_voided);
+        let state = app.state::<AppState>();
+        assert_eq!(state.timer.lock().unwrap().status().state, TimerState::Idle);
+        let status: String = open_db(&path)
+            .unwrap()
+            .query_row(
+                "SELECT status FROM timer_intervals WHERE id = ?1",
+                [interval_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(status, "cancelled");
+    }
+}
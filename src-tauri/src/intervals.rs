@@ -0,0 +1,716 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use std::path::Path;
+
+use crate::timer::{AppState, IntervalType};
+
+/// Longest interval that may be entered or edited by hand.
+const MAX_MANUAL_DURATION_SECONDS: i64 = 24 * 60 * 60;
+
+// ── Types ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Interval {
+    pub id: i64,
+    pub interval_type: String,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub duration_seconds: Option<i64>,
+    pub planned_duration_seconds: i64,
+    pub status: String,
+    pub source: String,
+    pub created_at: String,
+}
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+const INTERVAL_COLUMNS: &str = "id, interval_type, start_time, end_time, duration_seconds, \
+                                planned_duration_seconds, status, source, created_at";
+
+fn row_to_interval(row: &rusqlite::Row<'_>) -> rusqlite::Result<Interval> {
+    Ok(Interval {
+        id: row.get(0)?,
+        interval_type: row.get(1)?,
+        start_time: row.get(2)?,
+        end_time: row.get(3)?,
+        duration_seconds: row.get(4)?,
+        planned_duration_seconds: row.get(5)?,
+        status: row.get(6)?,
+        source: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+fn get_interval(conn: &Connection, id: i64) -> Result<Interval, String> {
+    conn.query_row(
+        &format!("SELECT {INTERVAL_COLUMNS} FROM timer_intervals WHERE id = ?1"),
+        [id],
+        row_to_interval,
+    )
+    .map_err(|e| format!("Interval not found: {e}"))
+}
+
+/// Parse an RFC 3339 timestamp and normalize it to the stored UTC format.
+fn parse_timestamp(value: &str, field: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid {field} '{value}': {e}"))
+}
+
+fn format_timestamp(dt: DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Validate a start/end pair and return the span in seconds.
+fn validate_span(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<i64, String> {
+    if end <= start {
+        return Err("End time must be after start time".into());
+    }
+    if end > now {
+        return Err("Intervals cannot end in the future".into());
+    }
+    let seconds = (end - start).num_seconds();
+    if seconds > MAX_MANUAL_DURATION_SECONDS {
+        return Err("Intervals cannot be longer than 24 hours".into());
+    }
+    Ok(seconds)
+}
+
+/// Reject spans that overlap any other interval. The interval the timer
+/// holds (`open_id`) is open-ended; any other row without an end time was
+/// left behind by a crash and only covers its planned duration.
+fn check_no_overlap(
+    conn: &Connection,
+    start_time: &str,
+    end_time: &str,
+    exclude_id: Option<i64>,
+    open_id: Option<i64>,
+) -> Result<(), String> {
+    let conflict: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM timer_intervals \
+             WHERE id != ?3 AND start_time < ?2 \
+               AND (end_time > ?1 OR (end_time IS NULL AND (id = ?4 OR \
+                    strftime('%Y-%m-%dT%H:%M:%SZ', start_time, \
+                             '+' || planned_duration_seconds || ' seconds') > ?1))) \
+             ORDER BY start_time LIMIT 1",
+            rusqlite::params![start_time, end_time, exclude_id.unwrap_or(0), open_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to check for overlapping intervals: {e}"))?;
+
+    match conflict {
+        Some(id) => Err(format!("Interval overlaps existing interval {id}")),
+        None => Ok(()),
+    }
+}
+
+fn insert_manual_interval(
+    conn: &Connection,
+    interval_type: IntervalType,
+    start_time: &str,
+    end_time: &str,
+    open_id: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<Interval, String> {
+    let start = parse_timestamp(start_time, "start time")?;
+    let end = parse_timestamp(end_time, "end time")?;
+    let duration = validate_span(start, end, now)?;
+    let (start_time, end_time) = (format_timestamp(start), format_timestamp(end));
+    check_no_overlap(conn, &start_time, &end_time, None, open_id)?;
+
+    conn.execute(
+        "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds, \
+         planned_duration_seconds, status, source) \
+         VALUES (?1, ?2, ?3, ?4, ?4, 'completed', 'manual')",
+        rusqlite::params![interval_type.as_db_str(), start_time, end_time, duration],
+    )
+    .map_err(|e| format!("Failed to create interval: {e}"))?;
+
+    get_interval(conn, conn.last_insert_rowid())
+}
+
+fn has_pauses(conn: &Connection, id: i64) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM interval_pauses WHERE interval_id = ?1)",
+        [id],
+        |row| row.get(0),
+    )
+    .map_err(|e| format!("Failed to check interval pauses: {e}"))
+}
+
+fn update_interval_row(
+    conn: &Connection,
+    id: i64,
+    interval_type: Option<IntervalType>,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+    open_id: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<Interval, String> {
+    let existing = get_interval(conn, id)?;
+    if existing.status == "in_progress" {
+        return Err("Cannot edit an interval that is still running".into());
+    }
+
+    let start = parse_timestamp(start_time.unwrap_or(&existing.start_time), "start time")?;
+    let end = match (end_time, existing.end_time.as_deref()) {
+        (Some(value), _) | (None, Some(value)) => parse_timestamp(value, "end time")?,
+        (None, None) => return Err("Interval has no end time".into()),
+    };
+    let interval_type = interval_type.map_or(existing.interval_type, |t| t.as_db_str().to_string());
+
+    let times_changed = start_time.is_some() || end_time.is_some();
+    // The stored duration of these is not the span between start and end,
+    // so it can't be recomputed from edited times
+    if times_changed && existing.status == "cancelled" {
+        return Err("Cannot change the times of a cancelled interval".into());
+    }
+    if times_changed && has_pauses(conn, id)? {
+        return Err("Cannot change the times of an interval with pauses".into());
+    }
+    let duration = if times_changed {
+        validate_span(start, end, now)?
+    } else {
+        existing.duration_seconds.unwrap_or(0)
+    };
+    let (start_time, end_time) = (format_timestamp(start), format_timestamp(end));
+    if times_changed {
+        check_no_overlap(conn, &start_time, &end_time, Some(id), open_id)?;
+    }
+
+    conn.execute(
+        "UPDATE timer_intervals \
+         SET interval_type = ?1, start_time = ?2, end_time = ?3, duration_seconds = ?4 \
+         WHERE id = ?5",
+        rusqlite::params![interval_type, start_time, end_time, duration, id],
+    )
+    .map_err(|e| format!("Failed to update interval: {e}"))?;
+
+    get_interval(conn, id)
+}
+
+fn delete_interval_row(conn: &Connection, id: i64) -> Result<(), String> {
+    let existing = get_interval(conn, id)?;
+    if existing.status == "in_progress" {
+        return Err("Cannot delete an interval that is still running. Cancel it first.".into());
+    }
+    conn.execute("DELETE FROM timer_intervals WHERE id = ?1", [id])
+        .map_err(|e| format!("Failed to delete interval: {e}"))?;
+    Ok(())
+}
+
+/// Intervals that started on a day in `[start_date, end_date]`, oldest first.
+pub fn list_between(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<Interval>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {INTERVAL_COLUMNS} FROM timer_intervals \
+             WHERE date(start_time) BETWEEN ?1 AND ?2 \
+             ORDER BY start_time ASC"
+        ))
+        .map_err(|e| format!("Failed to prepare interval query: {e}"))?;
+
+    let intervals = stmt
+        .query_map([start_date, end_date], row_to_interval)
+        .map_err(|e| format!("Failed to query intervals: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read intervals: {e}"))?;
+
+    Ok(intervals)
+}
+
+/// The interval the timer is currently timing, if any.
+fn open_interval_id(state: &AppState) -> Result<Option<i64>, String> {
+    let timer = state.timer.lock().map_err(|e| format!("Lock error: {e}"))?;
+    Ok(timer.active_interval().map(|(id, _)| id))
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn create_manual_interval(
+    state: tauri::State<'_, AppState>,
+    interval_type: IntervalType,
+    start_time: String,
+    end_time: String,
+) -> Result<Interval, String> {
+    let conn = open_db(&state.db_path)?;
+    insert_manual_interval(
+        &conn,
+        interval_type,
+        &start_time,
+        &end_time,
+        open_interval_id(&state)?,
+        Utc::now(),
+    )
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn update_interval(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    interval_type: Option<IntervalType>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Interval, String> {
+    let conn = open_db(&state.db_path)?;
+    update_interval_row(
+        &conn,
+        id,
+        interval_type,
+        start_time.as_deref(),
+        end_time.as_deref(),
+        open_interval_id(&state)?,
+        Utc::now(),
+    )
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn delete_interval(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    let conn = open_db(&state.db_path)?;
+    delete_interval_row(&conn, id)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        crate::database::run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn now() -> DateTime<Utc> {
+        parse_timestamp("2026-02-15T18:00:00Z", "now").unwrap()
+    }
+
+    fn insert_timer_interval(
+        conn: &Connection,
+        start: &str,
+        end: Option<&str>,
+        status: &str,
+    ) -> i64 {
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds, \
+             planned_duration_seconds, status) VALUES ('work', ?1, ?2, 1500, 1500, ?3)",
+            rusqlite::params![start, end, status],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    // ── Create tests ────────────────────────────────────────
+
+    #[test]
+    fn manual_interval_is_completed_and_flagged_manual() {
+        let conn = setup_test_db();
+        let interval = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T09:00:00Z",
+            "2026-02-15T09:25:00Z",
+            None,
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(interval.interval_type, "work");
+        assert_eq!(interval.status, "completed");
+        assert_eq!(interval.source, "manual");
+        assert_eq!(interval.duration_seconds, Some(1500));
+        assert_eq!(interval.planned_duration_seconds, 1500);
+    }
+
+    #[test]
+    fn manual_interval_normalizes_offsets_to_utc() {
+        let conn = setup_test_db();
+        let interval = insert_manual_interval(
+            &conn,
+            IntervalType::ShortBreak,
+            "2026-02-15T10:00:00+01:00",
+            "2026-02-15T10:05:00+01:00",
+            None,
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(interval.start_time, "2026-02-15T09:00:00Z");
+        assert_eq!(interval.end_time.as_deref(), Some("2026-02-15T09:05:00Z"));
+    }
+
+    #[test]
+    fn manual_interval_rejects_end_before_start() {
+        let conn = setup_test_db();
+        let result = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T09:25:00Z",
+            "2026-02-15T09:00:00Z",
+            None,
+            now(),
+        );
+        assert!(result.unwrap_err().contains("after start"));
+    }
+
+    #[test]
+    fn manual_interval_rejects_future_end() {
+        let conn = setup_test_db();
+        let result = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T17:50:00Z",
+            "2026-02-15T18:15:00Z",
+            None,
+            now(),
+        );
+        assert!(result.unwrap_err().contains("future"));
+    }
+
+    #[test]
+    fn manual_interval_rejects_invalid_timestamp() {
+        let conn = setup_test_db();
+        let result = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "09:00",
+            "2026-02-15T09:25:00Z",
+            None,
+            now(),
+        );
+        assert!(result.unwrap_err().contains("Invalid start time"));
+    }
+
+    #[test]
+    fn manual_interval_rejects_overlap() {
+        let conn = setup_test_db();
+        let existing = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+
+        let result = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T09:20:00Z",
+            "2026-02-15T09:45:00Z",
+            None,
+            now(),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            format!("Interval overlaps existing interval {existing}")
+        );
+    }
+
+    #[test]
+    fn manual_interval_may_touch_neighbours() {
+        let conn = setup_test_db();
+        insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+
+        let result = insert_manual_interval(
+            &conn,
+            IntervalType::ShortBreak,
+            "2026-02-15T09:25:00Z",
+            "2026-02-15T09:30:00Z",
+            None,
+            now(),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn manual_interval_rejects_overlap_with_running_interval() {
+        let conn = setup_test_db();
+        let running = insert_timer_interval(&conn, "2026-02-15T09:00:00Z", None, "in_progress");
+
+        let result = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T10:00:00Z",
+            "2026-02-15T10:25:00Z",
+            Some(running),
+            now(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn stale_in_progress_interval_only_blocks_its_planned_span() {
+        let conn = setup_test_db();
+        // Left behind by a crash: never ended, and not held by the timer
+        let stale = insert_timer_interval(&conn, "2026-02-15T09:00:00Z", None, "in_progress");
+
+        let within = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T09:20:00Z",
+            "2026-02-15T09:40:00Z",
+            None,
+            now(),
+        );
+        assert_eq!(
+            within.unwrap_err(),
+            format!("Interval overlaps existing interval {stale}")
+        );
+
+        let later = insert_manual_interval(
+            &conn,
+            IntervalType::Work,
+            "2026-02-15T09:25:00Z",
+            "2026-02-15T09:50:00Z",
+            None,
+            now(),
+        );
+        assert!(later.is_ok());
+    }
+
+    // ── Update tests ────────────────────────────────────────
+
+    #[test]
+    fn update_interval_recomputes_duration() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+
+        let updated = update_interval_row(
+            &conn,
+            id,
+            None,
+            None,
+            Some("2026-02-15T09:30:00Z"),
+            None,
+            now(),
+        )
+        .unwrap();
+        assert_eq!(updated.end_time.as_deref(), Some("2026-02-15T09:30:00Z"));
+        assert_eq!(updated.duration_seconds, Some(1800));
+        assert_eq!(updated.source, "timer");
+    }
+
+    #[test]
+    fn update_interval_rejects_time_edits_on_paused_intervals() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:35:00Z"),
+            "completed",
+        );
+        conn.execute(
+            "INSERT INTO interval_pauses (interval_id, paused_at, resumed_at, duration_seconds) \
+             VALUES (?1, '2026-02-15T09:10:00Z', '2026-02-15T09:20:00Z', 600)",
+            [id],
+        )
+        .unwrap();
+
+        let result = update_interval_row(
+            &conn,
+            id,
+            None,
+            None,
+            Some("2026-02-15T09:40:00Z"),
+            None,
+            now(),
+        );
+        assert!(result.is_err());
+        // Retyping leaves the paused-adjusted duration alone
+        let updated =
+            update_interval_row(&conn, id, Some(IntervalType::Work), None, None, None, now())
+                .unwrap();
+        assert_eq!(updated.duration_seconds, Some(1500));
+    }
+
+    #[test]
+    fn update_interval_rejects_time_edits_on_cancelled_intervals() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:10:00Z"),
+            "cancelled",
+        );
+        conn.execute(
+            "UPDATE timer_intervals SET duration_seconds = 600 WHERE id = ?1",
+            [id],
+        )
+        .unwrap();
+
+        let result = update_interval_row(
+            &conn,
+            id,
+            None,
+            Some("2026-02-15T08:55:00Z"),
+            None,
+            None,
+            now(),
+        );
+        assert!(result.is_err());
+        assert_eq!(get_interval(&conn, id).unwrap().duration_seconds, Some(600));
+    }
+
+    #[test]
+    fn update_interval_type_keeps_duration() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+
+        let updated = update_interval_row(
+            &conn,
+            id,
+            Some(IntervalType::LongBreak),
+            None,
+            None,
+            None,
+            now(),
+        )
+        .unwrap();
+        assert_eq!(updated.interval_type, "long_break");
+        assert_eq!(updated.duration_seconds, Some(1500));
+    }
+
+    #[test]
+    fn update_interval_ignores_itself_for_overlap() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+
+        let result = update_interval_row(
+            &conn,
+            id,
+            None,
+            Some("2026-02-15T09:05:00Z"),
+            None,
+            None,
+            now(),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn update_interval_rejects_overlap_with_other() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+        insert_timer_interval(
+            &conn,
+            "2026-02-15T09:30:00Z",
+            Some("2026-02-15T09:55:00Z"),
+            "completed",
+        );
+
+        let result = update_interval_row(
+            &conn,
+            id,
+            None,
+            None,
+            Some("2026-02-15T09:40:00Z"),
+            None,
+            now(),
+        );
+        assert!(result.unwrap_err().contains("overlaps"));
+    }
+
+    #[test]
+    fn update_running_interval_is_blocked() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(&conn, "2026-02-15T09:00:00Z", None, "in_progress");
+
+        let result = update_interval_row(
+            &conn,
+            id,
+            Some(IntervalType::ShortBreak),
+            None,
+            None,
+            None,
+            now(),
+        );
+        assert!(result.is_err());
+    }
+
+    // ── Delete tests ────────────────────────────────────────
+
+    #[test]
+    fn delete_interval_removes_row_and_links() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(
+            &conn,
+            "2026-02-15T09:00:00Z",
+            Some("2026-02-15T09:25:00Z"),
+            "completed",
+        );
+        conn.execute(
+            "INSERT INTO tasks (title, day_date, position) VALUES ('Task', '2026-02-15', 0)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO task_interval_links (task_id, interval_id) VALUES (?1, ?2)",
+            [conn.last_insert_rowid(), id],
+        )
+        .unwrap();
+
+        delete_interval_row(&conn, id).unwrap();
+
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM task_interval_links", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(remaining, 0);
+        assert!(get_interval(&conn, id).is_err());
+    }
+
+    #[test]
+    fn delete_running_interval_is_blocked() {
+        let conn = setup_test_db();
+        let id = insert_timer_interval(&conn, "2026-02-15T09:00:00Z", None, "in_progress");
+        assert!(delete_interval_row(&conn, id).is_err());
+    }
+}
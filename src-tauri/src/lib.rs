@@ -1,9 +1,24 @@
-use tauri::Manager;
+use tauri::{Manager, WindowEvent};
 
+pub mod api;
+pub mod calendar;
+pub mod cli;
+pub mod clock;
 mod database;
+pub mod export;
+pub mod goals;
+pub mod hooks;
+pub mod idle;
+pub mod insights;
+pub mod interruptions;
+pub mod intervals;
+pub mod meetings;
+pub mod notifications;
 pub mod reports;
+pub mod shortcuts;
 pub mod tasks;
 pub mod timer;
+pub mod tray;
 pub mod audio;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -11,12 +26,22 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_sql::Builder::default().build())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
         .invoke_handler(tauri::generate_handler![
             timer::start_timer,
             timer::pause_timer,
             timer::resume_timer,
+            timer::extend_timer,
             timer::cancel_timer,
             timer::get_timer_state,
+            shortcuts::reload_shortcuts,
+            shortcuts::get_shortcut_report,
+            idle::resolve_idle,
+            interruptions::log_interruption,
+            intervals::create_manual_interval,
+            intervals::update_interval,
+            intervals::delete_interval,
             tasks::create_task,
             tasks::update_task,
             tasks::delete_task,
@@ -34,16 +59,58 @@ pub fn run() {
             reports::get_daily_summary,
             reports::get_weekly_summary,
             reports::get_monthly_summary,
+            reports::get_range_summary,
+            reports::get_yearly_summary,
+            insights::get_insights,
             audio::play_alarm,
+            audio::stop_alarm,
+            audio::acknowledge_alarm,
+            audio::preview_sound,
+            audio::import_sound,
+            audio::reset_sound,
+            audio::get_sounds,
+            api::reload_api,
+            api::regenerate_api_token,
+            api::get_api_report,
+            hooks::list_hooks,
+            hooks::create_hook,
+            hooks::update_hook,
+            hooks::delete_hook,
+            hooks::get_hook_deliveries,
+            hooks::test_hook,
+            calendar::export_calendar,
+            calendar::refresh_calendar_feed,
+            meetings::import_calendar,
+            meetings::refresh_calendars,
+            meetings::list_calendar_sources,
+            meetings::delete_calendar_source,
+            meetings::get_day_plan,
+            goals::get_goal_config,
+            goals::set_goal_override,
+            goals::clear_goal_override,
         ])
+        .on_window_event(|window, event| {
+            if let WindowEvent::Focused(focused) = event {
+                if let Some(state) = window.try_state::<timer::AppState>() {
+                    state.set_ui_focused(*focused);
+                }
+            }
+        })
         .setup(|app| {
             let app_data_dir = app
                 .path()
                 .app_data_dir()
                 .expect("failed to resolve app data directory");
-            let db_path = app_data_dir.join("pomo.db");
+            let db_path = app_data_dir.join(database::DB_FILE_NAME);
             database::initialize(&db_path)?;
             app.manage(timer::AppState::new(db_path));
+            audio::init(app.handle());
+            tray::init(app.handle());
+            shortcuts::init(app.handle());
+            notifications::init(app.handle());
+            api::init(app.handle());
+            calendar::init(&app.state::<timer::AppState>().db_path);
+            idle::spawn_idle_watcher(app.handle().clone());
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -63,8 +130,16 @@ mod tests {
                 crate::timer::start_timer,
                 crate::timer::pause_timer,
                 crate::timer::resume_timer,
+                crate::timer::extend_timer,
                 crate::timer::cancel_timer,
                 crate::timer::get_timer_state,
+                crate::shortcuts::reload_shortcuts,
+                crate::shortcuts::get_shortcut_report,
+                crate::idle::resolve_idle,
+                crate::interruptions::log_interruption,
+                crate::intervals::create_manual_interval,
+                crate::intervals::update_interval,
+                crate::intervals::delete_interval,
                 crate::tasks::create_task,
                 crate::tasks::update_task,
                 crate::tasks::delete_task,
@@ -82,7 +157,35 @@ mod tests {
                 crate::reports::get_daily_summary,
                 crate::reports::get_weekly_summary,
                 crate::reports::get_monthly_summary,
+                crate::reports::get_range_summary,
+                crate::reports::get_yearly_summary,
+                crate::insights::get_insights,
                 crate::audio::play_alarm,
+                crate::audio::stop_alarm,
+                crate::audio::acknowledge_alarm,
+                crate::audio::preview_sound,
+                crate::audio::import_sound,
+                crate::audio::reset_sound,
+                crate::audio::get_sounds,
+                crate::api::reload_api,
+                crate::api::regenerate_api_token,
+                crate::api::get_api_report,
+                crate::hooks::list_hooks,
+                crate::hooks::create_hook,
+                crate::hooks::update_hook,
+                crate::hooks::delete_hook,
+                crate::hooks::get_hook_deliveries,
+                crate::hooks::test_hook,
+                crate::calendar::export_calendar,
+                crate::calendar::refresh_calendar_feed,
+                crate::meetings::import_calendar,
+                crate::meetings::refresh_calendars,
+                crate::meetings::list_calendar_sources,
+                crate::meetings::delete_calendar_source,
+                crate::meetings::get_day_plan,
+                crate::goals::get_goal_config,
+                crate::goals::set_goal_override,
+                crate::goals::clear_goal_override,
             ])
             .build(tauri::test::mock_context(noop_assets()))
             .expect("failed to build mock Tauri app");
@@ -0,0 +1,1764 @@
+use chrono::offset::LocalResult;
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::timer::{break_after, AppState, IntervalSettings, IntervalType, TimerState};
+
+/// Recurring events are expanded from this far in the past...
+const EXPANSION_PAST_DAYS: i64 = 90;
+/// ...to this far in the future, relative to the import.
+const EXPANSION_FUTURE_DAYS: i64 = 366;
+/// Upper bound on recurrence periods walked per event.
+const MAX_RECURRENCE_PERIODS: i64 = 10_000;
+/// Largest RRULE INTERVAL accepted; rules with a larger one are ignored.
+const MAX_RRULE_INTERVAL: u32 = 1_000;
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+const DEFAULT_WORKDAY_START: &str = "09:00";
+const DEFAULT_WORKDAY_END: &str = "17:00";
+
+// ── Types ────────────────────────────────────────────────────
+
+/// An imported calendar: a local `.ics` file or a URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarSource {
+    pub id: i64,
+    pub location: String,
+    pub name: Option<String>,
+    pub event_count: i64,
+    pub last_imported_at: Option<String>,
+    /// Why the most recent import failed; events from the last good import are kept.
+    pub last_error: Option<String>,
+    /// TZIDs in the last import that could not be resolved; their times
+    /// were read as local time.
+    pub unknown_time_zones: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CalendarEvent {
+    pub id: i64,
+    pub source_id: i64,
+    pub uid: String,
+    pub summary: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub all_day: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FreeBlock {
+    pub start_time: String,
+    pub end_time: String,
+    pub minutes: i64,
+    /// Work intervals that fit, with breaks between them.
+    pub pomodoros: u32,
+}
+
+/// Meetings and the focus time left around them for one day.
+#[derive(Debug, Clone, Serialize)]
+pub struct DayPlan {
+    pub day_date: String,
+    pub workday_start: String,
+    pub workday_end: String,
+    pub meetings: Vec<CalendarEvent>,
+    pub free_blocks: Vec<FreeBlock>,
+    pub free_minutes: i64,
+    pub pomodoros: u32,
+}
+
+/// Payload of the `meeting-conflict` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MeetingConflict {
+    pub summary: String,
+    pub start_time: String,
+    pub end_time: String,
+    /// Minutes from now until the meeting starts; 0 if already under way.
+    pub minutes_until: i64,
+}
+
+/// The events of one calendar document, before they are stored.
+#[derive(Debug, Clone, Default)]
+struct ParsedCalendar {
+    events: Vec<ParsedEvent>,
+    unknown_time_zones: Vec<String>,
+}
+
+/// An event occurrence produced by the parser, before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParsedEvent {
+    uid: String,
+    summary: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    all_day: bool,
+}
+
+// ── Database helpers ────────────────────────────────────────
+
+fn open_db(db_path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to set pragmas: {e}"))?;
+    Ok(conn)
+}
+
+fn read_setting(conn: &Connection, key: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM user_settings WHERE key = ?1",
+        [key],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+fn format_timestamp(dt: DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+const SOURCE_COLUMNS: &str = "s.id, s.location, s.name, \
+     (SELECT COUNT(*) FROM calendar_events e WHERE e.source_id = s.id), \
+     s.last_imported_at, s.last_error, s.unknown_time_zones, s.created_at";
+
+fn row_to_source(row: &rusqlite::Row<'_>) -> rusqlite::Result<CalendarSource> {
+    let unknown_time_zones: String = row.get(6)?;
+    Ok(CalendarSource {
+        id: row.get(0)?,
+        location: row.get(1)?,
+        name: row.get(2)?,
+        event_count: row.get(3)?,
+        last_imported_at: row.get(4)?,
+        last_error: row.get(5)?,
+        unknown_time_zones: serde_json::from_str(&unknown_time_zones).unwrap_or_default(),
+        created_at: row.get(7)?,
+    })
+}
+
+fn get_source(conn: &Connection, id: i64) -> Result<CalendarSource, String> {
+    conn.query_row(
+        &format!("SELECT {SOURCE_COLUMNS} FROM calendar_sources s WHERE s.id = ?1"),
+        [id],
+        row_to_source,
+    )
+    .map_err(|e| format!("Calendar not found: {e}"))
+}
+
+fn query_sources(conn: &Connection) -> Result<Vec<CalendarSource>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {SOURCE_COLUMNS} FROM calendar_sources s ORDER BY s.id"
+        ))
+        .map_err(|e| format!("Failed to prepare calendar query: {e}"))?;
+
+    let sources = stmt
+        .query_map([], row_to_source)
+        .map_err(|e| format!("Failed to query calendars: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read calendars: {e}"))?;
+
+    Ok(sources)
+}
+
+/// Register a source, or rename an existing one with the same location.
+fn upsert_source(conn: &Connection, location: &str, name: Option<&str>) -> Result<i64, String> {
+    conn.query_row(
+        "INSERT INTO calendar_sources (location, name) VALUES (?1, ?2)
+         ON CONFLICT (location) DO UPDATE SET name = COALESCE(excluded.name, name)
+         RETURNING id",
+        rusqlite::params![location, name],
+        |row| row.get(0),
+    )
+    .map_err(|e| format!("Failed to save calendar: {e}"))
+}
+
+/// Replace a source's events with a fresh import, or record why it failed.
+fn store_import(
+    conn: &mut Connection,
+    source_id: i64,
+    imported: Result<ParsedCalendar, String>,
+    now: DateTime<Utc>,
+) -> Result<CalendarSource, String> {
+    let calendar = match imported {
+        Ok(calendar) => calendar,
+        Err(e) => {
+            conn.execute(
+                "UPDATE calendar_sources SET last_error = ?1 WHERE id = ?2",
+                rusqlite::params![e, source_id],
+            )
+            .map_err(|e| format!("Failed to update calendar: {e}"))?;
+            return Err(e);
+        }
+    };
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start transaction: {e}"))?;
+    tx.execute(
+        "DELETE FROM calendar_events WHERE source_id = ?1",
+        [source_id],
+    )
+    .map_err(|e| format!("Failed to clear calendar events: {e}"))?;
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT OR IGNORE INTO calendar_events (source_id, uid, summary, start_time, end_time, all_day)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )
+            .map_err(|e| format!("Failed to prepare event insert: {e}"))?;
+        for event in &calendar.events {
+            insert
+                .execute(rusqlite::params![
+                    source_id,
+                    event.uid,
+                    event.summary,
+                    format_timestamp(event.start),
+                    format_timestamp(event.end),
+                    event.all_day
+                ])
+                .map_err(|e| format!("Failed to insert calendar event: {e}"))?;
+        }
+    }
+    let unknown_time_zones = serde_json::to_string(&calendar.unknown_time_zones)
+        .map_err(|e| format!("Failed to serialize time zones: {e}"))?;
+    tx.execute(
+        "UPDATE calendar_sources
+         SET last_imported_at = ?1, last_error = NULL, unknown_time_zones = ?2
+         WHERE id = ?3",
+        rusqlite::params![format_timestamp(now), unknown_time_zones, source_id],
+    )
+    .map_err(|e| format!("Failed to update calendar: {e}"))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit calendar import: {e}"))?;
+
+    get_source(conn, source_id)
+}
+
+/// Timed (not all-day) events overlapping `[start, end)`.
+fn query_events_between(
+    conn: &Connection,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<CalendarEvent>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, source_id, uid, summary, start_time, end_time, all_day
+             FROM calendar_events
+             WHERE all_day = 0 AND start_time < ?2 AND end_time > ?1
+             ORDER BY start_time, end_time, id",
+        )
+        .map_err(|e| format!("Failed to prepare event query: {e}"))?;
+
+    let events = stmt
+        .query_map([format_timestamp(start), format_timestamp(end)], |row| {
+            Ok(CalendarEvent {
+                id: row.get(0)?,
+                source_id: row.get(1)?,
+                uid: row.get(2)?,
+                summary: row.get(3)?,
+                start_time: row.get(4)?,
+                end_time: row.get(5)?,
+                all_day: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query events: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read events: {e}"))?;
+
+    Ok(events)
+}
+
+// ── iCalendar parsing ───────────────────────────────────────
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Join folded content lines (RFC 5545 §3.1).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(previous)) => previous.push_str(rest),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter value
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let mut head = line[..colon].split(';');
+    let name = head.next()?.to_ascii_uppercase();
+    let params = head
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    Some(Property {
+        name,
+        params,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
+
+/// Windows time zone names, as Outlook and Exchange write them in TZID, and
+/// the IANA zone for each (from CLDR's windowsZones, territory 001).
+const WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("Pacific Standard Time (Mexico)", "America/Tijuana"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time (Mexico)", "America/Mazatlan"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time (Mexico)", "America/Cancun"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("US Eastern Standard Time", "America/Indiana/Indianapolis"),
+    ("Cuba Standard Time", "America/Havana"),
+    ("Haiti Standard Time", "America/Port-au-Prince"),
+    ("Turks And Caicos Standard Time", "America/Grand_Turk"),
+    ("Paraguay Standard Time", "America/Asuncion"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Venezuela Standard Time", "America/Caracas"),
+    ("Central Brazilian Standard Time", "America/Cuiaba"),
+    ("SA Western Standard Time", "America/La_Paz"),
+    ("Pacific SA Standard Time", "America/Santiago"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("SA Eastern Standard Time", "America/Cayenne"),
+    ("Greenland Standard Time", "America/Nuuk"),
+    ("Montevideo Standard Time", "America/Montevideo"),
+    ("Bahia Standard Time", "America/Bahia"),
+    ("UTC-02", "Etc/GMT+2"),
+    ("Azores Standard Time", "Atlantic/Azores"),
+    ("Cape Verde Standard Time", "Atlantic/Cape_Verde"),
+    ("UTC", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("Morocco Standard Time", "Africa/Casablanca"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("Jordan Standard Time", "Asia/Amman"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("Middle East Standard Time", "Asia/Beirut"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("Syria Standard Time", "Asia/Damascus"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("FLE Standard Time", "Europe/Kyiv"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("Kaliningrad Standard Time", "Europe/Kaliningrad"),
+    ("Namibia Standard Time", "Africa/Windhoek"),
+    ("Arabic Standard Time", "Asia/Baghdad"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Belarus Standard Time", "Europe/Minsk"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("E. Africa Standard Time", "Africa/Nairobi"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Azerbaijan Standard Time", "Asia/Baku"),
+    ("Mauritius Standard Time", "Indian/Mauritius"),
+    ("Georgian Standard Time", "Asia/Tbilisi"),
+    ("Caucasus Standard Time", "Asia/Yerevan"),
+    ("Afghanistan Standard Time", "Asia/Kabul"),
+    ("West Asia Standard Time", "Asia/Tashkent"),
+    ("Ekaterinburg Standard Time", "Asia/Yekaterinburg"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("India Standard Time", "Asia/Kolkata"),
+    ("Sri Lanka Standard Time", "Asia/Colombo"),
+    ("Nepal Standard Time", "Asia/Kathmandu"),
+    ("Central Asia Standard Time", "Asia/Almaty"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("Myanmar Standard Time", "Asia/Yangon"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("N. Central Asia Standard Time", "Asia/Novosibirsk"),
+    ("North Asia Standard Time", "Asia/Krasnoyarsk"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("North Asia East Standard Time", "Asia/Irkutsk"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("Ulaanbaatar Standard Time", "Asia/Ulaanbaatar"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("Yakutsk Standard Time", "Asia/Yakutsk"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("West Pacific Standard Time", "Pacific/Port_Moresby"),
+    ("Tasmania Standard Time", "Australia/Hobart"),
+    ("Vladivostok Standard Time", "Asia/Vladivostok"),
+    ("Central Pacific Standard Time", "Pacific/Guadalcanal"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+    ("UTC+12", "Etc/GMT-12"),
+    ("Fiji Standard Time", "Pacific/Fiji"),
+    ("Tonga Standard Time", "Pacific/Tongatapu"),
+    ("Samoa Standard Time", "Pacific/Apia"),
+];
+
+/// Resolve a TZID parameter: an IANA name (optionally with a leading `/`)
+/// or a Windows zone name.
+fn resolve_tzid(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim().trim_start_matches('/');
+    tzid.parse().ok().or_else(|| {
+        WINDOWS_ZONES
+            .iter()
+            .find(|(windows, _)| windows.eq_ignore_ascii_case(tzid))
+            .and_then(|(_, iana)| iana.parse().ok())
+    })
+}
+
+/// The time zone a DATE-TIME value is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Zone {
+    Utc,
+    Named(Tz),
+    /// No zone, or one we don't know: the user's own local time.
+    Floating,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IcsTime {
+    naive: NaiveDateTime,
+    zone: Zone,
+    date_only: bool,
+}
+
+fn parse_time(prop: &Property) -> Option<IcsTime> {
+    let value = prop.value.trim();
+    if prop.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(IcsTime {
+            naive: date.and_time(NaiveTime::MIN),
+            zone: Zone::Floating,
+            date_only: true,
+        });
+    }
+    let (value, zone) = match value.strip_suffix('Z') {
+        Some(utc) => (utc, Zone::Utc),
+        None => (
+            value,
+            prop.param("TZID")
+                .and_then(resolve_tzid)
+                .map_or(Zone::Floating, Zone::Named),
+        ),
+    };
+    Some(IcsTime {
+        naive: NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?,
+        zone,
+        date_only: false,
+    })
+}
+
+/// Resolve a wall-clock time in `tz`, moving times in a DST gap forward.
+fn resolve_local<Z: TimeZone>(tz: &Z, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    let resolved = match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => Some(dt),
+        LocalResult::None => naive
+            .checked_add_signed(Duration::hours(1))
+            .and_then(|later| tz.from_local_datetime(&later).earliest()),
+    };
+    resolved.map(|dt| dt.with_timezone(&Utc))
+}
+
+fn to_utc<Z: TimeZone>(naive: NaiveDateTime, zone: Zone, local: &Z) -> Option<DateTime<Utc>> {
+    match zone {
+        Zone::Utc => Some(Utc.from_utc_datetime(&naive)),
+        Zone::Named(tz) => resolve_local(&tz, naive),
+        Zone::Floating => resolve_local(local, naive),
+    }
+}
+
+/// Parse a DURATION value such as `PT1H30M` or `P1D`, or `None` if it is
+/// malformed or too long to represent.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                let part = match unit {
+                    'W' => Duration::try_weeks(n),
+                    'D' => Duration::try_days(n),
+                    'H' => Duration::try_hours(n),
+                    'M' => Duration::try_minutes(n),
+                    'S' => Duration::try_seconds(n),
+                    _ => None,
+                }?;
+                total = total.checked_add(&part)?;
+            }
+        }
+    }
+    Some(if negative { -total } else { total })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RRULE needed for meeting series: FREQ, INTERVAL, COUNT,
+/// UNTIL, BYDAY and BYMONTHDAY. Other parts are ignored.
+#[derive(Debug, Clone)]
+struct Rrule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<IcsTime>,
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    Some(match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn parse_rrule(value: &str) -> Option<Rrule> {
+    let mut rule = Rrule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        by_month_day: Vec::new(),
+    };
+    let mut frequency = None;
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => None,
+                };
+            }
+            "INTERVAL" => {
+                rule.interval = value
+                    .parse()
+                    .ok()
+                    .filter(|n| (1..=MAX_RRULE_INTERVAL).contains(n))?;
+            }
+            "COUNT" => rule.count = value.parse().ok(),
+            "UNTIL" => {
+                rule.until = parse_time(&Property {
+                    name: "UNTIL".into(),
+                    params: Vec::new(),
+                    value: value.to_string(),
+                });
+            }
+            "BYDAY" => {
+                rule.by_day = value
+                    .split(',')
+                    .filter_map(|day| {
+                        let day = day.trim().to_ascii_uppercase();
+                        let (ordinal, code) = day.split_at(day.len().checked_sub(2)?);
+                        let ordinal = if ordinal.is_empty() {
+                            None
+                        } else {
+                            Some(ordinal.trim_start_matches('+').parse().ok()?)
+                        };
+                        Some((ordinal, parse_weekday(code)?))
+                    })
+                    .collect();
+            }
+            "BYMONTHDAY" => {
+                rule.by_month_day = value.split(',').filter_map(|d| d.parse().ok()).collect();
+            }
+            _ => {}
+        }
+    }
+    rule.frequency = frequency?;
+    Some(rule)
+}
+
+fn add_months(year: i32, month0: u32, offset: i64) -> Option<(i32, u32)> {
+    let index = (i64::from(year) * 12 + i64::from(month0)).checked_add(offset)?;
+    Some((
+        i32::try_from(index.div_euclid(12)).ok()?,
+        u32::try_from(index.rem_euclid(12)).ok()?,
+    ))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map_or(28, |d| d.day())
+}
+
+/// Dates in a month matching `BYDAY` (with optional ordinals like `-1FR`).
+fn month_weekdays(year: i32, month: u32, by_day: &[(Option<i32>, Weekday)]) -> Vec<NaiveDate> {
+    let days: Vec<NaiveDate> = (1..=days_in_month(year, month))
+        .filter_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .collect();
+    let mut dates = Vec::new();
+    for (ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = days
+            .iter()
+            .copied()
+            .filter(|d| d.weekday() == *weekday)
+            .collect();
+        match ordinal {
+            None => dates.extend(matching),
+            Some(n) if *n > 0 => {
+                dates.extend(usize::try_from(*n - 1).ok().and_then(|i| matching.get(i)));
+            }
+            Some(n) => dates.extend(
+                usize::try_from(-*n)
+                    .ok()
+                    .and_then(|back| matching.len().checked_sub(back))
+                    .and_then(|i| matching.get(i)),
+            ),
+        }
+    }
+    dates
+}
+
+/// Candidate dates for the `period`th repetition of a rule, in order, or
+/// `None` once the repetition lies past the last representable date.
+fn period_dates(rule: &Rrule, first: NaiveDate, period: i64) -> Option<Vec<NaiveDate>> {
+    let step = period.checked_mul(i64::from(rule.interval))?;
+    let add_days = |date: NaiveDate, days: i64| -> Option<NaiveDate> {
+        date.checked_add_signed(Duration::try_days(days)?)
+    };
+    let mut dates = match rule.frequency {
+        Frequency::Daily => vec![add_days(first, step)?],
+        Frequency::Weekly => {
+            let weekday = i64::from(first.weekday().num_days_from_monday());
+            let week = add_days(first, -weekday)?.checked_add_signed(Duration::try_weeks(step)?)?;
+            if rule.by_day.is_empty() {
+                vec![add_days(week, weekday)?]
+            } else {
+                rule.by_day
+                    .iter()
+                    .map(|(_, wd)| add_days(week, i64::from(wd.num_days_from_monday())))
+                    .collect::<Option<_>>()?
+            }
+        }
+        Frequency::Monthly => {
+            let (year, month0) = add_months(first.year(), first.month0(), step)?;
+            let month = month0 + 1;
+            NaiveDate::from_ymd_opt(year, month, 1)?;
+            if !rule.by_day.is_empty() {
+                month_weekdays(year, month, &rule.by_day)
+            } else if !rule.by_month_day.is_empty() {
+                let last = i32::try_from(days_in_month(year, month)).unwrap_or(28);
+                rule.by_month_day
+                    .iter()
+                    .filter_map(|d| {
+                        let day = if *d < 0 { last + d + 1 } else { *d };
+                        NaiveDate::from_ymd_opt(year, month, u32::try_from(day).ok()?)
+                    })
+                    .collect()
+            } else {
+                NaiveDate::from_ymd_opt(year, month, first.day())
+                    .into_iter()
+                    .collect()
+            }
+        }
+        Frequency::Yearly => {
+            let year = first.year().checked_add(i32::try_from(step).ok()?)?;
+            NaiveDate::from_ymd_opt(year, 1, 1)?;
+            NaiveDate::from_ymd_opt(year, first.month(), first.day())
+                .into_iter()
+                .collect()
+        }
+    };
+    if rule.frequency == Frequency::Daily && !rule.by_day.is_empty() {
+        dates.retain(|d| rule.by_day.iter().any(|(_, wd)| *wd == d.weekday()));
+    }
+    dates.sort_unstable();
+    dates.dedup();
+    Some(dates)
+}
+
+/// Repetitions of a rule that end before `from`, so a long-running series
+/// can be expanded from near the window instead of from its first date.
+fn periods_before(rule: &Rrule, first: NaiveDate, from: NaiveDate) -> i64 {
+    let elapsed = match rule.frequency {
+        Frequency::Daily => (from - first).num_days(),
+        // Weeks count from the Monday of the first one
+        Frequency::Weekly => {
+            ((from - first).num_days() + i64::from(first.weekday().num_days_from_monday())) / 7
+        }
+        Frequency::Monthly => {
+            i64::from(from.year() - first.year()) * 12 + i64::from(from.month0())
+                - i64::from(first.month0())
+        }
+        Frequency::Yearly => i64::from(from.year() - first.year()),
+    };
+    (elapsed / i64::from(rule.interval)).max(0)
+}
+
+/// Wall-clock start times of a recurring event, up to the end of `window`.
+/// Repetitions well before the window are skipped where that leaves COUNT
+/// intact, so the start of the list can precede the window.
+fn expand_rrule<Z: TimeZone>(
+    rule: &Rrule,
+    start: &IcsTime,
+    local: &Z,
+    window: (DateTime<Utc>, DateTime<Utc>),
+) -> Vec<NaiveDateTime> {
+    let horizon = window.1;
+    let until = rule.until.and_then(|until| {
+        let zone = if until.zone == Zone::Utc {
+            Zone::Utc
+        } else {
+            start.zone
+        };
+        let naive = if until.date_only {
+            until
+                .naive
+                .checked_add_signed(Duration::days(1) - Duration::seconds(1))?
+        } else {
+            until.naive
+        };
+        to_utc(naive, zone, local)
+    });
+    let time = start.naive.time();
+    let mut starts = Vec::new();
+    // A plain daily or weekly repetition is exactly one occurrence, so
+    // skipped ones can still be counted
+    let one_per_period =
+        rule.by_day.is_empty() && matches!(rule.frequency, Frequency::Daily | Frequency::Weekly);
+    // A day of slack covers any difference between UTC and the event's
+    // wall clock
+    let skipped = match window.0.checked_sub_signed(Duration::days(1)) {
+        Some(from) if rule.count.is_none() || one_per_period => {
+            periods_before(rule, start.naive.date(), from.date_naive())
+        }
+        _ => 0,
+    };
+    let mut produced = if one_per_period {
+        u32::try_from(skipped).unwrap_or(u32::MAX)
+    } else {
+        0
+    };
+    for period in skipped..skipped.saturating_add(MAX_RECURRENCE_PERIODS) {
+        let Some(dates) = period_dates(rule, start.naive.date(), period) else {
+            break;
+        };
+        for date in dates {
+            let naive = date.and_time(time);
+            if naive < start.naive {
+                continue;
+            }
+            let Some(utc) = to_utc(naive, start.zone, local) else {
+                continue;
+            };
+            if until.is_some_and(|until| utc > until) || utc > horizon {
+                return starts;
+            }
+            if rule.count.is_some_and(|count| produced >= count) {
+                return starts;
+            }
+            produced += 1;
+            starts.push(naive);
+        }
+    }
+    starts
+}
+
+#[derive(Default)]
+struct RawEvent {
+    uid: String,
+    summary: String,
+    start: Option<IcsTime>,
+    end: Option<IcsTime>,
+    duration: Option<Duration>,
+    rrule: Option<Rrule>,
+    exdates: Vec<IcsTime>,
+    recurrence_id: Option<IcsTime>,
+    cancelled: bool,
+    transparent: bool,
+    /// TZIDs on this event's times that `resolve_tzid` doesn't know.
+    unknown_time_zones: Vec<String>,
+}
+
+impl RawEvent {
+    fn apply(&mut self, prop: &Property) {
+        if let Some(tzid) = prop
+            .param("TZID")
+            .filter(|tzid| resolve_tzid(tzid).is_none())
+        {
+            self.unknown_time_zones.push(tzid.to_string());
+        }
+        match prop.name.as_str() {
+            "UID" => self.uid = prop.value.trim().to_string(),
+            "SUMMARY" => self.summary = unescape_text(&prop.value),
+            "DTSTART" => self.start = parse_time(prop),
+            "DTEND" => self.end = parse_time(prop),
+            "DURATION" => self.duration = parse_duration(&prop.value),
+            "RRULE" => self.rrule = parse_rrule(&prop.value),
+            "EXDATE" => self
+                .exdates
+                .extend(prop.value.split(',').filter_map(|value| {
+                    parse_time(&Property {
+                        name: prop.name.clone(),
+                        params: prop.params.clone(),
+                        value: value.to_string(),
+                    })
+                })),
+            "RECURRENCE-ID" => self.recurrence_id = parse_time(prop),
+            "STATUS" => self.cancelled = prop.value.trim().eq_ignore_ascii_case("CANCELLED"),
+            "TRANSP" => self.transparent = prop.value.trim().eq_ignore_ascii_case("TRANSPARENT"),
+            _ => {}
+        }
+    }
+
+    /// Whether the event takes up time that could otherwise be focused.
+    fn is_busy(&self) -> bool {
+        !self.cancelled && !self.transparent
+    }
+}
+
+fn read_events(text: &str) -> Result<Vec<RawEvent>, String> {
+    let lines = unfold(text);
+    if !lines
+        .iter()
+        .any(|l| l.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("Not an iCalendar file".into());
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+    // Depth of components nested inside the current event (e.g. VALARM)
+    let mut nested = 0;
+    for line in &lines {
+        let Some(prop) = parse_property(line) else {
+            continue;
+        };
+        let component = prop.value.trim().to_ascii_uppercase();
+        match (prop.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if component == "VEVENT" => current = Some(RawEvent::default()),
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if component == "VEVENT" => events.extend(current.take()),
+            (_, Some(event)) if nested == 0 => event.apply(&prop),
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+/// Parse an iCalendar document into event occurrences, expanding recurring
+/// events that fall within `window`. Floating times and unknown TZIDs are
+/// read as `local` time; the unknown TZIDs are reported with the events.
+/// Cancelled and free (transparent) events are skipped.
+fn parse_ics<Z: TimeZone>(
+    text: &str,
+    local: &Z,
+    window: (DateTime<Utc>, DateTime<Utc>),
+) -> Result<ParsedCalendar, String> {
+    let raw = read_events(text)?;
+    let unknown_time_zones: BTreeSet<String> = raw
+        .iter()
+        .flat_map(|event| event.unknown_time_zones.iter().cloned())
+        .collect();
+
+    // Instances of a series that were moved or cancelled individually
+    let mut overridden: HashMap<&str, HashSet<DateTime<Utc>>> = HashMap::new();
+    for event in &raw {
+        if let Some(id) = &event.recurrence_id {
+            if let Some(utc) = to_utc(id.naive, id.zone, local) {
+                overridden
+                    .entry(event.uid.as_str())
+                    .or_default()
+                    .insert(utc);
+            }
+        }
+    }
+
+    let mut parsed = Vec::new();
+    for event in raw.iter().filter(|e| e.is_busy()) {
+        let Some(start) = event.start else {
+            continue;
+        };
+        // Length on the wall clock, so a series keeps its local times across DST
+        let length = match (event.end, event.duration) {
+            (Some(end), _) => end.naive - start.naive,
+            (None, Some(duration)) => duration,
+            (None, None) if start.date_only => Duration::days(1),
+            (None, None) => Duration::zero(),
+        };
+
+        let starts = match (&event.rrule, &event.recurrence_id) {
+            (Some(rule), None) => {
+                let skipped: HashSet<DateTime<Utc>> = event
+                    .exdates
+                    .iter()
+                    .filter_map(|t| to_utc(t.naive, t.zone, local))
+                    .chain(
+                        overridden
+                            .get(event.uid.as_str())
+                            .into_iter()
+                            .flatten()
+                            .copied(),
+                    )
+                    .collect();
+                expand_rrule(rule, &start, local, window)
+                    .into_iter()
+                    .filter(|naive| {
+                        to_utc(*naive, start.zone, local)
+                            .is_some_and(|utc| utc >= window.0 && !skipped.contains(&utc))
+                    })
+                    .collect()
+            }
+            _ => vec![start.naive],
+        };
+
+        for naive in starts {
+            let (Some(begin), Some(end)) = (
+                to_utc(naive, start.zone, local),
+                naive
+                    .checked_add_signed(length)
+                    .and_then(|end| to_utc(end, start.zone, local)),
+            ) else {
+                continue;
+            };
+            parsed.push(ParsedEvent {
+                uid: event.uid.clone(),
+                summary: event.summary.clone(),
+                start: begin,
+                end: end.max(begin),
+                all_day: start.date_only,
+            });
+        }
+    }
+    parsed.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.uid.cmp(&b.uid)));
+    Ok(ParsedCalendar {
+        events: parsed,
+        unknown_time_zones: unknown_time_zones.into_iter().collect(),
+    })
+}
+
+// ── Import ──────────────────────────────────────────────────
+
+/// Read a calendar from a local path or an `http(s)://` / `webcal://` URL.
+async fn fetch(location: &str) -> Result<String, String> {
+    let url = match location.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{rest}"),
+        None => location.to_string(),
+    };
+    if url.starts_with("http://") || url.starts_with("https://") {
+        let response = reqwest::Client::new()
+            .get(&url)
+            .timeout(FETCH_TIMEOUT)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| format!("Failed to download calendar: {e}"))?;
+        return response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to download calendar: {e}"));
+    }
+    let path = location.strip_prefix("file://").unwrap_or(location);
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read calendar {path}: {e}"))
+}
+
+/// Fetch and parse a source, replacing its previously imported events.
+async fn import_source(
+    db_path: &Path,
+    location: &str,
+    name: Option<&str>,
+) -> Result<CalendarSource, String> {
+    let location = location.trim();
+    if location.is_empty() {
+        return Err("Calendar location must not be empty".into());
+    }
+    let source_id = upsert_source(&open_db(db_path)?, location, name)?;
+
+    let now = Utc::now();
+    let window = (
+        now - Duration::days(EXPANSION_PAST_DAYS),
+        now + Duration::days(EXPANSION_FUTURE_DAYS),
+    );
+    let imported = fetch(location)
+        .await
+        .and_then(|text| parse_ics(&text, &Local, window));
+    store_import(&mut open_db(db_path)?, source_id, imported, now)
+}
+
+// ── Planning ────────────────────────────────────────────────
+
+fn workday_time(conn: &Connection, key: &str, default: &str) -> NaiveTime {
+    read_setting(conn, key)
+        .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok())
+        .unwrap_or_else(|| NaiveTime::parse_from_str(default, "%H:%M").unwrap_or(NaiveTime::MIN))
+}
+
+/// Spans of `[start, end)` not covered by any meeting.
+fn free_spans(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    meetings: &[CalendarEvent],
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut spans = Vec::new();
+    let mut cursor = start;
+    for meeting in meetings {
+        let (Some(busy_from), Some(busy_until)) = (
+            parse_timestamp(&meeting.start_time),
+            parse_timestamp(&meeting.end_time),
+        ) else {
+            continue;
+        };
+        if busy_from > cursor {
+            spans.push((cursor, busy_from.min(end)));
+        }
+        cursor = cursor.max(busy_until);
+        if cursor >= end {
+            return spans;
+        }
+    }
+    if cursor < end {
+        spans.push((cursor, end));
+    }
+    spans
+}
+
+/// Work intervals that fit in `seconds`, each followed by the break due
+/// after it except the last. `completed` carries the day's count so far
+/// so long breaks fall where the timer would put them.
+fn pomodoros_in(seconds: i64, settings: &IntervalSettings, completed: &mut u32) -> u32 {
+    let work = i64::from(settings.duration_seconds(IntervalType::Work));
+    let mut fitted = 0;
+    let mut used = 0;
+    while used + work <= seconds {
+        fitted += 1;
+        *completed += 1;
+        let break_type = break_after(*completed, settings.long_break_frequency);
+        used += work + i64::from(settings.duration_seconds(break_type));
+    }
+    fitted
+}
+
+/// Lay out the working hours of `day` in the `local` time zone around its
+/// meetings.
+fn plan_day<Z: TimeZone>(
+    conn: &Connection,
+    day: NaiveDate,
+    local: &Z,
+    settings: &IntervalSettings,
+) -> Result<DayPlan, String> {
+    let workday_start = workday_time(conn, "workday_start", DEFAULT_WORKDAY_START);
+    let workday_end = workday_time(conn, "workday_end", DEFAULT_WORKDAY_END);
+    if workday_end <= workday_start {
+        return Err("Workday end must be after its start".into());
+    }
+    let (Some(start), Some(end)) = (
+        resolve_local(local, day.and_time(workday_start)),
+        resolve_local(local, day.and_time(workday_end)),
+    ) else {
+        return Err(format!("Invalid working hours on {day}"));
+    };
+
+    let meetings = query_events_between(conn, start, end)?;
+    let mut completed = 0;
+    let free_blocks: Vec<FreeBlock> = free_spans(start, end, &meetings)
+        .into_iter()
+        .map(|(from, until)| {
+            let seconds = (until - from).num_seconds();
+            FreeBlock {
+                start_time: format_timestamp(from),
+                end_time: format_timestamp(until),
+                minutes: seconds / 60,
+                pomodoros: pomodoros_in(seconds, settings, &mut completed),
+            }
+        })
+        .collect();
+
+    Ok(DayPlan {
+        day_date: day.format("%Y-%m-%d").to_string(),
+        workday_start: format_timestamp(start),
+        workday_end: format_timestamp(end),
+        meetings,
+        free_minutes: free_blocks.iter().map(|b| b.minutes).sum(),
+        pomodoros: free_blocks.iter().map(|b| b.pomodoros).sum(),
+        free_blocks,
+    })
+}
+
+/// The first meeting overlapping `[start, end)`, if any.
+fn find_conflict(
+    conn: &Connection,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Option<MeetingConflict>, String> {
+    let Some(meeting) = query_events_between(conn, start, end)?.into_iter().next() else {
+        return Ok(None);
+    };
+    let minutes_until =
+        parse_timestamp(&meeting.start_time).map_or(0, |at| (at - start).num_minutes().max(0));
+    Ok(Some(MeetingConflict {
+        summary: meeting.summary,
+        start_time: meeting.start_time,
+        end_time: meeting.end_time,
+        minutes_until,
+    }))
+}
+
+/// Emit `meeting-conflict` if the running work interval would run into a
+/// meeting, or `meeting-check-error` if the meetings couldn't be read.
+/// Called whenever the timer starts or resumes ticking.
+pub(crate) fn warn_on_meeting_conflict<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<AppState>();
+    let Ok(status) = state.timer.lock().map(|timer| timer.status()) else {
+        return;
+    };
+    if status.state != TimerState::Running
+        || status.interval_type != IntervalType::Work
+        || status.overtime
+    {
+        return;
+    }
+    let conflict = open_db(&state.db_path).and_then(|conn| {
+        if read_setting(&conn, "meeting_warning_enabled").as_deref() != Some("true") {
+            return Ok(None);
+        }
+        let now = state.clock.utc_now();
+        let remaining = Duration::milliseconds(i64::try_from(status.remaining_ms).unwrap_or(0));
+        find_conflict(&conn, now, now + remaining)
+    });
+    match conflict {
+        Ok(Some(conflict)) => {
+            let _ = app.emit("meeting-conflict", conflict);
+        }
+        Ok(None) => {}
+        Err(e) => {
+            let _ = app.emit("meeting-check-error", e);
+        }
+    }
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+/// Import a calendar from a file path or URL, or re-import it if known.
+#[tauri::command]
+pub async fn import_calendar(
+    state: tauri::State<'_, AppState>,
+    location: String,
+    name: Option<String>,
+) -> Result<CalendarSource, String> {
+    import_source(&state.db_path, &location, name.as_deref()).await
+}
+
+/// Re-import every calendar. Failures are recorded on the source rather
+/// than aborting the rest.
+#[tauri::command]
+pub async fn refresh_calendars(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<CalendarSource>, String> {
+    let db_path = state.db_path.clone();
+    for source in query_sources(&open_db(&db_path)?)? {
+        let _ = import_source(&db_path, &source.location, None).await;
+    }
+    query_sources(&open_db(&db_path)?)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn list_calendar_sources(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<CalendarSource>, String> {
+    query_sources(&open_db(&state.db_path)?)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn delete_calendar_source(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    open_db(&state.db_path)?
+        .execute("DELETE FROM calendar_sources WHERE id = ?1", [id])
+        .map_err(|e| format!("Failed to delete calendar: {e}"))?;
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_day_plan(
+    state: tauri::State<'_, AppState>,
+    day_date: String,
+) -> Result<DayPlan, String> {
+    let day = NaiveDate::parse_from_str(&day_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date '{day_date}': {e}"))?;
+    let settings = IntervalSettings::load(&state.db_path)?;
+    plan_day(&open_db(&state.db_path)?, day, &Local, &settings)
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/meetings.ics");
+
+    fn setup_test_db_file(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("pomo-meetings-{}-{name}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+        path
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        parse_timestamp(value).unwrap()
+    }
+
+    fn window() -> (DateTime<Utc>, DateTime<Utc>) {
+        (utc("2026-01-01T00:00:00Z"), utc("2026-12-31T00:00:00Z"))
+    }
+
+    fn fixture_events() -> Vec<ParsedEvent> {
+        parse_ics(FIXTURE, &Berlin, window()).unwrap().events
+    }
+
+    fn starts_of(events: &[ParsedEvent], uid: &str) -> Vec<String> {
+        events
+            .iter()
+            .filter(|e| e.uid == uid)
+            .map(|e| format_timestamp(e.start))
+            .collect()
+    }
+
+    fn import_fixture(db_path: &Path) -> CalendarSource {
+        let mut conn = open_db(db_path).unwrap();
+        let source_id = upsert_source(&conn, "fixture.ics", Some("Work")).unwrap();
+        let calendar = parse_ics(FIXTURE, &Berlin, window()).unwrap();
+        store_import(&mut conn, source_id, Ok(calendar), Utc::now()).unwrap()
+    }
+
+    #[test]
+    fn unfolds_and_unescapes() {
+        let lines = unfold("SUMMARY:Design\r\n  review\\, part\r\n\tone\r\n");
+        assert_eq!(lines, vec!["SUMMARY:Design review\\, partone".to_string()]);
+        let prop = parse_property("DTSTART;TZID=\"America/New_York\":20260302T090000").unwrap();
+        assert_eq!(prop.name, "DTSTART");
+        assert_eq!(prop.param("TZID"), Some("America/New_York"));
+        assert_eq!(prop.value, "20260302T090000");
+        assert_eq!(unescape_text("a\\, b\\; c\\nd\\\\"), "a, b; c\nd\\");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1DT2S"), Some(Duration::seconds(86_402)));
+        assert_eq!(parse_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("-PT5M"), Some(Duration::minutes(-5)));
+        assert_eq!(parse_duration("1H"), None);
+        assert_eq!(parse_duration("P99999999999999D"), None);
+        assert_eq!(parse_duration("P100000000000DT1000000000000000S"), None);
+    }
+
+    #[test]
+    fn oversized_durations_and_intervals_are_skipped() {
+        let ics = "BEGIN:VCALENDAR\n\
+                   BEGIN:VEVENT\nUID:endless\nDTSTART:20260302T090000Z\n\
+                   DURATION:P99999999999999D\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:far\nDTSTART:20260302T100000Z\n\
+                   DURATION:P100000000D\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:sparse\nDTSTART:20260302T110000Z\nDURATION:PT15M\n\
+                   RRULE:FREQ=DAILY;INTERVAL=100000000\nEND:VEVENT\n\
+                   END:VCALENDAR\n";
+        let events = parse_ics(ics, &Utc, window()).unwrap().events;
+        // No length to fall back on, an end past the last date, and an
+        // unsupported rule read as a single occurrence
+        assert_eq!(starts_of(&events, "endless"), vec!["2026-03-02T09:00:00Z"]);
+        assert!(starts_of(&events, "far").is_empty());
+        assert_eq!(starts_of(&events, "sparse"), vec!["2026-03-02T11:00:00Z"]);
+    }
+
+    #[test]
+    fn old_series_still_reach_the_window() {
+        let ics = "BEGIN:VCALENDAR\n\
+                   BEGIN:VEVENT\nUID:daily\nDTSTART:19900105T090000Z\nDURATION:PT15M\n\
+                   RRULE:FREQ=DAILY\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:weekly\nDTSTART:19900105T100000Z\nDURATION:PT15M\n\
+                   RRULE:FREQ=WEEKLY;INTERVAL=3;BYDAY=MO,FR\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:monthly\nDTSTART:11000105T110000Z\nDURATION:PT15M\n\
+                   RRULE:FREQ=MONTHLY\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:counted\nDTSTART:19900105T120000Z\nDURATION:PT15M\n\
+                   RRULE:FREQ=DAILY;COUNT=20000\nEND:VEVENT\n\
+                   END:VCALENDAR\n";
+        let window = (utc("2026-03-02T00:00:00Z"), utc("2026-03-10T00:00:00Z"));
+        let events = parse_ics(ics, &Utc, window).unwrap().events;
+
+        let daily = starts_of(&events, "daily");
+        assert_eq!(daily.len(), 8);
+        assert_eq!(daily[0], "2026-03-02T09:00:00Z");
+        assert_eq!(daily[7], "2026-03-09T09:00:00Z");
+        // 1990-01-05 was a Friday; every third week from then includes
+        // the week of 2026-03-02
+        assert_eq!(
+            starts_of(&events, "weekly"),
+            vec!["2026-03-02T10:00:00Z", "2026-03-06T10:00:00Z"]
+        );
+        assert_eq!(starts_of(&events, "monthly"), vec!["2026-03-05T11:00:00Z"]);
+        // The 20000th day from 1990-01-05 is 2044-10-07, past the window
+        assert_eq!(starts_of(&events, "counted").len(), 8);
+    }
+
+    #[test]
+    fn series_stop_at_the_last_representable_date() {
+        let start = IcsTime {
+            naive: NaiveDate::MAX.and_hms_opt(9, 0, 0).unwrap(),
+            zone: Zone::Utc,
+            date_only: false,
+        };
+        for rrule in [
+            "FREQ=DAILY;INTERVAL=1000",
+            "FREQ=WEEKLY;BYDAY=MO,FR",
+            "FREQ=MONTHLY",
+            "FREQ=YEARLY",
+        ] {
+            let rule = parse_rrule(rrule).unwrap();
+            let starts = expand_rrule(
+                &rule,
+                &start,
+                &Utc,
+                (DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC),
+            );
+            assert!(starts.len() <= 1, "{rrule}: {starts:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_non_calendar_text() {
+        assert!(parse_ics("<html></html>", &Utc, window()).is_err());
+    }
+
+    #[test]
+    fn fixture_single_events() {
+        let events = fixture_events();
+        let review = events
+            .iter()
+            .find(|e| e.uid == "review-7@example.com")
+            .unwrap();
+        assert_eq!(review.summary, "Design review, checkout flow");
+        assert_eq!(format_timestamp(review.end), "2026-03-02T14:00:00Z");
+
+        // Floating time is read in the local zone
+        assert_eq!(
+            starts_of(&events, "lunch-2@example.com"),
+            vec!["2026-03-02T11:00:00Z"]
+        );
+        // DURATION instead of DTEND
+        let one_on_one = events
+            .iter()
+            .find(|e| e.uid == "one-on-one-6@example.com")
+            .unwrap();
+        assert_eq!(format_timestamp(one_on_one.end), "2026-03-05T10:30:00Z");
+
+        let offsite = events
+            .iter()
+            .find(|e| e.uid == "offsite-5@example.com")
+            .unwrap();
+        assert!(offsite.all_day);
+        assert_eq!(offsite.end - offsite.start, Duration::days(1));
+
+        // Cancelled and free events don't block time
+        assert!(starts_of(&events, "cancelled-3@example.com").is_empty());
+        assert!(starts_of(&events, "hold-4@example.com").is_empty());
+    }
+
+    #[test]
+    fn fixture_recurring_events() {
+        let events = fixture_events();
+        // Weekdays until Friday, without the excluded Wednesday; Tuesday's
+        // instance is replaced by its moved override
+        assert_eq!(
+            starts_of(&events, "standup-1@example.com"),
+            vec![
+                "2026-03-02T08:30:00Z",
+                "2026-03-03T09:00:00Z",
+                "2026-03-05T08:30:00Z",
+                "2026-03-06T08:30:00Z",
+            ]
+        );
+        let moved = events
+            .iter()
+            .find(|e| e.summary == "Team standup (moved)")
+            .unwrap();
+        assert_eq!(format_timestamp(moved.end), "2026-03-03T09:15:00Z");
+
+        assert_eq!(
+            starts_of(&events, "onboarding-8@example.com"),
+            vec![
+                "2026-03-09T13:00:00Z",
+                "2026-03-10T13:00:00Z",
+                "2026-03-11T13:00:00Z",
+            ]
+        );
+    }
+
+    #[test]
+    fn series_keep_local_time_across_dst() {
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:weekly\n\
+                   DTSTART;TZID=Europe/Berlin:20260323T093000\n\
+                   DTEND;TZID=Europe/Berlin:20260323T100000\n\
+                   RRULE:FREQ=WEEKLY;COUNT=2\nEND:VEVENT\nEND:VCALENDAR\n";
+        let events = parse_ics(ics, &Utc, window()).unwrap().events;
+        assert_eq!(
+            starts_of(&events, "weekly"),
+            vec!["2026-03-23T08:30:00Z", "2026-03-30T07:30:00Z"]
+        );
+        assert_eq!(events[1].end - events[1].start, Duration::minutes(30));
+    }
+
+    #[test]
+    fn monthly_rules_with_ordinals() {
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:retro\n\
+                   DTSTART:20260130T150000Z\nDURATION:PT1H\n\
+                   RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:billing\nDTSTART:20260131T090000Z\n\
+                   RRULE:FREQ=MONTHLY;COUNT=3\nEND:VEVENT\nEND:VCALENDAR\n";
+        let events = parse_ics(ics, &Utc, window()).unwrap().events;
+        assert_eq!(
+            starts_of(&events, "retro"),
+            vec![
+                "2026-01-30T15:00:00Z",
+                "2026-02-27T15:00:00Z",
+                "2026-03-27T15:00:00Z",
+            ]
+        );
+        // Months without a 31st are skipped
+        assert_eq!(
+            starts_of(&events, "billing"),
+            vec![
+                "2026-01-31T09:00:00Z",
+                "2026-03-31T09:00:00Z",
+                "2026-05-31T09:00:00Z",
+            ]
+        );
+    }
+
+    #[test]
+    fn windows_time_zone_names_resolve() {
+        for (windows, iana) in WINDOWS_ZONES {
+            assert!(iana.parse::<Tz>().is_ok(), "{windows} -> {iana}");
+        }
+        assert_eq!(resolve_tzid("W. Europe Standard Time"), Some(Berlin));
+        assert_eq!(resolve_tzid("/Europe/Berlin"), Some(Berlin));
+        assert_eq!(resolve_tzid("Mars Standard Time"), None);
+    }
+
+    #[test]
+    fn unknown_time_zones_are_reported() {
+        let ics = "BEGIN:VCALENDAR\n\
+                   BEGIN:VEVENT\nUID:outlook\n\
+                   DTSTART;TZID=W. Europe Standard Time:20260302T090000\n\
+                   DTEND;TZID=W. Europe Standard Time:20260302T093000\nEND:VEVENT\n\
+                   BEGIN:VEVENT\nUID:custom\n\
+                   DTSTART;TZID=Mars Standard Time:20260302T120000\n\
+                   DTEND;TZID=Mars Standard Time:20260302T123000\nEND:VEVENT\n\
+                   END:VCALENDAR\n";
+        let calendar = parse_ics(ics, &Utc, window()).unwrap();
+        assert_eq!(
+            starts_of(&calendar.events, "outlook"),
+            vec!["2026-03-02T08:00:00Z"]
+        );
+        // Read as local time, and reported once
+        assert_eq!(
+            starts_of(&calendar.events, "custom"),
+            vec!["2026-03-02T12:00:00Z"]
+        );
+        assert_eq!(calendar.unknown_time_zones, vec!["Mars Standard Time"]);
+
+        let db_path = setup_test_db_file("unknown-zones");
+        let mut conn = open_db(&db_path).unwrap();
+        let source_id = upsert_source(&conn, "outlook.ics", None).unwrap();
+        let source = store_import(&mut conn, source_id, Ok(calendar), Utc::now()).unwrap();
+        assert_eq!(source.unknown_time_zones, vec!["Mars Standard Time"]);
+
+        let source = store_import(
+            &mut conn,
+            source_id,
+            Ok(ParsedCalendar::default()),
+            Utc::now(),
+        )
+        .unwrap();
+        assert!(source.unknown_time_zones.is_empty());
+    }
+
+    #[test]
+    fn open_ended_series_stop_at_window() {
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:daily\n\
+                   DTSTART:20200101T090000Z\nDURATION:PT15M\n\
+                   RRULE:FREQ=DAILY\nEND:VEVENT\nEND:VCALENDAR\n";
+        let window = (utc("2026-03-01T00:00:00Z"), utc("2026-03-08T00:00:00Z"));
+        let events = parse_ics(ics, &Utc, window).unwrap().events;
+        assert_eq!(events.len(), 7);
+        assert_eq!(format_timestamp(events[0].start), "2026-03-01T09:00:00Z");
+    }
+
+    #[test]
+    fn reimport_replaces_events() {
+        let db_path = setup_test_db_file("reimport");
+        let source = import_fixture(&db_path);
+        assert_eq!(source.name.as_deref(), Some("Work"));
+        assert_eq!(source.event_count, 11);
+        assert!(source.last_imported_at.is_some());
+
+        let mut conn = open_db(&db_path).unwrap();
+        let failed = store_import(&mut conn, source.id, Err("offline".into()), Utc::now());
+        assert!(failed.is_err());
+        let source = get_source(&conn, source.id).unwrap();
+        assert_eq!(source.last_error.as_deref(), Some("offline"));
+        assert_eq!(source.event_count, 11, "failed import keeps events");
+
+        let source = store_import(
+            &mut conn,
+            source.id,
+            Ok(ParsedCalendar::default()),
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(source.event_count, 0);
+        assert_eq!(source.last_error, None);
+        // Same location reuses the source
+        assert_eq!(
+            upsert_source(&conn, "fixture.ics", None).unwrap(),
+            source.id
+        );
+        assert_eq!(
+            get_source(&conn, source.id).unwrap().name.as_deref(),
+            Some("Work")
+        );
+    }
+
+    #[test]
+    fn imports_from_file_and_url() {
+        let db_path = setup_test_db_file("fetch");
+        let fixture_path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/meetings.ics");
+        let source = tauri::async_runtime::block_on(import_source(
+            &db_path,
+            fixture_path.to_str().unwrap(),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(source.last_error, None);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/work.ics", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/calendar\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{FIXTURE}",
+                FIXTURE.len()
+            )
+            .unwrap();
+        });
+        let source =
+            tauri::async_runtime::block_on(import_source(&db_path, &url, Some("Remote"))).unwrap();
+        assert_eq!(source.location, url);
+        assert_eq!(source.last_error, None);
+
+        let missing =
+            tauri::async_runtime::block_on(import_source(&db_path, "/nonexistent/cal.ics", None));
+        assert!(missing.unwrap_err().contains("Failed to read calendar"));
+        assert_eq!(query_sources(&open_db(&db_path).unwrap()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn plans_focus_blocks_around_meetings() {
+        let db_path = setup_test_db_file("plan");
+        import_fixture(&db_path);
+        let settings = IntervalSettings::load(&db_path).unwrap();
+        let conn = open_db(&db_path).unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let plan = plan_day(&conn, day, &Berlin, &settings).unwrap();
+
+        assert_eq!(plan.workday_start, "2026-03-02T08:00:00Z");
+        assert_eq!(plan.workday_end, "2026-03-02T16:00:00Z");
+        let meetings: Vec<&str> = plan.meetings.iter().map(|m| m.summary.as_str()).collect();
+        assert_eq!(
+            meetings,
+            vec![
+                "Team standup",
+                "Lunch with Sam",
+                "Design review, checkout flow"
+            ]
+        );
+        let blocks: Vec<(&str, i64, u32)> = plan
+            .free_blocks
+            .iter()
+            .map(|b| (b.start_time.as_str(), b.minutes, b.pomodoros))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                ("2026-03-02T08:00:00Z", 30, 1),
+                ("2026-03-02T08:45:00Z", 135, 4),
+                ("2026-03-02T12:00:00Z", 60, 2),
+                // The 8th pomodoro of the day earns a long break
+                ("2026-03-02T14:00:00Z", 120, 3),
+            ]
+        );
+        assert_eq!(plan.free_minutes, 345);
+        assert_eq!(plan.pomodoros, 10);
+
+        // All-day events don't block the day
+        let offsite = NaiveDate::from_ymd_opt(2026, 3, 6).unwrap();
+        let plan = plan_day(&conn, offsite, &Berlin, &settings).unwrap();
+        assert_eq!(plan.meetings.len(), 1);
+    }
+
+    #[test]
+    fn working_hours_come_from_settings() {
+        let db_path = setup_test_db_file("hours");
+        let settings = IntervalSettings::load(&db_path).unwrap();
+        let conn = open_db(&db_path).unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        conn.execute(
+            "UPDATE user_settings SET value = '13:30' WHERE key = 'workday_start'",
+            [],
+        )
+        .unwrap();
+        let plan = plan_day(&conn, day, &Utc, &settings).unwrap();
+        assert_eq!(plan.free_blocks.len(), 1);
+        assert_eq!(plan.free_minutes, 210);
+
+        conn.execute(
+            "UPDATE user_settings SET value = '08:00' WHERE key = 'workday_end'",
+            [],
+        )
+        .unwrap();
+        assert!(plan_day(&conn, day, &Utc, &settings).is_err());
+    }
+
+    #[test]
+    fn detects_meeting_conflicts() {
+        let db_path = setup_test_db_file("conflict");
+        import_fixture(&db_path);
+        let conn = open_db(&db_path).unwrap();
+
+        let start = utc("2026-03-02T12:40:00Z");
+        let conflict = find_conflict(&conn, start, start + Duration::minutes(25))
+            .unwrap()
+            .unwrap();
+        assert_eq!(conflict.summary, "Design review, checkout flow");
+        assert_eq!(conflict.minutes_until, 20);
+
+        let start = utc("2026-03-02T14:00:00Z");
+        assert_eq!(
+            find_conflict(&conn, start, start + Duration::minutes(25)).unwrap(),
+            None
+        );
+    }
+}
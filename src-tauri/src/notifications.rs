@@ -0,0 +1,321 @@
+use rusqlite::Connection;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::{Action, ActionPerformed, ActionType, NotificationExt};
+
+use crate::timer::{self, break_after, AppState, IntervalType, TimerAction, TimerCompletePayload};
+
+/// Action type attached to completion notifications.
+const ACTION_TYPE_ID: &str = "interval-complete";
+const ACTION_START_NEXT: &str = "start_next";
+const ACTION_SNOOZE: &str = "snooze";
+/// Reported by the plugin when the notification body itself is clicked.
+const ACTION_TAP: &str = "tap";
+
+const DEFAULT_WORK_TEMPLATE: &str =
+    "Work done — {cycle_position}/{long_break_frequency} until long break";
+const DEFAULT_BREAK_TEMPLATE: &str = "{interval} over — time to focus";
+const DEFAULT_SNOOZE_MINUTES: u32 = 5;
+
+// ── Settings ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NotificationSettings {
+    enabled: bool,
+    work_template: String,
+    break_template: String,
+    snooze_minutes: u32,
+    long_break_frequency: u32,
+}
+
+impl NotificationSettings {
+    fn load(db_path: &Path) -> Result<Self, String> {
+        let conn =
+            Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+        let read = |key: &str| {
+            conn.query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                [key],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        };
+        let template = |key: &str, default: &str| {
+            read(key)
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| default.to_string())
+        };
+
+        Ok(Self {
+            enabled: read("notifications_enabled").as_deref() != Some("false"),
+            work_template: template("notification_work_template", DEFAULT_WORK_TEMPLATE),
+            break_template: template("notification_break_template", DEFAULT_BREAK_TEMPLATE),
+            snooze_minutes: read("notification_snooze_minutes")
+                .and_then(|v| v.parse::<u32>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_SNOOZE_MINUTES),
+            long_break_frequency: timer::IntervalSettings::load(db_path)?.long_break_frequency,
+        })
+    }
+}
+
+// ── Formatting ──────────────────────────────────────────────
+
+/// Replace `{name}` placeholders with their values. Unknown placeholders
+/// are left as-is so a typo shows up in the notification.
+fn render_template(template: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Position of the last completed pomodoro within the current long-break
+/// cycle, counting from 1; a completed cycle reports the full frequency.
+fn cycle_position(completed_work_count: u32, long_break_frequency: u32) -> u32 {
+    if long_break_frequency == 0 {
+        return completed_work_count;
+    }
+    match completed_work_count % long_break_frequency {
+        0 if completed_work_count > 0 => long_break_frequency,
+        position => position,
+    }
+}
+
+/// Title and body for a completion notification.
+fn notification_text(
+    settings: &NotificationSettings,
+    payload: &TimerCompletePayload,
+) -> (String, String) {
+    let next = match payload.interval_type {
+        IntervalType::Work => {
+            break_after(payload.completed_work_count, settings.long_break_frequency)
+        }
+        IntervalType::ShortBreak | IntervalType::LongBreak => IntervalType::Work,
+    };
+    let values = [
+        ("interval", payload.interval_type.label().to_string()),
+        ("completed", payload.completed_work_count.to_string()),
+        (
+            "cycle_position",
+            cycle_position(payload.completed_work_count, settings.long_break_frequency).to_string(),
+        ),
+        (
+            "long_break_frequency",
+            settings.long_break_frequency.to_string(),
+        ),
+        ("next", next.label().to_string()),
+    ];
+    let template = match payload.interval_type {
+        IntervalType::Work => &settings.work_template,
+        IntervalType::ShortBreak | IntervalType::LongBreak => &settings.break_template,
+    };
+
+    let title = format!("{} complete", payload.interval_type.label());
+    (title, render_template(template, &values))
+}
+
+// ── Delivery ────────────────────────────────────────────────
+
+/// The last completion shown, so a snooze can repeat it.
+#[derive(Default)]
+pub struct NotificationState(Mutex<Option<Shown>>);
+
+#[derive(Clone)]
+struct Shown {
+    payload: TimerCompletePayload,
+    /// Timer `seq` right after the completion; a snooze only fires if the
+    /// timer has not moved on since.
+    seq: u64,
+}
+
+fn show<R: Runtime>(app: &AppHandle<R>, settings: &NotificationSettings, shown: &Shown) {
+    let (title, body) = notification_text(settings, &shown.payload);
+    let result = app
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .action_type_id(ACTION_TYPE_ID)
+        .show();
+    if let Err(e) = result {
+        eprintln!("Failed to show notification: {e}");
+        return;
+    }
+    if let Some(state) = app.try_state::<NotificationState>() {
+        if let Ok(mut last) = state.0.lock() {
+            *last = Some(shown.clone());
+        }
+    }
+}
+
+/// Post a native notification for a completed interval, if enabled.
+pub(crate) fn notify_complete<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: &TimerCompletePayload,
+    seq: u64,
+) {
+    if app.try_state::<NotificationState>().is_none() {
+        return;
+    }
+    let settings = match NotificationSettings::load(&app.state::<AppState>().db_path) {
+        Ok(settings) if settings.enabled => settings,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("Failed to read notification settings: {e}");
+            return;
+        }
+    };
+    show(
+        app,
+        &settings,
+        &Shown {
+            payload: payload.clone(),
+            seq,
+        },
+    );
+}
+
+/// Show the last notification again after the snooze delay, unless the
+/// timer changed in the meantime.
+fn snooze<R: Runtime>(app: &AppHandle<R>) {
+    let Some(shown) = app
+        .try_state::<NotificationState>()
+        .and_then(|state| state.0.lock().ok().and_then(|last| last.clone()))
+    else {
+        return;
+    };
+    let settings = match NotificationSettings::load(&app.state::<AppState>().db_path) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Failed to read notification settings: {e}");
+            return;
+        }
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_mins(u64::from(settings.snooze_minutes))).await;
+        let current_seq = {
+            let state = app.state::<AppState>();
+            let timer = state.timer.lock().expect("timer lock poisoned");
+            timer.status().seq
+        };
+        if current_seq == shown.seq {
+            show(&app, &settings, &shown);
+        }
+    });
+}
+
+fn handle_action<R: Runtime>(app: &AppHandle<R>, performed: &ActionPerformed) {
+    match performed.action_id() {
+        ACTION_START_NEXT => {
+            if let Err(e) = timer::dispatch_action(app, TimerAction::StartNext) {
+                eprintln!("Notification action failed: {e}");
+            }
+        }
+        ACTION_SNOOZE => snooze(app),
+        ACTION_TAP => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Register the notification actions. Requires the notification plugin.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    app.manage(NotificationState::default());
+
+    let notification = app.notification();
+    let action_type = ActionType::builder(ACTION_TYPE_ID)
+        .actions(vec![
+            Action::builder(ACTION_START_NEXT, "Start next").build(),
+            Action::builder(ACTION_SNOOZE, "Snooze").build(),
+        ])
+        .build();
+    if let Err(e) = notification.register_action_types(vec![action_type]) {
+        eprintln!("Failed to register notification actions: {e}");
+    }
+
+    let handle = app.clone();
+    let _ = notification.on_action(move |performed| handle_action(&handle, performed));
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> NotificationSettings {
+        NotificationSettings {
+            enabled: true,
+            work_template: DEFAULT_WORK_TEMPLATE.into(),
+            break_template: DEFAULT_BREAK_TEMPLATE.into(),
+            snooze_minutes: DEFAULT_SNOOZE_MINUTES,
+            long_break_frequency: 4,
+        }
+    }
+
+    fn payload(interval_type: IntervalType, completed_work_count: u32) -> TimerCompletePayload {
+        TimerCompletePayload {
+            interval_id: 1,
+            interval_type,
+            completed_work_count,
+            overtime: false,
+        }
+    }
+
+    #[test]
+    fn defaults_are_seeded_and_loaded() {
+        let path = std::env::temp_dir().join(format!(
+            "pomo-notifications-{}-defaults.db",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+
+        assert_eq!(NotificationSettings::load(&path).unwrap(), settings());
+    }
+
+    #[test]
+    fn cycle_position_wraps_at_long_break() {
+        assert_eq!(cycle_position(0, 4), 0);
+        assert_eq!(cycle_position(3, 4), 3);
+        assert_eq!(cycle_position(4, 4), 4);
+        assert_eq!(cycle_position(5, 4), 1);
+        assert_eq!(cycle_position(7, 0), 7);
+    }
+
+    #[test]
+    fn work_text_shows_cycle_progress() {
+        let (title, body) = notification_text(&settings(), &payload(IntervalType::Work, 3));
+        assert_eq!(title, "Work complete");
+        assert_eq!(body, "Work done — 3/4 until long break");
+    }
+
+    #[test]
+    fn break_text_uses_break_template() {
+        let (title, body) = notification_text(&settings(), &payload(IntervalType::LongBreak, 4));
+        assert_eq!(title, "Long break complete");
+        assert_eq!(body, "Long break over — time to focus");
+    }
+
+    #[test]
+    fn custom_template_placeholders() {
+        let mut settings = settings();
+        settings.work_template = "#{completed} done, next: {next} {unknown}".into();
+
+        let (_, body) = notification_text(&settings, &payload(IntervalType::Work, 8));
+        assert_eq!(body, "#8 done, next: Long break {unknown}");
+        let (_, body) = notification_text(&settings, &payload(IntervalType::Work, 6));
+        assert_eq!(body, "#6 done, next: Short break {unknown}");
+    }
+}
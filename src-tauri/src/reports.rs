@@ -1,7 +1,10 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc, Weekday};
 use rusqlite::Connection;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::goals::{self, DailyTotals, GoalConfig, GoalProgress, GoalStreaks};
 use crate::timer::AppState;
 
 // ── Types ────────────────────────────────────────────────────
@@ -15,6 +18,12 @@ pub struct IntervalSummary {
     pub duration_seconds: i64,
     pub planned_duration_seconds: i64,
     pub status: String,
+    /// `timer` for recorded intervals, `manual` for hand-entered ones.
+    pub source: String,
+    pub pause_count: i64,
+    pub paused_seconds: i64,
+    /// Cancelled before the end, so `duration_seconds` is the time spent.
+    pub partial: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -25,6 +34,8 @@ pub struct TaskSummary {
     pub jira_key: Option<String>,
     pub tag: Option<String>,
     pub completed_in_pomodoro: Option<i64>,
+    /// Interruptions logged during intervals linked to this task.
+    pub interruption_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -40,8 +51,19 @@ pub struct DailySummary {
     pub total_focus_minutes: i64,
     pub tasks_completed: i64,
     pub tasks_total: i64,
+    /// Completed work intervals that were paused at least once.
+    pub interrupted_pomodoros: i64,
+    pub internal_interruptions: i64,
+    pub external_interruptions: i64,
+    /// Cancelled work intervals and the minutes spent in them.
+    pub cancelled_pomodoros: i64,
+    pub abandoned_minutes: i64,
+    /// Completed intervals, plus cancelled ones when asked for.
     pub intervals: Vec<IntervalSummary>,
     pub task_groups: Vec<TaskGroup>,
+    /// `None` on days off and days without a goal.
+    pub goal: Option<GoalProgress>,
+    pub streak: GoalStreaks,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -50,6 +72,11 @@ pub struct DailyStat {
     pub pomodoro_count: i64,
     pub focus_minutes: i64,
     pub tasks_completed: i64,
+    pub interruption_count: i64,
+    pub cancelled_count: i64,
+    pub abandoned_minutes: i64,
+    /// `None` on days off and days without a goal.
+    pub goal_met: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -60,6 +87,12 @@ pub struct WeeklySummary {
     pub total_pomodoros: i64,
     pub total_focus_minutes: i64,
     pub total_tasks_completed: i64,
+    pub total_interruptions: i64,
+    pub total_cancelled: i64,
+    pub total_abandoned_minutes: i64,
+    /// Days in the week that had a goal, and how many of them met it.
+    pub goal_days: i64,
+    pub goals_met: i64,
     pub task_groups: Vec<TaskGroup>,
 }
 
@@ -88,6 +121,22 @@ fn query_pomodoro_stats(
     .map_err(|e| format!("Failed to query pomodoro stats: {e}"))
 }
 
+/// Cancelled work intervals on `day_date` and the seconds spent in them.
+fn query_cancelled_stats(
+    conn: &Connection,
+    day_date: &str,
+) -> Result<(i64, i64), String> {
+    conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(duration_seconds), 0)
+         FROM timer_intervals
+         WHERE status = 'cancelled' AND interval_type = 'work'
+           AND date(start_time) = ?1",
+        [day_date],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .map_err(|e| format!("Failed to query cancelled stats: {e}"))
+}
+
 fn query_task_counts(
     conn: &Connection,
     day_date: &str,
@@ -116,19 +165,25 @@ fn query_task_counts(
 fn query_intervals(
     conn: &Connection,
     day_date: &str,
+    include_cancelled: bool,
 ) -> Result<Vec<IntervalSummary>, String> {
     let mut stmt = conn
         .prepare(
-            "SELECT id, interval_type, start_time, end_time,
-                    duration_seconds, planned_duration_seconds, status
-             FROM timer_intervals
-             WHERE date(start_time) = ?1 AND status = 'completed'
-             ORDER BY start_time ASC",
+            "SELECT ti.id, ti.interval_type, ti.start_time, ti.end_time,
+                    ti.duration_seconds, ti.planned_duration_seconds, ti.status, ti.source,
+                    (SELECT COUNT(*) FROM interval_pauses p WHERE p.interval_id = ti.id),
+                    (SELECT COALESCE(SUM(p.duration_seconds), 0)
+                     FROM interval_pauses p WHERE p.interval_id = ti.id),
+                    ti.status = 'cancelled'
+             FROM timer_intervals ti
+             WHERE date(ti.start_time) = ?1
+               AND (ti.status = 'completed' OR (?2 AND ti.status = 'cancelled'))
+             ORDER BY ti.start_time ASC",
         )
         .map_err(|e| format!("Failed to prepare intervals query: {e}"))?;
 
     let rows = stmt
-        .query_map([day_date], |row| {
+        .query_map(rusqlite::params![day_date, include_cancelled], |row| {
             Ok(IntervalSummary {
                 id: row.get(0)?,
                 interval_type: row.get(1)?,
@@ -137,6 +192,10 @@ fn query_intervals(
                 duration_seconds: row.get(4)?,
                 planned_duration_seconds: row.get(5)?,
                 status: row.get(6)?,
+                source: row.get(7)?,
+                pause_count: row.get(8)?,
+                paused_seconds: row.get(9)?,
+                partial: row.get(10)?,
             })
         })
         .map_err(|e| format!("Failed to query intervals: {e}"))?;
@@ -145,6 +204,32 @@ fn query_intervals(
         .map_err(|e| format!("Failed to collect intervals: {e}"))
 }
 
+/// Count interruptions logged on intervals started on `day_date`,
+/// returned as `(internal, external)`.
+fn query_interruption_counts(
+    conn: &Connection,
+    day_date: &str,
+) -> Result<(i64, i64), String> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(i.kind = 'internal'), 0), COALESCE(SUM(i.kind = 'external'), 0)
+         FROM interruptions i
+         JOIN timer_intervals ti ON ti.id = i.interval_id
+         WHERE date(ti.start_time) = ?1",
+        [day_date],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .map_err(|e| format!("Failed to query interruption counts: {e}"))
+}
+
+/// Count completed work intervals that were paused at least once.
+fn count_interrupted_pomodoros(intervals: &[IntervalSummary]) -> i64 {
+    let count = intervals
+        .iter()
+        .filter(|i| i.interval_type == "work" && !i.partial && i.pause_count > 0)
+        .count();
+    i64::try_from(count).unwrap_or(i64::MAX)
+}
+
 fn query_task_groups(
     conn: &Connection,
     start_date: &str,
@@ -152,7 +237,11 @@ fn query_task_groups(
 ) -> Result<Vec<TaskGroup>, String> {
     let mut stmt = conn
         .prepare(
-            "SELECT id, title, status, jira_key, tag, completed_in_pomodoro
+            "SELECT id, title, status, jira_key, tag, completed_in_pomodoro,
+                    (SELECT COUNT(*)
+                     FROM interruptions i
+                     JOIN task_interval_links til ON til.interval_id = i.interval_id
+                     WHERE til.task_id = tasks.id)
              FROM tasks
              WHERE day_date BETWEEN ?1 AND ?2
                AND parent_task_id IS NULL
@@ -169,6 +258,7 @@ fn query_task_groups(
                 jira_key: row.get(3)?,
                 tag: row.get(4)?,
                 completed_in_pomodoro: row.get(5)?,
+                interruption_count: row.get(6)?,
             })
         })
         .map_err(|e| format!("Failed to query tasks: {e}"))?
@@ -201,6 +291,11 @@ pub struct WeekStat {
     pub pomodoro_count: i64,
     pub focus_minutes: i64,
     pub tasks_completed: i64,
+    pub interruption_count: i64,
+    pub cancelled_count: i64,
+    pub abandoned_minutes: i64,
+    pub goal_days: i64,
+    pub goals_met: i64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -211,164 +306,537 @@ pub struct MonthlySummary {
     pub total_pomodoros: i64,
     pub total_focus_minutes: i64,
     pub total_tasks_completed: i64,
+    pub total_interruptions: i64,
+    pub total_cancelled: i64,
+    pub total_abandoned_minutes: i64,
+    pub goal_days: i64,
+    pub goals_met: i64,
 }
 
-// ── Range helpers ──────────────────────────────────────────
+// ── Range types ────────────────────────────────────────────
 
-fn query_range_pomodoro_stats(
-    conn: &Connection,
-    start: &str,
-    end: &str,
-) -> Result<(i64, i64), String> {
-    conn.query_row(
-        "SELECT COUNT(*), COALESCE(SUM(duration_seconds), 0)
-         FROM timer_intervals
-         WHERE status = 'completed' AND interval_type = 'work'
-           AND date(start_time) BETWEEN ?1 AND ?2",
-        [start, end],
-        |row| Ok((row.get(0)?, row.get(1)?)),
-    )
-    .map_err(|e| format!("Failed to query range pomodoro stats: {e}"))
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Day,
+    Week,
+    Month,
+    Quarter,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketStat {
+    /// The whole period; only days inside the requested range are counted.
+    pub start: String,
+    pub end: String,
+    pub pomodoro_count: i64,
+    pub focus_minutes: i64,
+    pub tasks_completed: i64,
+    pub interruption_count: i64,
+    pub cancelled_count: i64,
+    pub abandoned_minutes: i64,
+    pub goal_days: i64,
+    pub goals_met: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RangeSummary {
+    pub start: String,
+    pub end: String,
+    pub granularity: Granularity,
+    pub buckets: Vec<BucketStat>,
+    pub total_pomodoros: i64,
+    pub total_focus_minutes: i64,
+    pub total_tasks_completed: i64,
+    pub total_interruptions: i64,
+    pub total_cancelled: i64,
+    pub total_abandoned_minutes: i64,
+    pub goal_days: i64,
+    pub goals_met: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub pomodoro_count: i64,
+    pub focus_minutes: i64,
+    /// Intensity from 0 (nothing) to 4, relative to the busiest day.
+    pub level: u8,
+    /// Column and row in the grid: weeks since the one holding Jan 1,
+    /// and days since the start of the week.
+    pub week: i64,
+    pub weekday: i64,
+    pub goal_met: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YearlySummary {
+    pub year: i32,
+    pub heatmap: Vec<HeatmapDay>,
+    pub monthly_stats: Vec<BucketStat>,
+    pub total_pomodoros: i64,
+    pub total_focus_minutes: i64,
+    pub total_tasks_completed: i64,
+    pub total_interruptions: i64,
+    pub total_cancelled: i64,
+    pub total_abandoned_minutes: i64,
+    pub goal_days: i64,
+    pub goals_met: i64,
+    /// Days with at least one completed pomodoro.
+    pub active_days: i64,
+    pub max_pomodoros: i64,
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| format!("Invalid date '{value}': {e}"))
 }
 
-fn query_range_tasks_completed(
+fn day_string(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+// ── Buckets ────────────────────────────────────────────────
+
+/// First day of the period of `granularity` that contains `day`, with
+/// weeks starting on `week_start`.
+fn period_start(day: NaiveDate, granularity: Granularity, week_start: Weekday) -> NaiveDate {
+    match granularity {
+        Granularity::Day => day,
+        Granularity::Week => {
+            day - Duration::days(i64::from(day.weekday().days_since(week_start)))
+        }
+        Granularity::Month => day.with_day(1).unwrap_or(day),
+        Granularity::Quarter => {
+            let month = (day.month0() / 3) * 3 + 1;
+            NaiveDate::from_ymd_opt(day.year(), month, 1).unwrap_or(day)
+        }
+    }
+}
+
+fn next_period(start: NaiveDate, granularity: Granularity) -> NaiveDate {
+    match granularity {
+        Granularity::Day => start + Duration::days(1),
+        Granularity::Week => start + Duration::days(7),
+        Granularity::Month => start + Months::new(1),
+        Granularity::Quarter => start + Months::new(3),
+    }
+}
+
+/// Whole periods overlapping `[start, end]`, as `(first, last)` days.
+fn bucket_spans(
+    start: NaiveDate,
+    end: NaiveDate,
+    granularity: Granularity,
+    week_start: Weekday,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut spans = Vec::new();
+    let mut cursor = period_start(start, granularity, week_start);
+    while cursor <= end {
+        let next = next_period(cursor, granularity);
+        spans.push((cursor, next - Duration::days(1)));
+        cursor = next;
+    }
+    spans
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct DayTotals {
+    pomodoros: i64,
+    focus_seconds: i64,
+    tasks_completed: i64,
+    interruptions: i64,
+    cancelled: i64,
+    abandoned_seconds: i64,
+}
+
+/// Per-day totals for a range, loaded once and summed into buckets.
+struct Ledger {
+    start: NaiveDate,
+    end: NaiveDate,
+    days: BTreeMap<NaiveDate, DayTotals>,
+    goals: GoalConfig,
+    goal_totals: DailyTotals,
+}
+
+/// Rows of `(day, a, b)` from a query grouped by day over `[?1, ?2]`.
+fn query_per_day(
     conn: &Connection,
+    sql: &str,
     start: &str,
     end: &str,
-) -> Result<i64, String> {
-    conn.query_row(
-        "SELECT COUNT(*) FROM tasks
-         WHERE day_date BETWEEN ?1 AND ?2
-           AND status = 'completed' AND parent_task_id IS NULL",
-        [start, end],
-        |row| row.get(0),
-    )
-    .map_err(|e| format!("Failed to query range tasks completed: {e}"))
+) -> Result<Vec<(NaiveDate, i64, i64)>, String> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| format!("Failed to prepare per-day query: {e}"))?;
+    let rows = stmt
+        .query_map([start, end], |row| {
+            Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?))
+        })
+        .map_err(|e| format!("Failed to query per-day totals: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect per-day totals: {e}"))?;
+    rows.into_iter()
+        .map(|(day, a, b)| Ok((parse_date(&day)?, a, b)))
+        .collect()
 }
 
-// ── Tauri commands ──────────────────────────────────────────
+impl Ledger {
+    fn load(
+        conn: &Connection,
+        goals: GoalConfig,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Self, String> {
+        let (from, to) = (day_string(start), day_string(end));
+        let mut days: BTreeMap<NaiveDate, DayTotals> = BTreeMap::new();
+
+        let pomodoros = query_per_day(
+            conn,
+            "SELECT date(start_time), COUNT(*), COALESCE(SUM(duration_seconds), 0)
+             FROM timer_intervals
+             WHERE status = 'completed' AND interval_type = 'work'
+               AND date(start_time) BETWEEN ?1 AND ?2
+             GROUP BY date(start_time)",
+            &from,
+            &to,
+        )?;
+        for (day, count, seconds) in pomodoros {
+            let totals = days.entry(day).or_default();
+            totals.pomodoros = count;
+            totals.focus_seconds = seconds;
+        }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn get_daily_summary(
-    state: tauri::State<'_, AppState>,
-    day_date: String,
-) -> Result<DailySummary, String> {
-    let conn = open_db(&state.db_path)?;
+        let tasks = query_per_day(
+            conn,
+            "SELECT day_date, COUNT(*), 0
+             FROM tasks
+             WHERE day_date BETWEEN ?1 AND ?2
+               AND status = 'completed' AND parent_task_id IS NULL
+             GROUP BY day_date",
+            &from,
+            &to,
+        )?;
+        for (day, count, _) in tasks {
+            days.entry(day).or_default().tasks_completed = count;
+        }
 
-    let (pomodoro_count, total_focus_seconds) = query_pomodoro_stats(&conn, &day_date)?;
-    let (tasks_completed, tasks_total) = query_task_counts(&conn, &day_date)?;
-    let intervals = query_intervals(&conn, &day_date)?;
-    let task_groups = query_task_groups(&conn, &day_date, &day_date)?;
+        let interruptions = query_per_day(
+            conn,
+            "SELECT date(ti.start_time), COUNT(*), 0
+             FROM interruptions i
+             JOIN timer_intervals ti ON ti.id = i.interval_id
+             WHERE date(ti.start_time) BETWEEN ?1 AND ?2
+             GROUP BY date(ti.start_time)",
+            &from,
+            &to,
+        )?;
+        for (day, count, _) in interruptions {
+            days.entry(day).or_default().interruptions = count;
+        }
+
+        let cancelled = query_per_day(
+            conn,
+            "SELECT date(start_time), COUNT(*), COALESCE(SUM(duration_seconds), 0)
+             FROM timer_intervals
+             WHERE status = 'cancelled' AND interval_type = 'work'
+               AND date(start_time) BETWEEN ?1 AND ?2
+             GROUP BY date(start_time)",
+            &from,
+            &to,
+        )?;
+        for (day, count, seconds) in cancelled {
+            let totals = days.entry(day).or_default();
+            totals.cancelled = count;
+            totals.abandoned_seconds = seconds;
+        }
+
+        let goal_totals = days
+            .iter()
+            .filter(|(_, t)| t.pomodoros > 0)
+            .map(|(day, t)| (*day, (t.pomodoros, t.focus_seconds / 60)))
+            .collect();
+
+        Ok(Self {
+            start,
+            end,
+            days,
+            goals,
+            goal_totals,
+        })
+    }
+
+    /// Sum the days of `(first, last)` that fall inside the loaded range.
+    fn stat(&self, (first, last): (NaiveDate, NaiveDate)) -> BucketStat {
+        let (from, to) = (first.max(self.start), last.min(self.end));
+        let mut stat = BucketStat {
+            start: day_string(first),
+            end: day_string(last),
+            pomodoro_count: 0,
+            focus_minutes: 0,
+            tasks_completed: 0,
+            interruption_count: 0,
+            cancelled_count: 0,
+            abandoned_minutes: 0,
+            goal_days: 0,
+            goals_met: 0,
+        };
+        if from > to {
+            return stat;
+        }
+
+        let (mut focus_seconds, mut abandoned_seconds) = (0, 0);
+        for totals in self.days.range(from..=to).map(|(_, t)| t) {
+            stat.pomodoro_count += totals.pomodoros;
+            focus_seconds += totals.focus_seconds;
+            stat.tasks_completed += totals.tasks_completed;
+            stat.interruption_count += totals.interruptions;
+            stat.cancelled_count += totals.cancelled;
+            abandoned_seconds += totals.abandoned_seconds;
+        }
+        stat.focus_minutes = focus_seconds / 60;
+        stat.abandoned_minutes = abandoned_seconds / 60;
+        (stat.goal_days, stat.goals_met) = self.goals.attainment(from, to, &self.goal_totals);
+        stat
+    }
+
+    fn buckets(&self, granularity: Granularity) -> Vec<BucketStat> {
+        bucket_spans(self.start, self.end, granularity, self.goals.week_start())
+            .into_iter()
+            .map(|span| self.stat(span))
+            .collect()
+    }
+}
+
+/// Heatmap intensity, splitting `(0, max]` into four even steps.
+fn heat_level(count: i64, max: i64) -> u8 {
+    if count <= 0 || max <= 0 {
+        return 0;
+    }
+    u8::try_from((count * 4 + max - 1) / max).map_or(4, |level| level.clamp(1, 4))
+}
+
+// ── Summaries ───────────────────────────────────────────────
+
+/// Pomodoros, tasks and interruptions for one day. Cancelled intervals
+/// are always counted but only listed with `include_cancelled`.
+pub fn daily_summary(
+    conn: &Connection,
+    day_date: &str,
+    include_cancelled: bool,
+) -> Result<DailySummary, String> {
+    let (pomodoro_count, total_focus_seconds) = query_pomodoro_stats(conn, day_date)?;
+    let (cancelled_pomodoros, abandoned_seconds) = query_cancelled_stats(conn, day_date)?;
+    let (tasks_completed, tasks_total) = query_task_counts(conn, day_date)?;
+    let intervals = query_intervals(conn, day_date, include_cancelled)?;
+    let interrupted_pomodoros = count_interrupted_pomodoros(&intervals);
+    let (internal_interruptions, external_interruptions) =
+        query_interruption_counts(conn, day_date)?;
+    let task_groups = query_task_groups(conn, day_date, day_date)?;
+
+    let day = parse_date(day_date)?;
+    let goal_config = GoalConfig::load(conn)?;
+    let goal = goal_config
+        .target_for(day)
+        .map(|target| goals::progress(target, pomodoro_count, total_focus_seconds / 60));
+    let streak = goals::streaks(conn, &goal_config, day, Utc::now().date_naive())?;
 
     Ok(DailySummary {
-        date: day_date,
+        date: day_date.to_string(),
         pomodoro_count,
         total_focus_minutes: total_focus_seconds / 60,
         tasks_completed,
         tasks_total,
+        interrupted_pomodoros,
+        internal_interruptions,
+        external_interruptions,
+        cancelled_pomodoros,
+        abandoned_minutes: abandoned_seconds / 60,
         intervals,
         task_groups,
+        goal,
+        streak,
     })
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn get_weekly_summary(
-    state: tauri::State<'_, AppState>,
-    week_start: String,
-) -> Result<WeeklySummary, String> {
-    let conn = open_db(&state.db_path)?;
-
-    // Compute week_end (6 days after week_start)
-    let week_end = conn
-        .query_row(
-            "SELECT date(?1, '+6 days')",
-            [&week_start],
-            |row| row.get::<_, String>(0),
-        )
-        .map_err(|e| format!("Failed to compute week end: {e}"))?;
+/// Per-day stats for the week containing `day_date`, starting on the
+/// `week_start_day` setting.
+pub fn weekly_summary(conn: &Connection, day_date: &str) -> Result<WeeklySummary, String> {
+    let goal_config = GoalConfig::load(conn)?;
+    let start = goal_config.start_of_week(parse_date(day_date)?);
+    let end = start + Duration::days(6);
+    let ledger = Ledger::load(conn, goal_config, start, end)?;
+
+    let daily_stats: Vec<DailyStat> = ledger
+        .buckets(Granularity::Day)
+        .into_iter()
+        .map(|day| DailyStat {
+            goal_met: (day.goal_days > 0).then_some(day.goals_met > 0),
+            date: day.start,
+            pomodoro_count: day.pomodoro_count,
+            focus_minutes: day.focus_minutes,
+            tasks_completed: day.tasks_completed,
+            interruption_count: day.interruption_count,
+            cancelled_count: day.cancelled_count,
+            abandoned_minutes: day.abandoned_minutes,
+        })
+        .collect();
 
-    // Get per-day pomodoro stats
-    let mut pomo_stmt = conn
-        .prepare(
-            "SELECT date(start_time) as day,
-                    COUNT(*) as pomo_count,
-                    COALESCE(SUM(duration_seconds), 0) as focus_secs
-             FROM timer_intervals
-             WHERE status = 'completed' AND interval_type = 'work'
-               AND date(start_time) BETWEEN ?1 AND ?2
-             GROUP BY date(start_time)",
-        )
-        .map_err(|e| format!("Failed to prepare weekly pomo query: {e}"))?;
+    let week = ledger.stat((start, end));
+    Ok(WeeklySummary {
+        total_pomodoros: daily_stats.iter().map(|d| d.pomodoro_count).sum(),
+        total_focus_minutes: daily_stats.iter().map(|d| d.focus_minutes).sum(),
+        total_tasks_completed: daily_stats.iter().map(|d| d.tasks_completed).sum(),
+        total_interruptions: daily_stats.iter().map(|d| d.interruption_count).sum(),
+        total_cancelled: daily_stats.iter().map(|d| d.cancelled_count).sum(),
+        total_abandoned_minutes: daily_stats.iter().map(|d| d.abandoned_minutes).sum(),
+        goal_days: week.goal_days,
+        goals_met: week.goals_met,
+        daily_stats,
+        task_groups: query_task_groups(conn, &week.start, &week.end)?,
+        week_start: week.start,
+        week_end: week.end,
+    })
+}
 
-    let pomo_rows: Vec<(String, i64, i64)> = pomo_stmt
-        .query_map([&week_start, &week_end], |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+/// Per-week stats for the month starting at `month_start`, with weeks
+/// starting on the `week_start_day` setting and clamped to the month.
+pub fn monthly_summary(conn: &Connection, month_start: &str) -> Result<MonthlySummary, String> {
+    let start = parse_date(month_start)?;
+    let end = start + Months::new(1) - Duration::days(1);
+    let ledger = Ledger::load(conn, GoalConfig::load(conn)?, start, end)?;
+
+    let weekly_stats: Vec<WeekStat> = ledger
+        .buckets(Granularity::Week)
+        .into_iter()
+        .map(|week| WeekStat {
+            week_start: week.start,
+            week_end: week.end,
+            pomodoro_count: week.pomodoro_count,
+            focus_minutes: week.focus_minutes,
+            tasks_completed: week.tasks_completed,
+            interruption_count: week.interruption_count,
+            cancelled_count: week.cancelled_count,
+            abandoned_minutes: week.abandoned_minutes,
+            goal_days: week.goal_days,
+            goals_met: week.goals_met,
         })
-        .map_err(|e| format!("Failed to query weekly pomos: {e}"))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to collect weekly pomos: {e}"))?;
+        .collect();
 
-    // Get per-day completed tasks
-    let mut task_stmt = conn
-        .prepare(
-            "SELECT day_date, COUNT(*) as completed_count
-             FROM tasks
-             WHERE day_date BETWEEN ?1 AND ?2
-               AND status = 'completed'
-               AND parent_task_id IS NULL
-             GROUP BY day_date",
-        )
-        .map_err(|e| format!("Failed to prepare weekly tasks query: {e}"))?;
+    Ok(MonthlySummary {
+        month_start: month_start.to_string(),
+        month_end: day_string(end),
+        total_pomodoros: weekly_stats.iter().map(|w| w.pomodoro_count).sum(),
+        total_focus_minutes: weekly_stats.iter().map(|w| w.focus_minutes).sum(),
+        total_tasks_completed: weekly_stats.iter().map(|w| w.tasks_completed).sum(),
+        total_interruptions: weekly_stats.iter().map(|w| w.interruption_count).sum(),
+        total_cancelled: weekly_stats.iter().map(|w| w.cancelled_count).sum(),
+        total_abandoned_minutes: weekly_stats.iter().map(|w| w.abandoned_minutes).sum(),
+        goal_days: weekly_stats.iter().map(|w| w.goal_days).sum(),
+        goals_met: weekly_stats.iter().map(|w| w.goals_met).sum(),
+        weekly_stats,
+    })
+}
 
-    let task_rows: Vec<(String, i64)> = task_stmt
-        .query_map([&week_start, &week_end], |row| {
-            Ok((row.get(0)?, row.get(1)?))
-        })
-        .map_err(|e| format!("Failed to query weekly tasks: {e}"))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to collect weekly tasks: {e}"))?;
-
-    // Build daily stats for all 7 days
-    let mut daily_stats: Vec<DailyStat> = Vec::new();
-    let mut current = week_start.clone();
-    for _ in 0..7 {
-        let pomo = pomo_rows.iter().find(|(d, _, _)| *d == current);
-        let tasks = task_rows.iter().find(|(d, _)| *d == current);
-
-        daily_stats.push(DailyStat {
-            date: current.clone(),
-            pomodoro_count: pomo.map_or(0, |(_, c, _)| *c),
-            focus_minutes: pomo.map_or(0, |(_, _, s)| *s / 60),
-            tasks_completed: tasks.map_or(0, |(_, c)| *c),
-        });
-
-        // Advance to next day using SQLite date()
-        current = conn
-            .query_row(
-                "SELECT date(?1, '+1 day')",
-                [&current],
-                |row| row.get::<_, String>(0),
-            )
-            .map_err(|e| format!("Failed to advance date: {e}"))?;
+/// Stats for `[start, end]` split into day, week, month or quarter buckets.
+/// Buckets at either end report their whole period but only count days
+/// inside the range.
+pub fn range_summary(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+    granularity: Granularity,
+) -> Result<RangeSummary, String> {
+    let (first, last) = (parse_date(start)?, parse_date(end)?);
+    if last < first {
+        return Err(format!("Range end {end} is before its start {start}"));
     }
+    let ledger = Ledger::load(conn, GoalConfig::load(conn)?, first, last)?;
+    let buckets = ledger.buckets(granularity);
+
+    Ok(RangeSummary {
+        start: start.to_string(),
+        end: end.to_string(),
+        granularity,
+        total_pomodoros: buckets.iter().map(|b| b.pomodoro_count).sum(),
+        total_focus_minutes: buckets.iter().map(|b| b.focus_minutes).sum(),
+        total_tasks_completed: buckets.iter().map(|b| b.tasks_completed).sum(),
+        total_interruptions: buckets.iter().map(|b| b.interruption_count).sum(),
+        total_cancelled: buckets.iter().map(|b| b.cancelled_count).sum(),
+        total_abandoned_minutes: buckets.iter().map(|b| b.abandoned_minutes).sum(),
+        goal_days: buckets.iter().map(|b| b.goal_days).sum(),
+        goals_met: buckets.iter().map(|b| b.goals_met).sum(),
+        buckets,
+    })
+}
 
-    let total_pomodoros = daily_stats.iter().map(|d| d.pomodoro_count).sum();
-    let total_focus_minutes = daily_stats.iter().map(|d| d.focus_minutes).sum();
-    let total_tasks_completed = daily_stats.iter().map(|d| d.tasks_completed).sum();
+/// A day-by-day heatmap of `year` plus per-month stats.
+pub fn yearly_summary(conn: &Connection, year: i32) -> Result<YearlySummary, String> {
+    let start =
+        NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| format!("Invalid year {year}"))?;
+    let end = start + Months::new(12) - Duration::days(1);
+    let ledger = Ledger::load(conn, GoalConfig::load(conn)?, start, end)?;
+    let week_start = ledger.goals.week_start();
+
+    let max_pomodoros = ledger.days.values().map(|t| t.pomodoros).max().unwrap_or(0);
+    let grid_start = period_start(start, Granularity::Week, week_start);
+    let heatmap: Vec<HeatmapDay> = bucket_spans(start, end, Granularity::Day, week_start)
+        .into_iter()
+        .map(|(day, _)| {
+            let totals = ledger.days.get(&day).copied().unwrap_or_default();
+            HeatmapDay {
+                date: day_string(day),
+                pomodoro_count: totals.pomodoros,
+                focus_minutes: totals.focus_seconds / 60,
+                level: heat_level(totals.pomodoros, max_pomodoros),
+                week: (day - grid_start).num_days() / 7,
+                weekday: i64::from(day.weekday().days_since(week_start)),
+                goal_met: ledger.goals.met_on(day, &ledger.goal_totals),
+            }
+        })
+        .collect();
+
+    let active_days = heatmap.iter().filter(|d| d.pomodoro_count > 0).count();
+    let monthly_stats = ledger.buckets(Granularity::Month);
+    Ok(YearlySummary {
+        year,
+        total_pomodoros: monthly_stats.iter().map(|m| m.pomodoro_count).sum(),
+        total_focus_minutes: monthly_stats.iter().map(|m| m.focus_minutes).sum(),
+        total_tasks_completed: monthly_stats.iter().map(|m| m.tasks_completed).sum(),
+        total_interruptions: monthly_stats.iter().map(|m| m.interruption_count).sum(),
+        total_cancelled: monthly_stats.iter().map(|m| m.cancelled_count).sum(),
+        total_abandoned_minutes: monthly_stats.iter().map(|m| m.abandoned_minutes).sum(),
+        goal_days: monthly_stats.iter().map(|m| m.goal_days).sum(),
+        goals_met: monthly_stats.iter().map(|m| m.goals_met).sum(),
+        active_days: i64::try_from(active_days).unwrap_or(i64::MAX),
+        max_pomodoros,
+        heatmap,
+        monthly_stats,
+    })
+}
 
-    let task_groups = query_task_groups(&conn, &week_start, &week_end)?;
+// ── Tauri commands ──────────────────────────────────────────
 
-    Ok(WeeklySummary {
-        week_start,
-        week_end,
-        daily_stats,
-        total_pomodoros,
-        total_focus_minutes,
-        total_tasks_completed,
-        task_groups,
-    })
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_daily_summary(
+    state: tauri::State<'_, AppState>,
+    day_date: String,
+    include_cancelled: Option<bool>,
+) -> Result<DailySummary, String> {
+    daily_summary(
+        &open_db(&state.db_path)?,
+        &day_date,
+        include_cancelled.unwrap_or(false),
+    )
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_weekly_summary(
+    state: tauri::State<'_, AppState>,
+    week_start: String,
+) -> Result<WeeklySummary, String> {
+    weekly_summary(&open_db(&state.db_path)?, &week_start)
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -377,90 +845,27 @@ pub fn get_monthly_summary(
     state: tauri::State<'_, AppState>,
     month_start: String,
 ) -> Result<MonthlySummary, String> {
-    let conn = open_db(&state.db_path)?;
-
-    // Compute month_end (last day of the month)
-    let month_end: String = conn
-        .query_row(
-            "SELECT date(?1, '+1 month', '-1 day')",
-            [&month_start],
-            |row| row.get(0),
-        )
-        .map_err(|e| format!("Failed to compute month end: {e}"))?;
-
-    // Find the Monday on or before month_start
-    // SQLite: strftime('%w', date) returns 0=Sun,1=Mon,...,6=Sat
-    let first_monday: String = conn
-        .query_row(
-            "SELECT date(?1, '-' || ((strftime('%w', ?1) + 6) % 7) || ' days')",
-            [&month_start],
-            |row| row.get(0),
-        )
-        .map_err(|e| format!("Failed to compute first Monday: {e}"))?;
-
-    // Walk forward by 7 days, collecting weeks that overlap with the month
-    let mut weekly_stats: Vec<WeekStat> = Vec::new();
-    let mut week_cursor = first_monday;
-
-    loop {
-        let ws_end: String = conn
-            .query_row(
-                "SELECT date(?1, '+6 days')",
-                [&week_cursor],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Failed to compute week end: {e}"))?;
-
-        // If the week starts after the month ends, we're done
-        if week_cursor > month_end {
-            break;
-        }
-
-        // Clamp effective range to [month_start, month_end]
-        let eff_start = if week_cursor < month_start {
-            month_start.clone()
-        } else {
-            week_cursor.clone()
-        };
-        let eff_end = if ws_end > month_end {
-            month_end.clone()
-        } else {
-            ws_end.clone()
-        };
-
-        let (pomo_count, focus_secs) = query_range_pomodoro_stats(&conn, &eff_start, &eff_end)?;
-        let tasks_completed = query_range_tasks_completed(&conn, &eff_start, &eff_end)?;
-
-        weekly_stats.push(WeekStat {
-            week_start: week_cursor.clone(),
-            week_end: ws_end,
-            pomodoro_count: pomo_count,
-            focus_minutes: focus_secs / 60,
-            tasks_completed,
-        });
-
-        // Advance to next week
-        week_cursor = conn
-            .query_row(
-                "SELECT date(?1, '+7 days')",
-                [&week_cursor],
-                |row| row.get::<_, String>(0),
-            )
-            .map_err(|e| format!("Failed to advance week: {e}"))?;
-    }
+    monthly_summary(&open_db(&state.db_path)?, &month_start)
+}
 
-    let total_pomodoros = weekly_stats.iter().map(|w| w.pomodoro_count).sum();
-    let total_focus_minutes = weekly_stats.iter().map(|w| w.focus_minutes).sum();
-    let total_tasks_completed = weekly_stats.iter().map(|w| w.tasks_completed).sum();
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_range_summary(
+    state: tauri::State<'_, AppState>,
+    start: String,
+    end: String,
+    granularity: Granularity,
+) -> Result<RangeSummary, String> {
+    range_summary(&open_db(&state.db_path)?, &start, &end, granularity)
+}
 
-    Ok(MonthlySummary {
-        month_start,
-        month_end,
-        weekly_stats,
-        total_pomodoros,
-        total_focus_minutes,
-        total_tasks_completed,
-    })
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_yearly_summary(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<YearlySummary, String> {
+    yearly_summary(&open_db(&state.db_path)?, year)
 }
 
 // ── Tests ───────────────────────────────────────────────────
@@ -540,7 +945,7 @@ mod tests {
         let conn = setup_test_db();
         let (pomo_count, focus_secs) = query_pomodoro_stats(&conn, "2026-02-15").unwrap();
         let (completed, total) = query_task_counts(&conn, "2026-02-15").unwrap();
-        let intervals = query_intervals(&conn, "2026-02-15").unwrap();
+        let intervals = query_intervals(&conn, "2026-02-15", false).unwrap();
 
         assert_eq!(pomo_count, 0);
         assert_eq!(focus_secs, 0);
@@ -569,7 +974,7 @@ mod tests {
         insert_interval(&conn, "short_break", "2026-02-15T09:25:00Z", "2026-02-15T09:30:00Z", 300, "completed");
         insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:25:00Z", 1500, "completed");
 
-        let intervals = query_intervals(&conn, "2026-02-15").unwrap();
+        let intervals = query_intervals(&conn, "2026-02-15", false).unwrap();
         assert_eq!(intervals.len(), 3);
         assert_eq!(intervals[0].start_time, "2026-02-15T09:00:00Z");
         assert_eq!(intervals[1].start_time, "2026-02-15T09:25:00Z");
@@ -624,6 +1029,119 @@ mod tests {
         assert_eq!(total, 1);
     }
 
+    fn insert_pause(conn: &Connection, interval_id: i64, paused_at: &str, duration_seconds: i64) {
+        conn.execute(
+            "INSERT INTO interval_pauses (interval_id, paused_at, resumed_at, duration_seconds)
+             VALUES (?1, ?2, ?2, ?3)",
+            rusqlite::params![interval_id, paused_at, duration_seconds],
+        )
+        .expect("Failed to insert pause");
+    }
+
+    #[test]
+    fn daily_summary_intervals_include_pause_totals() {
+        let conn = setup_test_db();
+        let paused = insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:30:00Z", 1500, "completed");
+        insert_pause(&conn, paused, "2026-02-15T09:05:00Z", 120);
+        insert_pause(&conn, paused, "2026-02-15T09:15:00Z", 180);
+        insert_interval(&conn, "work", "2026-02-15T10:00:00Z", "2026-02-15T10:25:00Z", 1500, "completed");
+
+        let intervals = query_intervals(&conn, "2026-02-15", false).unwrap();
+        assert_eq!(intervals[0].pause_count, 2);
+        assert_eq!(intervals[0].paused_seconds, 300);
+        assert_eq!(intervals[1].pause_count, 0);
+        assert_eq!(intervals[1].paused_seconds, 0);
+    }
+
+    #[test]
+    fn daily_summary_intervals_report_source() {
+        let conn = setup_test_db();
+        insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:25:00Z", 1500, "completed");
+        conn.execute(
+            "INSERT INTO timer_intervals (interval_type, start_time, end_time, duration_seconds,
+             planned_duration_seconds, status, source)
+             VALUES ('work', '2026-02-15T10:00:00Z', '2026-02-15T10:25:00Z', 1500, 1500, 'completed', 'manual')",
+            [],
+        )
+        .unwrap();
+
+        let intervals = query_intervals(&conn, "2026-02-15", false).unwrap();
+        assert_eq!(intervals[0].source, "timer");
+        assert_eq!(intervals[1].source, "manual");
+    }
+
+    #[test]
+    fn daily_summary_flags_interrupted_pomodoros() {
+        let conn = setup_test_db();
+        let work = insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:30:00Z", 1500, "completed");
+        insert_pause(&conn, work, "2026-02-15T09:05:00Z", 60);
+        insert_interval(&conn, "work", "2026-02-15T10:00:00Z", "2026-02-15T10:25:00Z", 1500, "completed");
+        // Paused breaks are not interrupted pomodoros
+        let brk = insert_interval(&conn, "short_break", "2026-02-15T09:30:00Z", "2026-02-15T09:36:00Z", 300, "completed");
+        insert_pause(&conn, brk, "2026-02-15T09:32:00Z", 60);
+
+        let intervals = query_intervals(&conn, "2026-02-15", false).unwrap();
+        assert_eq!(count_interrupted_pomodoros(&intervals), 1);
+    }
+
+    fn insert_interruption(conn: &Connection, interval_id: i64, kind: &str) {
+        conn.execute(
+            "INSERT INTO interruptions (interval_id, kind, occurred_at)
+             VALUES (?1, ?2, '2026-02-15T09:05:00Z')",
+            rusqlite::params![interval_id, kind],
+        )
+        .expect("Failed to insert interruption");
+    }
+
+    #[test]
+    fn daily_summary_counts_interruptions_by_kind() {
+        let conn = setup_test_db();
+        let first = insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:25:00Z", 1500, "completed");
+        let voided = insert_interval(&conn, "work", "2026-02-15T10:00:00Z", "2026-02-15T10:08:00Z", 480, "cancelled");
+        let other_day = insert_interval(&conn, "work", "2026-02-14T09:00:00Z", "2026-02-14T09:25:00Z", 1500, "completed");
+        insert_interruption(&conn, first, "internal");
+        insert_interruption(&conn, first, "external");
+        insert_interruption(&conn, voided, "internal");
+        insert_interruption(&conn, other_day, "external");
+
+        let (internal, external) = query_interruption_counts(&conn, "2026-02-15").unwrap();
+        assert_eq!(internal, 2);
+        assert_eq!(external, 1);
+    }
+
+    #[test]
+    fn task_groups_include_interruptions_per_task() {
+        let conn = setup_test_db();
+        let task = insert_task(&conn, "Focus", "2026-02-15", "pending", None, 0);
+        let quiet = insert_task(&conn, "Quiet", "2026-02-15", "pending", None, 1);
+        let interval = insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:25:00Z", 1500, "completed");
+        conn.execute(
+            "INSERT INTO task_interval_links (task_id, interval_id) VALUES (?1, ?2)",
+            [task, interval],
+        )
+        .unwrap();
+        insert_interruption(&conn, interval, "internal");
+        insert_interruption(&conn, interval, "external");
+
+        let groups = query_task_groups(&conn, "2026-02-15", "2026-02-15").unwrap();
+        let tasks = &groups[0].tasks;
+        assert_eq!(tasks.iter().find(|t| t.id == task).unwrap().interruption_count, 2);
+        assert_eq!(tasks.iter().find(|t| t.id == quiet).unwrap().interruption_count, 0);
+    }
+
+    #[test]
+    fn range_interruption_count_respects_bounds() {
+        let conn = setup_test_db();
+        let jan = insert_interval(&conn, "work", "2026-01-31T09:00:00Z", "2026-01-31T09:25:00Z", 1500, "completed");
+        let feb = insert_interval(&conn, "work", "2026-02-10T09:00:00Z", "2026-02-10T09:25:00Z", 1500, "completed");
+        insert_interruption(&conn, jan, "internal");
+        insert_interruption(&conn, feb, "internal");
+        insert_interruption(&conn, feb, "external");
+
+        let range = range_summary(&conn, "2026-02-01", "2026-02-28", Granularity::Month).unwrap();
+        assert_eq!(range.total_interruptions, 2);
+    }
+
     // ── Weekly summary tests ─────────────────────────────────
 
     #[test]
@@ -644,6 +1162,10 @@ mod tests {
                 pomodoro_count: pomo_count,
                 focus_minutes: focus_secs / 60,
                 tasks_completed: completed,
+                interruption_count: 0,
+                cancelled_count: 0,
+                abandoned_minutes: 0,
+                goal_met: None,
             });
             current = conn
                 .query_row("SELECT date(?1, '+1 day')", [&current], |row| row.get::<_, String>(0))
@@ -735,12 +1257,11 @@ mod tests {
     #[test]
     fn monthly_summary_empty_month() {
         let conn = setup_test_db();
-        let (pomo_count, focus_secs) = query_range_pomodoro_stats(&conn, "2026-02-01", "2026-02-28").unwrap();
-        let tasks = query_range_tasks_completed(&conn, "2026-02-01", "2026-02-28").unwrap();
+        let month = monthly_summary(&conn, "2026-02-01").unwrap();
 
-        assert_eq!(pomo_count, 0);
-        assert_eq!(focus_secs, 0);
-        assert_eq!(tasks, 0);
+        assert_eq!(month.total_pomodoros, 0);
+        assert_eq!(month.total_focus_minutes, 0);
+        assert_eq!(month.total_tasks_completed, 0);
     }
 
     #[test]
@@ -754,12 +1275,13 @@ mod tests {
         insert_interval(&conn, "work", "2026-02-10T09:00:00Z", "2026-02-10T09:25:00Z", 1500, "completed");
         insert_task(&conn, "Week2 Task", "2026-02-10", "completed", None, 0);
 
-        let (pomo_count, focus_secs) = query_range_pomodoro_stats(&conn, "2026-02-01", "2026-02-28").unwrap();
-        let tasks = query_range_tasks_completed(&conn, "2026-02-01", "2026-02-28").unwrap();
+        let month = monthly_summary(&conn, "2026-02-01").unwrap();
 
-        assert_eq!(pomo_count, 3);
-        assert_eq!(focus_secs, 4500);
-        assert_eq!(tasks, 2);
+        assert_eq!(month.total_pomodoros, 3);
+        assert_eq!(month.total_focus_minutes, 75);
+        assert_eq!(month.total_tasks_completed, 2);
+        assert_eq!(month.weekly_stats[1].pomodoro_count, 2);
+        assert_eq!(month.weekly_stats[2].pomodoro_count, 1);
     }
 
     #[test]
@@ -775,46 +1297,357 @@ mod tests {
         insert_interval(&conn, "work", "2026-03-01T09:00:00Z", "2026-03-01T09:25:00Z", 1500, "completed");
         insert_task(&conn, "Mar Task", "2026-03-01", "completed", None, 0);
 
-        let (pomo_count, _) = query_range_pomodoro_stats(&conn, "2026-02-01", "2026-02-28").unwrap();
-        let tasks = query_range_tasks_completed(&conn, "2026-02-01", "2026-02-28").unwrap();
+        let month = monthly_summary(&conn, "2026-02-01").unwrap();
 
-        assert_eq!(pomo_count, 1);
-        assert_eq!(tasks, 1);
+        assert_eq!(month.total_pomodoros, 1);
+        assert_eq!(month.total_tasks_completed, 1);
+        // The first week starts in January but only counts Feb 1
+        assert_eq!(month.weekly_stats[0].week_start, "2026-01-26");
+        assert_eq!(month.weekly_stats[0].pomodoro_count, 1);
     }
 
     #[test]
     fn monthly_summary_correct_week_count() {
         let conn = setup_test_db();
 
-        // February 2026: starts on Sunday Feb 1
-        // Monday on or before Feb 1 = Jan 26
-        // Weeks: Jan 26, Feb 2, Feb 9, Feb 16, Feb 23 = 5 weeks
-        let month_end: String = conn
-            .query_row("SELECT date('2026-02-01', '+1 month', '-1 day')", [], |row| row.get(0))
-            .unwrap();
-        assert_eq!(month_end, "2026-02-28");
+        // February 2026 starts on Sunday Feb 1, so the weeks begin on
+        // Jan 26, Feb 2, Feb 9, Feb 16 and Feb 23
+        let month = monthly_summary(&conn, "2026-02-01").unwrap();
+        assert_eq!(month.month_end, "2026-02-28");
+        assert_eq!(month.weekly_stats.len(), 5);
+        assert_eq!(month.weekly_stats[0].week_start, "2026-01-26");
+        assert_eq!(month.weekly_stats[4].week_end, "2026-03-01");
+    }
 
-        let first_monday: String = conn
-            .query_row(
-                "SELECT date('2026-02-01', '-' || ((strftime('%w', '2026-02-01') + 6) % 7) || ' days')",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap();
-        assert_eq!(first_monday, "2026-01-26");
-
-        // Count weeks that overlap with the month
-        let mut count = 0;
-        let mut cursor = first_monday;
-        loop {
-            if cursor > month_end {
-                break;
-            }
-            count += 1;
-            cursor = conn
-                .query_row("SELECT date(?1, '+7 days')", [&cursor], |row| row.get::<_, String>(0))
-                .unwrap();
+    // ── Range summary tests ──────────────────────────────
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    #[test]
+    fn bucket_spans_cover_whole_periods() {
+        let spans = bucket_spans(date("2025-12-30"), date("2026-01-02"), Granularity::Day, Weekday::Mon);
+        assert_eq!(spans.len(), 4);
+        assert_eq!(spans[2], (date("2026-01-01"), date("2026-01-01")));
+
+        let spans = bucket_spans(date("2025-12-30"), date("2026-01-06"), Granularity::Week, Weekday::Mon);
+        assert_eq!(
+            spans,
+            vec![
+                (date("2025-12-29"), date("2026-01-04")),
+                (date("2026-01-05"), date("2026-01-11")),
+            ]
+        );
+
+        let spans = bucket_spans(date("2024-01-31"), date("2024-03-01"), Granularity::Month, Weekday::Mon);
+        assert_eq!(spans[1], (date("2024-02-01"), date("2024-02-29")));
+        assert_eq!(spans.len(), 3);
+
+        let spans = bucket_spans(date("2025-11-15"), date("2026-02-01"), Granularity::Quarter, Weekday::Mon);
+        assert_eq!(
+            spans,
+            vec![
+                (date("2025-10-01"), date("2025-12-31")),
+                (date("2026-01-01"), date("2026-03-31")),
+            ]
+        );
+    }
+
+    #[test]
+    fn range_summary_clamps_edge_buckets() {
+        let conn = setup_test_db();
+        set_goal(&conn, "1");
+        insert_interval(&conn, "work", "2026-01-05T09:00:00Z", "2026-01-05T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-10T09:00:00Z", "2026-02-10T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-11T09:00:00Z", "2026-02-11T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-03-20T09:00:00Z", "2026-03-20T09:25:00Z", 1500, "completed");
+        insert_task(&conn, "Feb Task", "2026-02-11", "completed", None, 0);
+
+        let range = range_summary(&conn, "2026-02-10", "2026-03-05", Granularity::Month).unwrap();
+        assert_eq!(range.buckets.len(), 2);
+        assert_eq!(range.buckets[0].start, "2026-02-01");
+        assert_eq!(range.buckets[0].pomodoro_count, 2);
+        assert_eq!(range.buckets[0].tasks_completed, 1);
+        assert_eq!(range.buckets[1].end, "2026-03-31");
+        assert_eq!(range.buckets[1].pomodoro_count, 0);
+        assert_eq!(range.total_pomodoros, 2);
+        assert_eq!(range.total_focus_minutes, 50);
+        // Feb 10 to Mar 5 holds 18 weekdays
+        assert_eq!(range.goal_days, 18);
+        assert_eq!(range.goals_met, 2);
+
+        let quarters = range_summary(&conn, "2026-01-01", "2026-06-30", Granularity::Quarter).unwrap();
+        assert_eq!(quarters.buckets.len(), 2);
+        assert_eq!(quarters.buckets[0].pomodoro_count, 4);
+        assert_eq!(quarters.buckets[1].pomodoro_count, 0);
+
+        let days = range_summary(&conn, "2026-02-10", "2026-02-11", Granularity::Day).unwrap();
+        assert_eq!(days.buckets.len(), 2);
+        assert_eq!(days.buckets[1].goals_met, 1);
+    }
+
+    #[test]
+    fn range_summary_rejects_bad_ranges() {
+        let conn = setup_test_db();
+        assert!(range_summary(&conn, "2026-02-10", "2026-02-01", Granularity::Day).is_err());
+        assert!(range_summary(&conn, "2026-02-10", "soon", Granularity::Day).is_err());
+    }
+
+    #[test]
+    fn heat_levels_scale_to_the_busiest_day() {
+        assert_eq!(heat_level(0, 8), 0);
+        assert_eq!(heat_level(1, 8), 1);
+        assert_eq!(heat_level(2, 8), 1);
+        assert_eq!(heat_level(3, 8), 2);
+        assert_eq!(heat_level(6, 8), 3);
+        assert_eq!(heat_level(8, 8), 4);
+        assert_eq!(heat_level(1, 1), 4);
+    }
+
+    #[test]
+    fn yearly_summary_builds_heatmap_grid() {
+        let conn = setup_test_db();
+        for hour in 9..13 {
+            let start = format!("2026-03-02T{hour:02}:00:00Z");
+            let end = format!("2026-03-02T{hour:02}:25:00Z");
+            insert_interval(&conn, "work", &start, &end, 1500, "completed");
+        }
+        insert_interval(&conn, "work", "2026-12-31T09:00:00Z", "2026-12-31T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2027-01-01T09:00:00Z", "2027-01-01T09:25:00Z", 1500, "completed");
+
+        let year = yearly_summary(&conn, 2026).unwrap();
+        assert_eq!(year.heatmap.len(), 365);
+        assert_eq!(year.monthly_stats.len(), 12);
+        assert_eq!(year.total_pomodoros, 5);
+        assert_eq!(year.monthly_stats[2].pomodoro_count, 4);
+        assert_eq!(year.active_days, 2);
+        assert_eq!(year.max_pomodoros, 4);
+
+        // Jan 1 2026 is a Thursday, in the week starting Monday Dec 29
+        assert_eq!(year.heatmap[0].week, 0);
+        assert_eq!(year.heatmap[0].weekday, 3);
+        let march = year.heatmap.iter().find(|d| d.date == "2026-03-02").unwrap();
+        assert_eq!(march.level, 4);
+        assert_eq!(march.focus_minutes, 100);
+        assert_eq!((march.week, march.weekday), (9, 0));
+        let last = year.heatmap.last().unwrap();
+        assert_eq!(last.level, 1);
+        assert_eq!((last.week, last.weekday), (52, 3));
+    }
+
+    // ── Week start tests ─────────────────────────────────
+
+    fn set_week_start(conn: &Connection, iso_weekday: u32) {
+        conn.execute(
+            "UPDATE user_settings SET value = ?1 WHERE key = 'week_start_day'",
+            [iso_weekday.to_string()],
+        )
+        .unwrap();
+    }
+
+    /// For each ISO start day: the week holding Thu Jan 1 2026, and the
+    /// week holding Sat Jan 31 2026.
+    const JANUARY_WEEKS: [(u32, &str, &str, &str, &str); 7] = [
+        (1, "2025-12-29", "2026-01-04", "2026-01-26", "2026-02-01"),
+        (2, "2025-12-30", "2026-01-05", "2026-01-27", "2026-02-02"),
+        (3, "2025-12-31", "2026-01-06", "2026-01-28", "2026-02-03"),
+        (4, "2026-01-01", "2026-01-07", "2026-01-29", "2026-02-04"),
+        (5, "2025-12-26", "2026-01-01", "2026-01-30", "2026-02-05"),
+        (6, "2025-12-27", "2026-01-02", "2026-01-31", "2026-02-06"),
+        (7, "2025-12-28", "2026-01-03", "2026-01-25", "2026-01-31"),
+    ];
+
+    fn insert_year_end_work(conn: &Connection) {
+        for day in ["2025-12-31", "2026-01-01", "2026-01-31", "2026-02-01"] {
+            let start = format!("{day}T09:00:00Z");
+            let end = format!("{day}T09:25:00Z");
+            insert_interval(conn, "work", &start, &end, 1500, "completed");
         }
-        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn weekly_summary_starts_on_the_configured_day() {
+        let conn = setup_test_db();
+        insert_year_end_work(&conn);
+        for (iso, first_start, first_end, _, _) in JANUARY_WEEKS {
+            set_week_start(&conn, iso);
+            let week = weekly_summary(&conn, "2026-01-01").unwrap();
+            assert_eq!(week.week_start, first_start, "start day {iso}");
+            assert_eq!(week.week_end, first_end, "start day {iso}");
+            assert_eq!(week.daily_stats.len(), 7);
+            assert_eq!(week.daily_stats[0].date, first_start);
+            // Dec 31 is in the week unless it starts on Thursday
+            let expected = if iso == 4 { 1 } else { 2 };
+            assert_eq!(week.total_pomodoros, expected, "start day {iso}");
+        }
+    }
+
+    #[test]
+    fn monthly_summary_splits_weeks_on_the_configured_day() {
+        let conn = setup_test_db();
+        insert_year_end_work(&conn);
+        for (iso, first_start, first_end, last_start, last_end) in JANUARY_WEEKS {
+            set_week_start(&conn, iso);
+            let month = monthly_summary(&conn, "2026-01-01").unwrap();
+            let (first, last) = (&month.weekly_stats[0], month.weekly_stats.last().unwrap());
+            assert_eq!((first.week_start.as_str(), first.week_end.as_str()), (first_start, first_end));
+            assert_eq!((last.week_start.as_str(), last.week_end.as_str()), (last_start, last_end));
+            // Only January's days count, even in weeks spilling into
+            // December or February
+            assert_eq!(first.pomodoro_count, 1, "start day {iso}");
+            assert_eq!(last.pomodoro_count, 1, "start day {iso}");
+            assert_eq!(month.total_pomodoros, 2, "start day {iso}");
+            // Friday and Saturday starts need a sixth week
+            let weeks = if iso == 5 || iso == 6 { 6 } else { 5 };
+            assert_eq!(month.weekly_stats.len(), weeks, "start day {iso}");
+        }
+    }
+
+    #[test]
+    fn range_and_yearly_weeks_follow_the_configured_day() {
+        let conn = setup_test_db();
+        insert_year_end_work(&conn);
+        for (iso, first_start, _, _, _) in JANUARY_WEEKS {
+            set_week_start(&conn, iso);
+            let range =
+                range_summary(&conn, "2025-12-31", "2026-01-01", Granularity::Week).unwrap();
+            // Dec 31 and Jan 1 share a week unless it starts on Thursday
+            let buckets = if iso == 4 { 2 } else { 1 };
+            assert_eq!(range.buckets.len(), buckets, "start day {iso}");
+            assert_eq!(range.total_pomodoros, 2);
+
+            let year = yearly_summary(&conn, 2026).unwrap();
+            let jan_first = &year.heatmap[0];
+            assert_eq!(jan_first.week, 0);
+            let days_in = (date("2026-01-01") - date(first_start)).num_days();
+            assert_eq!(jan_first.weekday, days_in, "start day {iso}");
+            let jan_31 = &year.heatmap[30];
+            assert_eq!(jan_31.week, (30 + days_in) / 7, "start day {iso}");
+            assert_eq!(jan_31.weekday, (30 + days_in) % 7, "start day {iso}");
+        }
+    }
+
+    // ── Cancelled interval tests ─────────────────────────
+
+    #[test]
+    fn daily_summary_lists_cancelled_intervals_on_request() {
+        let conn = setup_test_db();
+        insert_interval(&conn, "work", "2026-02-15T09:00:00Z", "2026-02-15T09:25:00Z", 1500, "completed");
+        let cancelled = insert_interval(&conn, "work", "2026-02-15T10:00:00Z", "2026-02-15T10:10:00Z", 600, "cancelled");
+        insert_pause(&conn, cancelled, "2026-02-15T10:05:00Z", 60);
+        insert_interval(&conn, "short_break", "2026-02-15T10:10:00Z", "2026-02-15T10:12:00Z", 120, "cancelled");
+
+        let summary = daily_summary(&conn, "2026-02-15", false).unwrap();
+        assert_eq!(summary.intervals.len(), 1);
+        assert!(!summary.intervals[0].partial);
+        assert_eq!(summary.cancelled_pomodoros, 1);
+        assert_eq!(summary.abandoned_minutes, 10);
+        assert_eq!(summary.pomodoro_count, 1);
+
+        let summary = daily_summary(&conn, "2026-02-15", true).unwrap();
+        assert_eq!(summary.intervals.len(), 3);
+        let partial = &summary.intervals[1];
+        assert!(partial.partial);
+        assert_eq!(partial.status, "cancelled");
+        assert_eq!(partial.duration_seconds, 600);
+        // A paused-then-cancelled pomodoro is not an interrupted pomodoro
+        assert_eq!(summary.interrupted_pomodoros, 0);
+        assert_eq!(summary.pomodoro_count, 1);
+    }
+
+    #[test]
+    fn weekly_and_monthly_summaries_count_abandoned_minutes() {
+        let conn = setup_test_db();
+        insert_interval(&conn, "work", "2026-02-16T09:00:00Z", "2026-02-16T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-16T10:00:00Z", "2026-02-16T10:12:00Z", 720, "cancelled");
+        insert_interval(&conn, "work", "2026-02-18T10:00:00Z", "2026-02-18T10:03:30Z", 210, "cancelled");
+        insert_interval(&conn, "work", "2026-02-28T10:00:00Z", "2026-02-28T10:20:00Z", 1200, "cancelled");
+        insert_interval(&conn, "long_break", "2026-02-18T11:00:00Z", "2026-02-18T11:05:00Z", 300, "cancelled");
+
+        let week = weekly_summary(&conn, "2026-02-16").unwrap();
+        assert_eq!(week.daily_stats[0].cancelled_count, 1);
+        assert_eq!(week.daily_stats[0].abandoned_minutes, 12);
+        assert_eq!(week.daily_stats[2].abandoned_minutes, 3);
+        assert_eq!(week.total_cancelled, 2);
+        assert_eq!(week.total_abandoned_minutes, 15);
+        assert_eq!(week.total_pomodoros, 1);
+
+        let month = monthly_summary(&conn, "2026-02-01").unwrap();
+        assert_eq!(month.weekly_stats[3].cancelled_count, 2);
+        assert_eq!(month.weekly_stats[3].abandoned_minutes, 15);
+        assert_eq!(month.weekly_stats[4].abandoned_minutes, 20);
+        assert_eq!(month.total_cancelled, 3);
+        assert_eq!(month.total_abandoned_minutes, 35);
+
+        let range = range_summary(&conn, "2026-02-01", "2026-02-28", Granularity::Month).unwrap();
+        assert_eq!(range.total_cancelled, 3);
+        assert_eq!(range.buckets[0].abandoned_minutes, 35);
+    }
+
+    // ── Goal tests ───────────────────────────────────────
+
+    fn set_goal(conn: &Connection, pomodoros: &str) {
+        conn.execute(
+            "UPDATE user_settings SET value = ?1 WHERE key = 'daily_goal_pomodoros'",
+            [pomodoros],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn daily_summary_reports_goal_progress() {
+        let conn = setup_test_db();
+        set_goal(&conn, "2");
+        insert_interval(&conn, "work", "2026-02-16T09:00:00Z", "2026-02-16T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-16T10:00:00Z", "2026-02-16T10:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-17T09:00:00Z", "2026-02-17T09:25:00Z", 1500, "completed");
+
+        let monday = daily_summary(&conn, "2026-02-16", false).unwrap();
+        let goal = monday.goal.unwrap();
+        assert!(goal.met);
+        assert_eq!(goal.pomodoro_target, Some(2));
+        assert_eq!(monday.streak.current, 1);
+
+        let tuesday = daily_summary(&conn, "2026-02-17", false).unwrap();
+        assert_eq!(tuesday.goal.unwrap().percent, 50);
+        assert_eq!(tuesday.streak.current, 0);
+        assert_eq!(tuesday.streak.longest, 1);
+
+        // Sunday is not a working day
+        assert!(daily_summary(&conn, "2026-02-15", false).unwrap().goal.is_none());
+        assert!(daily_summary(&conn, "not-a-date", false).is_err());
+    }
+
+    #[test]
+    fn weekly_summary_counts_goal_attainment() {
+        let conn = setup_test_db();
+        set_goal(&conn, "1");
+        insert_interval(&conn, "work", "2026-02-16T09:00:00Z", "2026-02-16T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-18T09:00:00Z", "2026-02-18T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-21T09:00:00Z", "2026-02-21T09:25:00Z", 1500, "completed");
+
+        let week = weekly_summary(&conn, "2026-02-16").unwrap();
+        assert_eq!(week.goal_days, 5);
+        assert_eq!(week.goals_met, 2);
+        assert_eq!(week.daily_stats[0].goal_met, Some(true));
+        assert_eq!(week.daily_stats[1].goal_met, Some(false));
+        assert_eq!(week.daily_stats[5].goal_met, None);
+    }
+
+    #[test]
+    fn monthly_summary_counts_goal_attainment_per_week() {
+        let conn = setup_test_db();
+        set_goal(&conn, "1");
+        insert_interval(&conn, "work", "2026-02-02T09:00:00Z", "2026-02-02T09:25:00Z", 1500, "completed");
+        insert_interval(&conn, "work", "2026-02-27T09:00:00Z", "2026-02-27T09:25:00Z", 1500, "completed");
+
+        let month = monthly_summary(&conn, "2026-02-01").unwrap();
+        // The week of Jan 26 only contributes Sunday Feb 1, a day off
+        assert_eq!(month.weekly_stats[0].goal_days, 0);
+        assert_eq!(month.weekly_stats[1].goals_met, 1);
+        assert_eq!(month.weekly_stats[4].goals_met, 1);
+        assert_eq!(month.goal_days, 20);
+        assert_eq!(month.goals_met, 2);
     }
 }
@@ -0,0 +1,306 @@
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+
+use crate::timer::{self, AppState, TimerAction};
+
+/// How much time the "add 5 minutes" shortcut adds.
+const EXTEND_SECONDS: u32 = 300;
+
+// ── Types ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    Toggle,
+    Cancel,
+    Skip,
+    Extend,
+}
+
+impl ShortcutAction {
+    const ALL: [Self; 4] = [Self::Toggle, Self::Cancel, Self::Skip, Self::Extend];
+
+    fn setting_key(self) -> &'static str {
+        match self {
+            Self::Toggle => "shortcut_toggle",
+            Self::Cancel => "shortcut_cancel",
+            Self::Skip => "shortcut_skip",
+            Self::Extend => "shortcut_extend",
+        }
+    }
+
+    fn timer_action(self) -> TimerAction {
+        match self {
+            Self::Toggle => TimerAction::Toggle,
+            Self::Cancel => TimerAction::Cancel,
+            Self::Skip => TimerAction::Skip,
+            Self::Extend => TimerAction::Extend(EXTEND_SECONDS),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShortcutBinding {
+    pub action: ShortcutAction,
+    pub accelerator: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutProblemKind {
+    /// The accelerator string could not be parsed.
+    InvalidAccelerator,
+    /// A global shortcut needs at least one modifier key.
+    MissingModifier,
+    /// Another action is bound to the same key combination.
+    Conflict,
+    /// The OS refused the registration, usually because another app owns it.
+    RegistrationFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutProblem {
+    pub action: ShortcutAction,
+    pub accelerator: String,
+    pub kind: ShortcutProblemKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ShortcutReport {
+    pub enabled: bool,
+    pub registered: Vec<ShortcutBinding>,
+    pub problems: Vec<ShortcutProblem>,
+}
+
+/// The outcome of the last registration, for the settings UI.
+pub struct ShortcutRegistry(Mutex<ShortcutReport>);
+
+// ── Settings ────────────────────────────────────────────────
+
+/// Read whether shortcuts are enabled and the non-empty bindings.
+fn read_bindings(db_path: &Path) -> Result<(bool, Vec<ShortcutBinding>), String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    let read = |key: &str| {
+        conn.query_row(
+            "SELECT value FROM user_settings WHERE key = ?1",
+            [key],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+    };
+
+    let enabled = read("global_shortcuts_enabled").as_deref() == Some("true");
+    let bindings = ShortcutAction::ALL
+        .iter()
+        .filter_map(|&action| {
+            let accelerator = read(action.setting_key())?.trim().to_string();
+            (!accelerator.is_empty()).then_some(ShortcutBinding {
+                action,
+                accelerator,
+            })
+        })
+        .collect();
+    Ok((enabled, bindings))
+}
+
+// ── Validation ──────────────────────────────────────────────
+
+/// Parse and cross-check bindings. Returns the usable shortcuts and a
+/// problem for each binding that cannot be registered.
+fn validate(
+    bindings: Vec<ShortcutBinding>,
+) -> (Vec<(ShortcutBinding, Shortcut)>, Vec<ShortcutProblem>) {
+    let mut valid: Vec<(ShortcutBinding, Shortcut)> = Vec::new();
+    let mut problems = Vec::new();
+
+    for binding in bindings {
+        let problem = |kind, message: String| ShortcutProblem {
+            action: binding.action,
+            accelerator: binding.accelerator.clone(),
+            kind,
+            message,
+        };
+
+        let shortcut = match binding.accelerator.parse::<Shortcut>() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                problems.push(problem(
+                    ShortcutProblemKind::InvalidAccelerator,
+                    e.to_string(),
+                ));
+                continue;
+            }
+        };
+        if shortcut.mods == Modifiers::empty() {
+            problems.push(problem(
+                ShortcutProblemKind::MissingModifier,
+                "Global shortcuts need at least one modifier key".into(),
+            ));
+            continue;
+        }
+        if let Some((other, _)) = valid.iter().find(|(_, s)| s.id() == shortcut.id()) {
+            problems.push(problem(
+                ShortcutProblemKind::Conflict,
+                format!("Already bound to {:?}", other.action),
+            ));
+            continue;
+        }
+        valid.push((binding, shortcut));
+    }
+
+    (valid, problems)
+}
+
+// ── Registration ────────────────────────────────────────────
+
+/// Re-read the bindings from settings and (re)register them with the OS.
+pub fn register_shortcuts<R: Runtime>(app: &AppHandle<R>) -> ShortcutReport {
+    let manager = app.global_shortcut();
+    let _ = manager.unregister_all();
+
+    let mut report = ShortcutReport::default();
+    match read_bindings(&app.state::<AppState>().db_path) {
+        Ok((enabled, bindings)) if enabled => {
+            report.enabled = true;
+            let (valid, problems) = validate(bindings);
+            report.problems = problems;
+
+            for (binding, shortcut) in valid {
+                let action = binding.action.timer_action();
+                let result = manager.on_shortcut(shortcut, move |app, _, event| {
+                    if event.state == ShortcutState::Pressed {
+                        if let Err(e) = timer::dispatch_action(app, action) {
+                            eprintln!("Shortcut action failed: {e}");
+                        }
+                    }
+                });
+                match result {
+                    Ok(()) => report.registered.push(binding),
+                    Err(e) => report.problems.push(ShortcutProblem {
+                        action: binding.action,
+                        accelerator: binding.accelerator,
+                        kind: ShortcutProblemKind::RegistrationFailed,
+                        message: e.to_string(),
+                    }),
+                }
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to read shortcut settings: {e}"),
+    }
+
+    if let Some(registry) = app.try_state::<ShortcutRegistry>() {
+        if let Ok(mut last) = registry.0.lock() {
+            *last = report.clone();
+        }
+    }
+    report
+}
+
+/// Register shortcuts at startup. The UI reads the outcome with
+/// `get_shortcut_report` once it has loaded.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    app.manage(ShortcutRegistry(Mutex::new(ShortcutReport::default())));
+    let report = register_shortcuts(app);
+    for problem in &report.problems {
+        eprintln!(
+            "Shortcut {} for {:?} not registered: {}",
+            problem.accelerator, problem.action, problem.message
+        );
+    }
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+
+/// Re-register shortcuts after the bindings in settings changed.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn reload_shortcuts<R: Runtime>(app: AppHandle<R>) -> ShortcutReport {
+    register_shortcuts(&app)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_shortcut_report(
+    registry: tauri::State<'_, ShortcutRegistry>,
+) -> Result<ShortcutReport, String> {
+    registry
+        .0
+        .lock()
+        .map(|report| report.clone())
+        .map_err(|e| format!("Lock error: {e}"))
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(action: ShortcutAction, accelerator: &str) -> ShortcutBinding {
+        ShortcutBinding {
+            action,
+            accelerator: accelerator.into(),
+        }
+    }
+
+    #[test]
+    fn default_bindings_are_seeded_but_disabled() {
+        let path =
+            std::env::temp_dir().join(format!("pomo-shortcuts-{}-defaults.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+
+        let (enabled, bindings) = read_bindings(&path).unwrap();
+        assert!(!enabled);
+        assert_eq!(bindings.len(), 4);
+        let (valid, problems) = validate(bindings);
+        assert_eq!(valid.len(), 4);
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn invalid_accelerator_is_reported() {
+        let (valid, problems) = validate(vec![binding(ShortcutAction::Skip, "Ctrl+Alt+Nope")]);
+        assert!(valid.is_empty());
+        assert_eq!(problems[0].kind, ShortcutProblemKind::InvalidAccelerator);
+        assert_eq!(problems[0].action, ShortcutAction::Skip);
+    }
+
+    #[test]
+    fn bare_key_is_rejected() {
+        let (_, problems) = validate(vec![binding(ShortcutAction::Toggle, "P")]);
+        assert_eq!(problems[0].kind, ShortcutProblemKind::MissingModifier);
+    }
+
+    #[test]
+    fn duplicate_combination_is_a_conflict() {
+        let (valid, problems) = validate(vec![
+            binding(ShortcutAction::Toggle, "Ctrl+Alt+P"),
+            binding(ShortcutAction::Cancel, "Alt + Control + P"),
+        ]);
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].0.action, ShortcutAction::Toggle);
+        assert_eq!(problems[0].kind, ShortcutProblemKind::Conflict);
+        assert_eq!(problems[0].action, ShortcutAction::Cancel);
+    }
+
+    #[test]
+    fn extend_shortcut_adds_five_minutes() {
+        assert_eq!(
+            ShortcutAction::Extend.timer_action(),
+            TimerAction::Extend(300)
+        );
+    }
+
+    #[test]
+    fn problem_kind_serializes_snake_case() {
+        let json = serde_json::to_string(&ShortcutProblemKind::RegistrationFailed).unwrap();
+        assert_eq!(json, "\"registration_failed\"");
+    }
+}
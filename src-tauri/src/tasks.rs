@@ -2,7 +2,9 @@ use chrono::Utc;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use tauri::{AppHandle, Emitter, Runtime};
 
+use crate::hooks::{self, HookEvent, HookSubject};
 use crate::timer::AppState;
 
 // ── Types ────────────────────────────────────────────────────
@@ -31,6 +33,12 @@ pub struct Task {
     pub completed_in_pomodoro: Option<i64>,
 }
 
+/// Payload of the `tasks-changed` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct TasksChangedPayload {
+    pub day_date: String,
+}
+
 // ── Database helpers ────────────────────────────────────────
 
 fn open_db(db_path: &Path) -> Result<Connection, String> {
@@ -61,30 +69,56 @@ fn row_to_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<Task> {
 const TASK_COLUMNS: &str = "id, title, day_date, status, parent_task_id, linked_from_task_id, \
                             jira_key, tag, position, created_at, updated_at, completed_in_pomodoro";
 
-// ── Tauri commands ──────────────────────────────────────────
+// ── Task operations ─────────────────────────────────────────
+// Plain functions over a connection, shared by the Tauri commands, the
+// CLI and other front-ends.
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn create_task(
-    state: tauri::State<'_, AppState>,
-    title: String,
-    day_date: String,
-    parent_task_id: Option<i64>,
-    jira_key: Option<String>,
-    tag: Option<String>,
-) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
+fn fetch_task(conn: &Connection, id: i64) -> Result<Task, String> {
+    conn.query_row(
+        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
+        [id],
+        row_to_task,
+    )
+    .map_err(|e| format!("Task not found: {e}"))
+}
 
-    // Get next position for this day
+fn next_position(conn: &Connection, day_date: &str) -> Result<i64, String> {
     let max_pos: i64 = conn
         .query_row(
             "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE day_date = ?1 AND parent_task_id IS NULL",
-            [&day_date],
+            [day_date],
             |row| row.get(0),
         )
         .map_err(|e| format!("Failed to query max position: {e}"))?;
-    let position = max_pos + 1;
+    Ok(max_pos + 1)
+}
 
+fn query_subtasks(conn: &Connection, parent_id: i64) -> Result<Vec<Task>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE parent_task_id = ?1"
+        ))
+        .map_err(|e| format!("Failed to prepare subtask query: {e}"))?;
+
+    let subtasks = stmt
+        .query_map([parent_id], row_to_task)
+        .map_err(|e| format!("Failed to query subtasks: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read subtasks: {e}"))?;
+
+    Ok(subtasks)
+}
+
+/// Add a task at the end of its day's list.
+pub fn create(
+    conn: &Connection,
+    title: &str,
+    day_date: &str,
+    parent_task_id: Option<i64>,
+    jira_key: Option<&str>,
+    tag: Option<&str>,
+) -> Result<Task, String> {
+    let position = next_position(conn, day_date)?;
     let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
     conn.execute(
@@ -94,57 +128,43 @@ pub fn create_task(
     )
     .map_err(|e| format!("Failed to create task: {e}"))?;
 
-    let id = conn.last_insert_rowid();
-
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Failed to fetch created task: {e}"))
+    fetch_task(conn, conn.last_insert_rowid())
+        .map_err(|e| format!("Failed to fetch created task: {e}"))
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn update_task(
-    state: tauri::State<'_, AppState>,
+/// Change the given fields of a task; `None` leaves a field as it is.
+pub fn update(
+    conn: &Connection,
     id: i64,
-    title: Option<String>,
-    jira_key: Option<String>,
-    tag: Option<String>,
+    title: Option<&str>,
+    jira_key: Option<&str>,
+    tag: Option<&str>,
 ) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
     let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
     let mut set_clauses = Vec::new();
     let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
     let mut idx = 1;
 
-    if let Some(ref t) = title {
+    if let Some(t) = title {
         set_clauses.push(format!("title = ?{idx}"));
-        params.push(Box::new(t.clone()));
+        params.push(Box::new(t.to_string()));
         idx += 1;
     }
-    if let Some(ref j) = jira_key {
+    if let Some(j) = jira_key {
         set_clauses.push(format!("jira_key = ?{idx}"));
-        params.push(Box::new(j.clone()));
+        params.push(Box::new(j.to_string()));
         idx += 1;
     }
-    if let Some(ref tg) = tag {
+    if let Some(tg) = tag {
         set_clauses.push(format!("tag = ?{idx}"));
-        params.push(Box::new(tg.clone()));
+        params.push(Box::new(tg.to_string()));
         idx += 1;
     }
 
     if set_clauses.is_empty() {
         // Nothing to update, just return the current task
-        return conn
-            .query_row(
-                &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-                [id],
-                row_to_task,
-            )
-            .map_err(|e| format!("Task not found: {e}"));
+        return fetch_task(conn, id);
     }
 
     set_clauses.push(format!("updated_at = ?{idx}"));
@@ -161,25 +181,14 @@ pub fn update_task(
     conn.execute(&sql, param_refs.as_slice())
         .map_err(|e| format!("Failed to update task: {e}"))?;
 
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Task not found: {e}"))
+    fetch_task(conn, id)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn delete_task(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
-    let conn = open_db(&state.db_path)?;
-
+/// Delete a pending task and its subtasks, returning the deleted task.
+pub fn delete(conn: &Connection, id: i64) -> Result<Task, String> {
     // Block delete on completed or abandoned tasks
-    let status: String = conn
-        .query_row("SELECT status FROM tasks WHERE id = ?1", [id], |row| {
-            row.get(0)
-        })
-        .map_err(|e| format!("Task not found: {e}"))?;
+    let task = fetch_task(conn, id)?;
+    let status = &task.status;
 
     if status == "completed" || status == "abandoned" {
         return Err(format!(
@@ -189,18 +198,26 @@ pub fn delete_task(state: tauri::State<'_, AppState>, id: i64) -> Result<(), Str
 
     conn.execute("DELETE FROM tasks WHERE id = ?1", [id])
         .map_err(|e| format!("Failed to delete task: {e}"))?;
-    Ok(())
+    Ok(task)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn complete_task(
-    state: tauri::State<'_, AppState>,
+/// Tell the webview that the tasks of `day_date` changed, whichever
+/// front-end changed them.
+pub fn emit_changed<R: Runtime>(app: &AppHandle<R>, day_date: &str) {
+    let _ = app.emit(
+        "tasks-changed",
+        TasksChangedPayload {
+            day_date: day_date.to_string(),
+        },
+    );
+}
+
+/// Mark a task completed, optionally recording which pomodoro finished it.
+pub fn complete(
+    conn: &Connection,
     id: i64,
     pomodoro_number: Option<i64>,
 ) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
-
     // Check for pending subtasks
     let pending_count: i64 = conn
         .query_row(
@@ -221,18 +238,10 @@ pub fn complete_task(
     )
     .map_err(|e| format!("Failed to complete task: {e}"))?;
 
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Task not found: {e}"))
+    fetch_task(conn, id)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn abandon_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
+pub fn abandon(conn: &Connection, id: i64) -> Result<Task, String> {
     let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
     conn.execute(
@@ -241,19 +250,11 @@ pub fn abandon_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task,
     )
     .map_err(|e| format!("Failed to abandon task: {e}"))?;
 
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Task not found: {e}"))
+    fetch_task(conn, id)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn reopen_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
-
+/// Put a completed or abandoned task back to pending.
+pub fn reopen(conn: &Connection, id: i64) -> Result<Task, String> {
     let status: String = conn
         .query_row("SELECT status FROM tasks WHERE id = ?1", [id], |row| {
             row.get(0)
@@ -271,21 +272,11 @@ pub fn reopen_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task, S
     )
     .map_err(|e| format!("Failed to reopen task: {e}"))?;
 
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Task not found: {e}"))
+    fetch_task(conn, id)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn get_tasks_by_date(
-    state: tauri::State<'_, AppState>,
-    day_date: String,
-) -> Result<Vec<Task>, String> {
-    let conn = open_db(&state.db_path)?;
+/// All tasks (and subtasks) on a day, in list order.
+pub fn list_by_date(conn: &Connection, day_date: &str) -> Result<Vec<Task>, String> {
     let mut stmt = conn
         .prepare(&format!(
             "SELECT {TASK_COLUMNS} FROM tasks WHERE day_date = ?1 ORDER BY position ASC, created_at ASC"
@@ -293,7 +284,7 @@ pub fn get_tasks_by_date(
         .map_err(|e| format!("Failed to prepare query: {e}"))?;
 
     let tasks = stmt
-        .query_map([&day_date], row_to_task)
+        .query_map([day_date], row_to_task)
         .map_err(|e| format!("Failed to query tasks: {e}"))?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Failed to read tasks: {e}"))?;
@@ -301,54 +292,46 @@ pub fn get_tasks_by_date(
     Ok(tasks)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn clone_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
-    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+/// All tasks on days in `[start_date, end_date]`, by day and list order.
+pub fn list_between(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<Task>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE day_date BETWEEN ?1 AND ?2 \
+             ORDER BY day_date ASC, position ASC, created_at ASC"
+        ))
+        .map_err(|e| format!("Failed to prepare query: {e}"))?;
 
-    // Fetch original
-    let original = conn
-        .query_row(
-            &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-            [id],
-            row_to_task,
-        )
-        .map_err(|e| format!("Task not found: {e}"))?;
+    let tasks = stmt
+        .query_map([start_date, end_date], row_to_task)
+        .map_err(|e| format!("Failed to query tasks: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read tasks: {e}"))?;
 
-    // Get next position
-    let max_pos: i64 = conn
-        .query_row(
-            "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE day_date = ?1 AND parent_task_id IS NULL",
-            [&original.day_date],
-            |row| row.get(0),
-        )
-        .map_err(|e| format!("Failed to query max position: {e}"))?;
+    Ok(tasks)
+}
+
+/// Copy a task and its subtasks as new pending tasks on the same day.
+pub fn duplicate(conn: &Connection, id: i64) -> Result<Task, String> {
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let original = fetch_task(conn, id)?;
+    let position = next_position(conn, &original.day_date)?;
 
     // Clone parent task
     conn.execute(
         "INSERT INTO tasks (title, day_date, status, jira_key, tag, position, created_at, updated_at) \
          VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7)",
-        rusqlite::params![original.title, original.day_date, original.jira_key, original.tag, max_pos + 1, now, now],
+        rusqlite::params![original.title, original.day_date, original.jira_key, original.tag, position, now, now],
     )
     .map_err(|e| format!("Failed to clone task: {e}"))?;
 
     let new_id = conn.last_insert_rowid();
 
     // Clone subtasks
-    let mut stmt = conn
-        .prepare(&format!(
-            "SELECT {TASK_COLUMNS} FROM tasks WHERE parent_task_id = ?1"
-        ))
-        .map_err(|e| format!("Failed to prepare subtask query: {e}"))?;
-
-    let subtasks: Vec<Task> = stmt
-        .query_map([id], row_to_task)
-        .map_err(|e| format!("Failed to query subtasks: {e}"))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read subtasks: {e}"))?;
-
-    for sub in subtasks {
+    for sub in query_subtasks(conn, id)? {
         conn.execute(
             "INSERT INTO tasks (title, day_date, status, parent_task_id, jira_key, tag, position, created_at, updated_at) \
              VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8)",
@@ -357,21 +340,11 @@ pub fn clone_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task, St
         .map_err(|e| format!("Failed to clone subtask: {e}"))?;
     }
 
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [new_id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Failed to fetch cloned task: {e}"))
+    fetch_task(conn, new_id).map_err(|e| format!("Failed to fetch cloned task: {e}"))
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn reorder_tasks(
-    state: tauri::State<'_, AppState>,
-    task_ids: Vec<i64>,
-) -> Result<(), String> {
-    let conn = open_db(&state.db_path)?;
+/// Set positions to match the order of `task_ids`.
+pub fn reorder(conn: &Connection, task_ids: &[i64]) -> Result<(), String> {
     for (i, task_id) in task_ids.iter().enumerate() {
         conn.execute(
             "UPDATE tasks SET position = ?1 WHERE id = ?2",
@@ -382,6 +355,35 @@ pub fn reorder_tasks(
     Ok(())
 }
 
+/// Copy a task and its subtasks to another day, linked to the original.
+pub fn copy_to_day(conn: &Connection, id: i64, target_date: &str) -> Result<Task, String> {
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let original = fetch_task(conn, id)?;
+    let position = next_position(conn, target_date)?;
+
+    // Copy parent task with linked_from_task_id
+    conn.execute(
+        "INSERT INTO tasks (title, day_date, status, linked_from_task_id, jira_key, tag, position, created_at, updated_at) \
+         VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![original.title, target_date, id, original.jira_key, original.tag, position, now, now],
+    )
+    .map_err(|e| format!("Failed to copy task: {e}"))?;
+
+    let new_id = conn.last_insert_rowid();
+
+    // Deep copy subtasks
+    for sub in query_subtasks(conn, id)? {
+        conn.execute(
+            "INSERT INTO tasks (title, day_date, status, parent_task_id, jira_key, tag, position, created_at, updated_at) \
+             VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![sub.title, target_date, new_id, sub.jira_key, sub.tag, sub.position, now, now],
+        )
+        .map_err(|e| format!("Failed to copy subtask: {e}"))?;
+    }
+
+    fetch_task(conn, new_id).map_err(|e| format!("Failed to fetch copied task: {e}"))
+}
+
 // ── Task-Interval Link types ────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -390,16 +392,13 @@ pub struct TaskIntervalCount {
     pub count: i64,
 }
 
-// ── Task-Interval Link commands ─────────────────────────────
+// ── Task-Interval Link operations ───────────────────────────
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn link_tasks_to_interval(
-    state: tauri::State<'_, AppState>,
-    task_ids: Vec<i64>,
+pub fn link_to_interval(
+    conn: &Connection,
+    task_ids: &[i64],
     interval_id: i64,
 ) -> Result<(), String> {
-    let conn = open_db(&state.db_path)?;
     for task_id in task_ids {
         conn.execute(
             "INSERT OR IGNORE INTO task_interval_links (task_id, interval_id) VALUES (?1, ?2)",
@@ -410,13 +409,8 @@ pub fn link_tasks_to_interval(
     Ok(())
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn get_task_interval_counts(
-    state: tauri::State<'_, AppState>,
-    day_date: String,
-) -> Result<Vec<TaskIntervalCount>, String> {
-    let conn = open_db(&state.db_path)?;
+/// Linked interval counts for the top-level tasks of a day that have any.
+pub fn interval_counts(conn: &Connection, day_date: &str) -> Result<Vec<TaskIntervalCount>, String> {
     let mut stmt = conn
         .prepare(
             "SELECT t.id, COUNT(til.id) as link_count \
@@ -429,7 +423,7 @@ pub fn get_task_interval_counts(
         .map_err(|e| format!("Failed to prepare interval count query: {e}"))?;
 
     let counts = stmt
-        .query_map([&day_date], |row| {
+        .query_map([day_date], |row| {
             Ok(TaskIntervalCount {
                 task_id: row.get(0)?,
                 count: row.get(1)?,
@@ -450,84 +444,14 @@ pub struct TaskOriginDate {
     pub origin_day_date: String,
 }
 
-// ── Cross-day copy & history commands ────────────────────────
+// ── History operations ──────────────────────────────────────
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn copy_task_to_day(
-    state: tauri::State<'_, AppState>,
-    id: i64,
-    target_date: String,
-) -> Result<Task, String> {
-    let conn = open_db(&state.db_path)?;
-    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-
-    // Fetch original
-    let original = conn
-        .query_row(
-            &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-            [id],
-            row_to_task,
-        )
-        .map_err(|e| format!("Task not found: {e}"))?;
-
-    // Get next position on the target day
-    let max_pos: i64 = conn
-        .query_row(
-            "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE day_date = ?1 AND parent_task_id IS NULL",
-            [&target_date],
-            |row| row.get(0),
-        )
-        .map_err(|e| format!("Failed to query max position: {e}"))?;
-
-    // Copy parent task with linked_from_task_id
-    conn.execute(
-        "INSERT INTO tasks (title, day_date, status, linked_from_task_id, jira_key, tag, position, created_at, updated_at) \
-         VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8)",
-        rusqlite::params![original.title, target_date, id, original.jira_key, original.tag, max_pos + 1, now, now],
-    )
-    .map_err(|e| format!("Failed to copy task: {e}"))?;
-
-    let new_id = conn.last_insert_rowid();
-
-    // Deep copy subtasks
-    let mut stmt = conn
-        .prepare(&format!(
-            "SELECT {TASK_COLUMNS} FROM tasks WHERE parent_task_id = ?1"
-        ))
-        .map_err(|e| format!("Failed to prepare subtask query: {e}"))?;
-
-    let subtasks: Vec<Task> = stmt
-        .query_map([id], row_to_task)
-        .map_err(|e| format!("Failed to query subtasks: {e}"))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read subtasks: {e}"))?;
-
-    for sub in subtasks {
-        conn.execute(
-            "INSERT INTO tasks (title, day_date, status, parent_task_id, jira_key, tag, position, created_at, updated_at) \
-             VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8)",
-            rusqlite::params![sub.title, target_date, new_id, sub.jira_key, sub.tag, sub.position, now, now],
-        )
-        .map_err(|e| format!("Failed to copy subtask: {e}"))?;
-    }
-
-    conn.query_row(
-        &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1"),
-        [new_id],
-        row_to_task,
-    )
-    .map_err(|e| format!("Failed to fetch copied task: {e}"))
-}
-
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn get_days_with_tasks(
-    state: tauri::State<'_, AppState>,
-    start_date: String,
-    end_date: String,
+/// Days in `[start_date, end_date]` that have at least one top-level task.
+pub fn days_with_tasks(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
 ) -> Result<Vec<String>, String> {
-    let conn = open_db(&state.db_path)?;
     let mut stmt = conn
         .prepare(
             "SELECT DISTINCT day_date FROM tasks \
@@ -545,13 +469,8 @@ pub fn get_days_with_tasks(
     Ok(dates)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn get_task_origin_dates(
-    state: tauri::State<'_, AppState>,
-    day_date: String,
-) -> Result<Vec<TaskOriginDate>, String> {
-    let conn = open_db(&state.db_path)?;
+/// For tasks on `day_date` copied from another day, the day they came from.
+pub fn origin_dates(conn: &Connection, day_date: &str) -> Result<Vec<TaskOriginDate>, String> {
     let mut stmt = conn
         .prepare(
             "SELECT t.id, origin.day_date \
@@ -562,7 +481,7 @@ pub fn get_task_origin_dates(
         .map_err(|e| format!("Failed to prepare origin date query: {e}"))?;
 
     let origins = stmt
-        .query_map([&day_date], |row| {
+        .query_map([day_date], |row| {
             Ok(TaskOriginDate {
                 task_id: row.get(0)?,
                 origin_day_date: row.get(1)?,
@@ -575,6 +494,180 @@ pub fn get_task_origin_dates(
     Ok(origins)
 }
 
+// ── Tauri commands ──────────────────────────────────────────
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn create_task<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    title: String,
+    day_date: String,
+    parent_task_id: Option<i64>,
+    jira_key: Option<String>,
+    tag: Option<String>,
+) -> Result<Task, String> {
+    let conn = open_db(&state.db_path)?;
+    let task = create(
+        &conn,
+        &title,
+        &day_date,
+        parent_task_id,
+        jira_key.as_deref(),
+        tag.as_deref(),
+    )?;
+    emit_changed(&app, &task.day_date);
+    Ok(task)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn update_task<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    title: Option<String>,
+    jira_key: Option<String>,
+    tag: Option<String>,
+) -> Result<Task, String> {
+    let conn = open_db(&state.db_path)?;
+    let task = update(
+        &conn,
+        id,
+        title.as_deref(),
+        jira_key.as_deref(),
+        tag.as_deref(),
+    )?;
+    emit_changed(&app, &task.day_date);
+    Ok(task)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn delete_task<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<(), String> {
+    let task = delete(&open_db(&state.db_path)?, id)?;
+    emit_changed(&app, &task.day_date);
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn complete_task<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    pomodoro_number: Option<i64>,
+) -> Result<Task, String> {
+    let task = complete(&open_db(&state.db_path)?, id, pomodoro_number)?;
+    emit_changed(&app, &task.day_date);
+    hooks::fire(
+        &state.db_path,
+        HookEvent::TaskCompleted,
+        HookSubject::Task(Box::new(task.clone())),
+    );
+    Ok(task)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn abandon_task<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<Task, String> {
+    let task = abandon(&open_db(&state.db_path)?, id)?;
+    emit_changed(&app, &task.day_date);
+    Ok(task)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn reopen_task<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<Task, String> {
+    let task = reopen(&open_db(&state.db_path)?, id)?;
+    emit_changed(&app, &task.day_date);
+    Ok(task)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_tasks_by_date(
+    state: tauri::State<'_, AppState>,
+    day_date: String,
+) -> Result<Vec<Task>, String> {
+    list_by_date(&open_db(&state.db_path)?, &day_date)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn clone_task(state: tauri::State<'_, AppState>, id: i64) -> Result<Task, String> {
+    duplicate(&open_db(&state.db_path)?, id)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn reorder_tasks(
+    state: tauri::State<'_, AppState>,
+    task_ids: Vec<i64>,
+) -> Result<(), String> {
+    reorder(&open_db(&state.db_path)?, &task_ids)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn link_tasks_to_interval(
+    state: tauri::State<'_, AppState>,
+    task_ids: Vec<i64>,
+    interval_id: i64,
+) -> Result<(), String> {
+    link_to_interval(&open_db(&state.db_path)?, &task_ids, interval_id)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_task_interval_counts(
+    state: tauri::State<'_, AppState>,
+    day_date: String,
+) -> Result<Vec<TaskIntervalCount>, String> {
+    interval_counts(&open_db(&state.db_path)?, &day_date)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn copy_task_to_day(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    target_date: String,
+) -> Result<Task, String> {
+    copy_to_day(&open_db(&state.db_path)?, id, &target_date)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_days_with_tasks(
+    state: tauri::State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<String>, String> {
+    days_with_tasks(&open_db(&state.db_path)?, &start_date, &end_date)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn get_task_origin_dates(
+    state: tauri::State<'_, AppState>,
+    day_date: String,
+) -> Result<Vec<TaskOriginDate>, String> {
+    origin_dates(&open_db(&state.db_path)?, &day_date)
+}
+
 // ── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
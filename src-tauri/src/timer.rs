@@ -1,10 +1,25 @@
-use chrono::Utc;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Notify;
+
+use crate::audio::{self, AudioService};
+use crate::calendar;
+use crate::clock::{Clock, SystemClock};
+use crate::idle::{self, IdleSource};
+use crate::meetings;
+use crate::hooks::{self, HookEvent, HookSubject};
+use crate::notifications;
+use crate::tray;
+
+/// UI tick period while a window has focus.
+const FOCUSED_TICK_PERIOD: Duration = Duration::from_millis(250);
+/// UI tick period while no window has focus.
+const UNFOCUSED_TICK_PERIOD: Duration = Duration::from_secs(1);
 
 // ── Enums ────────────────────────────────────────────────────
 
@@ -25,13 +40,22 @@ pub enum IntervalType {
 }
 
 impl IntervalType {
-    fn as_db_str(self) -> &'static str {
+    pub(crate) fn as_db_str(self) -> &'static str {
         match self {
             Self::Work => "work",
             Self::ShortBreak => "short_break",
             Self::LongBreak => "long_break",
         }
     }
+
+    /// Human-readable name for tray and notification text.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Work => "Work",
+            Self::ShortBreak => "Short break",
+            Self::LongBreak => "Long break",
+        }
+    }
 }
 
 // ── Event payloads ──────────────────────────────────────────
@@ -43,7 +67,7 @@ pub struct TimerTickPayload {
     pub overtime_ms: u64,
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TimerCompletePayload {
     pub interval_id: i64,
     pub interval_type: IntervalType,
@@ -63,6 +87,10 @@ pub struct TimerStatus {
     pub completed_work_count: u32,
     pub overtime: bool,
     pub overtime_ms: u64,
+    /// True while auto-paused for inactivity and awaiting a keep/discard decision.
+    pub idle_paused: bool,
+    /// Incremented on every state transition; listeners drop stale updates.
+    pub seq: u64,
 }
 
 // ── Timer inner state ───────────────────────────────────────
@@ -75,9 +103,30 @@ pub struct TimerInner {
     planned_duration_seconds: u32,
     interval_id: Option<i64>,
     completed_work_count: u32,
+    /// Type of the interval that last ran to completion, cleared on start.
+    last_completed: Option<IntervalType>,
     overtime: bool,
     break_overtime_enabled: bool,
     overtime_start: Option<Instant>,
+    /// When the user went idle, if the timer was auto-paused for inactivity.
+    idle_started: Option<Instant>,
+    /// Whether the "welcome back" prompt has been emitted for the idle pause.
+    idle_return_notified: bool,
+    /// Transition sequence number, bumped on every state change.
+    seq: u64,
+    clock: Arc<dyn Clock>,
+}
+
+/// The break earned after `completed_work_count` work intervals.
+pub(crate) fn break_after(completed_work_count: u32, long_break_frequency: u32) -> IntervalType {
+    if long_break_frequency > 0
+        && completed_work_count > 0
+        && completed_work_count.is_multiple_of(long_break_frequency)
+    {
+        IntervalType::LongBreak
+    } else {
+        IntervalType::ShortBreak
+    }
 }
 
 /// Convert a `Duration` to milliseconds without truncation casts.
@@ -86,7 +135,11 @@ fn duration_to_ms(d: Duration) -> u64 {
 }
 
 impl TimerInner {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub(crate) fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
             state: TimerState::Idle,
             interval_type: IntervalType::Work,
@@ -95,9 +148,14 @@ impl TimerInner {
             planned_duration_seconds: 0,
             interval_id: None,
             completed_work_count: 0,
+            last_completed: None,
             overtime: false,
             break_overtime_enabled: false,
             overtime_start: None,
+            idle_started: None,
+            idle_return_notified: false,
+            seq: 0,
+            clock,
         }
     }
 
@@ -107,7 +165,7 @@ impl TimerInner {
         }
         match self.state {
             TimerState::Running => self.end_instant.map_or(0, |end| {
-                let now = Instant::now();
+                let now = self.clock.now();
                 if end > now {
                     duration_to_ms(end.duration_since(now))
                 } else {
@@ -122,7 +180,7 @@ impl TimerInner {
     fn compute_overtime_ms(&self) -> u64 {
         if self.overtime {
             self.overtime_start.map_or(0, |start| {
-                duration_to_ms(Instant::now().duration_since(start))
+                duration_to_ms(self.clock.now().duration_since(start))
             })
         } else {
             0
@@ -131,10 +189,11 @@ impl TimerInner {
 
     fn enter_overtime(&mut self) {
         self.overtime = true;
-        self.overtime_start = Some(Instant::now());
+        self.overtime_start = Some(self.clock.now());
+        self.seq += 1;
     }
 
-    fn status(&self) -> TimerStatus {
+    pub(crate) fn status(&self) -> TimerStatus {
         TimerStatus {
             state: self.state,
             interval_type: self.interval_type,
@@ -144,11 +203,34 @@ impl TimerInner {
             completed_work_count: self.completed_work_count,
             overtime: self.overtime,
             overtime_ms: self.compute_overtime_ms(),
+            idle_paused: self.idle_started.is_some(),
+            seq: self.seq,
+        }
+    }
+
+    /// The interval currently being timed, if any. Overtime is excluded
+    /// because its interval is already completed in the DB.
+    pub(crate) fn active_interval(&self) -> Option<(i64, IntervalType)> {
+        if self.state == TimerState::Idle || self.overtime {
+            return None;
+        }
+        self.interval_id.map(|id| (id, self.interval_type))
+    }
+
+    /// The running work interval eligible for idle auto-pause, if any.
+    pub(crate) fn idle_candidate(&self) -> Option<i64> {
+        if self.state == TimerState::Running
+            && !self.overtime
+            && self.interval_type == IntervalType::Work
+        {
+            self.interval_id
+        } else {
+            None
         }
     }
 
     /// Transition from Idle → Running. Returns `Err` if not Idle.
-    fn start(
+    pub(crate) fn start(
         &mut self,
         interval_type: IntervalType,
         duration_seconds: u32,
@@ -161,20 +243,23 @@ impl TimerInner {
         self.interval_type = interval_type;
         self.planned_duration_seconds = duration_seconds;
         self.interval_id = Some(interval_id);
+        self.last_completed = None;
         self.end_instant =
-            Some(Instant::now() + Duration::from_secs(u64::from(duration_seconds)));
+            Some(self.clock.now() + Duration::from_secs(u64::from(duration_seconds)));
         self.remaining_ms = u64::from(duration_seconds) * 1000;
+        self.seq += 1;
         Ok(())
     }
 
     /// Transition from Running → Paused. Returns `Err` if not Running.
-    fn pause(&mut self) -> Result<(), &'static str> {
+    pub(crate) fn pause(&mut self) -> Result<(), &'static str> {
         if self.state != TimerState::Running {
             return Err("Timer is not running");
         }
         self.remaining_ms = self.compute_remaining_ms();
         self.state = TimerState::Paused;
         self.end_instant = None;
+        self.seq += 1;
         Ok(())
     }
 
@@ -184,10 +269,107 @@ impl TimerInner {
             return Err("Timer is not paused");
         }
         self.state = TimerState::Running;
-        self.end_instant = Some(Instant::now() + Duration::from_millis(self.remaining_ms));
+        self.end_instant = Some(self.clock.now() + Duration::from_millis(self.remaining_ms));
+        self.idle_started = None;
+        self.idle_return_notified = false;
+        self.seq += 1;
+        Ok(())
+    }
+
+    /// Add time to the running or paused interval. Overtime cannot be
+    /// extended because its interval is already completed.
+    pub(crate) fn extend(&mut self, seconds: u32) -> Result<(), &'static str> {
+        if self.state == TimerState::Idle || self.overtime {
+            return Err("No interval to extend");
+        }
+        if let Some(end) = self.end_instant {
+            self.end_instant = Some(end + Duration::from_secs(u64::from(seconds)));
+        }
+        self.remaining_ms += u64::from(seconds) * 1000;
+        self.planned_duration_seconds = self.planned_duration_seconds.saturating_add(seconds);
+        self.seq += 1;
         Ok(())
     }
 
+    /// Auto-pause a running work interval after `idle` of inactivity.
+    /// The idle time is credited back so it does not count as focus.
+    pub(crate) fn idle_pause(&mut self, idle: Duration) -> Result<(), &'static str> {
+        if self.state != TimerState::Running
+            || self.overtime
+            || self.interval_type != IntervalType::Work
+        {
+            return Err("Only a running work interval can be idle-paused");
+        }
+        self.pause()?;
+        let planned_ms = u64::from(self.planned_duration_seconds) * 1000;
+        self.remaining_ms = (self.remaining_ms + duration_to_ms(idle)).min(planned_ms);
+        let now = self.clock.now();
+        self.idle_started = Some(now.checked_sub(idle).unwrap_or(now));
+        self.idle_return_notified = false;
+        Ok(())
+    }
+
+    /// Time since the user went idle, if paused for inactivity.
+    pub(crate) fn idle_away(&self) -> Option<Duration> {
+        self.idle_started
+            .map(|start| self.clock.now().duration_since(start))
+    }
+
+    /// The interval that should follow what last ran: a break after a
+    /// completed work interval, otherwise work.
+    pub(crate) fn next_interval_type(&self, long_break_frequency: u32) -> IntervalType {
+        match self.last_completed {
+            Some(IntervalType::Work) => {
+                break_after(self.completed_work_count, long_break_frequency)
+            }
+            _ => IntervalType::Work,
+        }
+    }
+
+    /// Time until the tick task should next wake, or `None` if the timer
+    /// is not running. Wakes are aligned so the displayed countdown changes
+    /// on `period` boundaries and the last wake lands exactly on the deadline.
+    pub(crate) fn next_wake(&self, period: Duration) -> Option<Duration> {
+        if self.state != TimerState::Running {
+            return None;
+        }
+        let period_ms = duration_to_ms(period).max(1);
+        if self.overtime {
+            let overtime_ms = self.compute_overtime_ms();
+            return Some(Duration::from_millis(period_ms - overtime_ms % period_ms));
+        }
+        let remaining_ms = self.compute_remaining_ms();
+        let until_tick = match remaining_ms % period_ms {
+            0 => period_ms,
+            partial => partial,
+        };
+        Some(Duration::from_millis(until_tick.min(remaining_ms)))
+    }
+
+    /// Mark the return prompt as sent. Returns false if it already was.
+    pub(crate) fn mark_idle_return_notified(&mut self) -> bool {
+        if self.idle_started.is_none() || self.idle_return_notified {
+            return false;
+        }
+        self.idle_return_notified = true;
+        true
+    }
+
+    /// Resume after an idle pause. With `keep`, the time away counts
+    /// towards the interval; otherwise it is discarded.
+    pub(crate) fn resolve_idle(&mut self, keep: bool) -> Result<(), &'static str> {
+        if self.state != TimerState::Paused {
+            return Err("Timer is not paused");
+        }
+        let Some(away) = self.idle_away() else {
+            return Err("Timer was not paused for inactivity");
+        };
+        if keep {
+            self.remaining_ms = self.remaining_ms.saturating_sub(duration_to_ms(away));
+        }
+        self.resume()
+    }
+
     /// Transition from Running|Paused → Idle on cancel.
     /// Returns the elapsed time in seconds (or 0 if in overtime, since interval is already completed).
     fn cancel(&mut self) -> Result<u32, &'static str> {
@@ -203,6 +385,7 @@ impl TimerInner {
             self.overtime = false;
             self.overtime_start = None;
             self.interval_id = None;
+            self.seq += 1;
             return Ok(0);
         }
 
@@ -216,12 +399,16 @@ impl TimerInner {
         self.remaining_ms = 0;
         self.overtime = false;
         self.overtime_start = None;
+        self.idle_started = None;
+        self.idle_return_notified = false;
+        self.seq += 1;
         // interval_id is intentionally NOT cleared here — caller reads it before reset
         Ok(u32::try_from(elapsed_seconds).unwrap_or(u32::MAX))
     }
 
     /// Mark the current interval as complete and update work count.
     fn complete(&mut self) {
+        self.last_completed = Some(self.interval_type);
         match self.interval_type {
             IntervalType::Work => self.completed_work_count += 1,
             IntervalType::LongBreak => self.completed_work_count = 0,
@@ -233,6 +420,9 @@ impl TimerInner {
         self.interval_id = None;
         self.overtime = false;
         self.overtime_start = None;
+        self.idle_started = None;
+        self.idle_return_notified = false;
+        self.seq += 1;
     }
 }
 
@@ -241,6 +431,15 @@ impl TimerInner {
 pub struct AppState {
     pub timer: Mutex<TimerInner>,
     pub db_path: PathBuf,
+    pub idle_source: Arc<dyn IdleSource>,
+    pub clock: Arc<dyn Clock>,
+    pub audio: AudioService,
+    /// Wakes the tick task so it re-reads the deadline after a transition.
+    ticker: Notify,
+    /// Identifies the current tick task; older tasks exit when they see a newer one.
+    tick_generation: AtomicU64,
+    /// Whether any window has focus; controls the UI tick rate.
+    ui_focused: AtomicBool,
 }
 
 impl AppState {
@@ -248,6 +447,61 @@ impl AppState {
         Self {
             timer: Mutex::new(TimerInner::new()),
             db_path,
+            idle_source: Arc::from(idle::platform_source()),
+            clock: Arc::new(SystemClock),
+            audio: AudioService::default(),
+            ticker: Notify::new(),
+            tick_generation: AtomicU64::new(0),
+            ui_focused: AtomicBool::new(true),
+        }
+    }
+
+    /// Replace the platform idle source (used by tests).
+    #[must_use]
+    pub fn with_idle_source(mut self, idle_source: Arc<dyn IdleSource>) -> Self {
+        self.idle_source = idle_source;
+        self
+    }
+
+    /// Replace the system clock (used by tests to run the timer at
+    /// simulated speed). Must be called before the timer is started.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.timer = Mutex::new(TimerInner::with_clock(Arc::clone(&clock)));
+        self.clock = clock;
+        self
+    }
+
+    /// Wake the tick task so it re-arms against the current deadline and
+    /// tick period.
+    fn rearm_ticker(&self) {
+        self.ticker.notify_waiters();
+    }
+
+    /// Retire the current tick task (it exits on its next wake) and return
+    /// the generation for a replacement. Call on every transition.
+    pub(crate) fn retire_tick_task(&self) -> u64 {
+        let generation = self.tick_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.rearm_ticker();
+        generation
+    }
+
+    fn is_current_tick(&self, generation: u64) -> bool {
+        self.tick_generation.load(Ordering::SeqCst) == generation
+    }
+
+    /// Record window focus; unfocused windows get throttled UI ticks.
+    pub fn set_ui_focused(&self, focused: bool) {
+        if self.ui_focused.swap(focused, Ordering::Relaxed) != focused {
+            self.rearm_ticker();
+        }
+    }
+
+    fn tick_period(&self) -> Duration {
+        if self.ui_focused.load(Ordering::Relaxed) {
+            FOCUSED_TICK_PERIOD
+        } else {
+            UNFOCUSED_TICK_PERIOD
         }
     }
 }
@@ -312,140 +566,210 @@ fn db_cancel_interval(
     Ok(())
 }
 
+fn db_set_planned_duration(
+    db_path: &Path,
+    id: i64,
+    planned_duration_seconds: u32,
+) -> Result<(), String> {
+    let conn = open_db(db_path)?;
+    conn.execute(
+        "UPDATE timer_intervals SET planned_duration_seconds = ?1 WHERE id = ?2",
+        rusqlite::params![planned_duration_seconds, id],
+    )
+    .map_err(|e| format!("Failed to extend interval: {e}"))?;
+    Ok(())
+}
+
+/// Record the start of a pause for the given interval.
+fn db_insert_pause(db_path: &Path, interval_id: i64, paused_at: &str) -> Result<(), String> {
+    let conn = open_db(db_path)?;
+    conn.execute(
+        "INSERT INTO interval_pauses (interval_id, paused_at) VALUES (?1, ?2)",
+        rusqlite::params![interval_id, paused_at],
+    )
+    .map_err(|e| format!("Failed to record pause: {e}"))?;
+    Ok(())
+}
+
+/// Close the open pause (if any) for the given interval.
+fn db_end_pause(db_path: &Path, interval_id: i64, resumed_at: &str) -> Result<(), String> {
+    let conn = open_db(db_path)?;
+    conn.execute(
+        "UPDATE interval_pauses \
+         SET resumed_at = ?1, \
+             duration_seconds = MAX(0, CAST(ROUND((julianday(?1) - julianday(paused_at)) * 86400) AS INTEGER)) \
+         WHERE interval_id = ?2 AND resumed_at IS NULL",
+        rusqlite::params![resumed_at, interval_id],
+    )
+    .map_err(|e| format!("Failed to record resume: {e}"))?;
+    Ok(())
+}
+
+/// Drop the open pause for the given interval, so the paused time counts as focus.
+fn db_discard_pause(db_path: &Path, interval_id: i64) -> Result<(), String> {
+    let conn = open_db(db_path)?;
+    conn.execute(
+        "DELETE FROM interval_pauses WHERE interval_id = ?1 AND resumed_at IS NULL",
+        [interval_id],
+    )
+    .map_err(|e| format!("Failed to discard pause: {e}"))?;
+    Ok(())
+}
+
+// ── Events ──────────────────────────────────────────────────
+
+/// Broadcast a state transition to every listener (all windows, tray).
+/// Listeners should ignore payloads whose `seq` is not newer than the last seen.
+pub(crate) fn emit_state_changed<R: Runtime>(app: &AppHandle<R>, status: &TimerStatus) {
+    let _ = app.emit("timer-state-changed", status.clone());
+    tray::refresh_status(app, status);
+    if let Some(state) = app.try_state::<AppState>() {
+        audio::sync_ambient(&state, status);
+    }
+}
+
 // ── Background tick task ────────────────────────────────────
 
-fn spawn_tick_task<R: Runtime>(app: AppHandle<R>) {
-    tauri::async_runtime::spawn(async move {
-        let mut ticker = tokio::time::interval(Duration::from_millis(250));
+/// Outcome of advancing the timer to the current time.
+pub(crate) enum Tick {
+    /// Still counting down (or counting overtime).
+    Running(TimerTickPayload),
+    /// The interval just ended and was recorded as completed. When the
+    /// payload's `overtime` is set the timer keeps running in overtime.
+    Completed(TimerCompletePayload, TimerStatus),
+    /// The timer is not running; the tick task should exit.
+    Stopped,
+}
 
-        loop {
-            ticker.tick().await;
+/// Advance the running timer to the clock's current time, completing the
+/// interval in the DB once its end is reached.
+pub(crate) fn advance_timer(state: &AppState) -> Tick {
+    let mut timer = state.timer.lock().expect("timer lock poisoned");
+    if timer.state != TimerState::Running {
+        return Tick::Stopped;
+    }
 
-            let state = app.state::<AppState>();
+    // In overtime mode, keep ticking with overtime_ms
+    if timer.overtime {
+        return Tick::Running(TimerTickPayload {
+            remaining_ms: 0,
+            interval_type: timer.interval_type,
+            overtime_ms: timer.compute_overtime_ms(),
+        });
+    }
 
-            // Read state under lock, release quickly
-            let tick_data = {
-                let timer = state.timer.lock().expect("timer lock poisoned");
-                if timer.state != TimerState::Running {
-                    return; // Timer no longer running — exit task
-                }
+    let Some(end) = timer.end_instant else {
+        return Tick::Stopped;
+    };
+    let interval_type = timer.interval_type;
+    let now = state.clock.now();
+    if now < end {
+        return Tick::Running(TimerTickPayload {
+            remaining_ms: duration_to_ms(end.duration_since(now)),
+            interval_type,
+            overtime_ms: 0,
+        });
+    }
 
-                // In overtime mode, keep ticking with overtime_ms
-                if timer.overtime {
-                    let overtime_ms = timer.compute_overtime_ms();
-                    let _ = app.emit(
-                        "timer-tick",
-                        TimerTickPayload {
-                            remaining_ms: 0,
-                            interval_type: timer.interval_type,
-                            overtime_ms,
-                        },
-                    );
-                    continue;
-                }
+    let interval_id = timer.interval_id.unwrap_or(0);
+    let planned = timer.planned_duration_seconds;
+    let is_break = matches!(interval_type, IntervalType::ShortBreak | IntervalType::LongBreak);
+    let overtime = is_break && timer.break_overtime_enabled;
 
-                let Some(end) = timer.end_instant else {
-                    return;
-                };
-                (
-                    end,
-                    timer.interval_type,
-                    timer.interval_id.unwrap_or(0),
-                    timer.planned_duration_seconds,
-                    timer.break_overtime_enabled,
-                )
-            };
-
-            let (end, interval_type, interval_id, planned, break_overtime_enabled) = tick_data;
-            let now = Instant::now();
-
-            if now >= end {
-                let is_break = matches!(interval_type, IntervalType::ShortBreak | IntervalType::LongBreak);
-
-                if is_break && break_overtime_enabled {
-                    // Complete the interval in DB, enter overtime mode
-                    let db_path = state.db_path.clone();
-                    let completed_work_count = {
-                        let mut timer = state.timer.lock().expect("timer lock poisoned");
-                        if timer.state != TimerState::Running {
-                            return;
-                        }
-                        timer.complete();
-                        let cwc = timer.completed_work_count;
-                        // Re-enter Running state for overtime display
-                        timer.state = TimerState::Running;
-                        timer.interval_type = interval_type;
-                        timer.enter_overtime();
-                        cwc
-                    };
-
-                    let end_time = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-                    let _ = db_complete_interval(&db_path, interval_id, &end_time, planned);
-
-                    let _ = app.emit(
-                        "timer-complete",
-                        TimerCompletePayload {
-                            interval_id,
-                            interval_type,
-                            completed_work_count,
-                            overtime: true,
-                        },
-                    );
-
-                    // Continue loop — don't return, overtime ticking will happen
-                    continue;
-                }
+    timer.complete();
+    if overtime {
+        // Re-enter Running state for overtime display
+        timer.state = TimerState::Running;
+        timer.interval_type = interval_type;
+        timer.enter_overtime();
+    }
+    let payload = TimerCompletePayload {
+        interval_id,
+        interval_type,
+        completed_work_count: timer.completed_work_count,
+        overtime,
+    };
+    let status = timer.status();
+    drop(timer);
 
-                // Normal completion
-                let db_path = state.db_path.clone();
-                let completed_work_count = {
-                    let mut timer = state.timer.lock().expect("timer lock poisoned");
-                    // Guard against race: another command may have changed state
-                    if timer.state != TimerState::Running {
-                        return;
-                    }
-                    timer.complete();
-                    timer.completed_work_count
-                };
-
-                let end_time = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-                let _ = db_complete_interval(&db_path, interval_id, &end_time, planned);
-
-                let _ = app.emit(
-                    "timer-complete",
-                    TimerCompletePayload {
-                        interval_id,
-                        interval_type,
-                        completed_work_count,
-                        overtime: false,
-                    },
-                );
+    let _ = db_complete_interval(&state.db_path, interval_id, &state.clock.timestamp(), planned);
+    Tick::Completed(payload, status)
+}
 
-                return;
-            }
+/// Drive the running timer: sleep until the next UI tick or the deadline,
+/// whichever is first, re-arming whenever `AppState::rearm_ticker` is called.
+/// Any previously spawned tick task is retired, so at most one runs.
+pub(crate) fn spawn_tick_task<R: Runtime>(app: AppHandle<R>) {
+    let generation = app.state::<AppState>().retire_tick_task();
+    meetings::warn_on_meeting_conflict(&app);
+    tauri::async_runtime::spawn(run_tick_task(app, generation));
+}
 
-            // Normal tick
-            let remaining_ms = duration_to_ms(end.duration_since(now));
-            let _ = app.emit(
-                "timer-tick",
-                TimerTickPayload {
-                    remaining_ms,
-                    interval_type,
-                    overtime_ms: 0,
-                },
-            );
+/// Body of the tick task for `generation`; returns once the timer stops or
+/// a newer task takes over.
+async fn run_tick_task<R: Runtime>(app: AppHandle<R>, generation: u64) {
+    // Overtime at the previous tick, for spacing reminder chimes
+    let mut last_overtime_ms = None;
+    loop {
+        let state = app.state::<AppState>();
+        if !state.is_current_tick(generation) {
+            return;
+        }
+        // Register for wakeups before reading the deadline so a
+        // transition in between is not missed
+        let rearmed = state.ticker.notified();
+        let wake = {
+            let timer = state.timer.lock().expect("timer lock poisoned");
+            timer.next_wake(state.tick_period())
+        };
+        let Some(wake) = wake else {
+            return;
+        };
+
+        tokio::select! {
+            () = tokio::time::sleep(wake) => {}
+            () = rearmed => continue,
+        }
+
+        match advance_timer(&state) {
+            Tick::Running(payload) => {
+                if payload.remaining_ms == 0 {
+                    audio::overtime_reminder(&state, last_overtime_ms, payload.overtime_ms);
+                    last_overtime_ms = Some(payload.overtime_ms);
+                }
+                tray::refresh_tick(&app, &payload);
+                let _ = app.emit("timer-tick", payload);
+            }
+            Tick::Completed(payload, status) => {
+                let overtime = payload.overtime;
+                notifications::notify_complete(&app, &payload, status.seq);
+                audio::ring_completion_alarm(&state, payload.interval_type);
+                hooks::fire(
+                    &state.db_path,
+                    HookEvent::IntervalCompleted,
+                    HookSubject::Interval(payload.clone()),
+                );
+                let _ = app.emit("timer-complete", payload);
+                emit_state_changed(&app, &status);
+                calendar::spawn_feed_refresh(&app, &state.db_path);
+                // In overtime the loop continues so overtime ticks are emitted
+                if !overtime {
+                    return;
+                }
+            }
+            Tick::Stopped => return,
         }
-    });
+    }
 }
 
-// ── Tauri commands ──────────────────────────────────────────
-// `tauri::State` is injected by value per Tauri's command API; clippy's
-// suggestion to take a reference does not compile with the framework.
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn start_timer<R: Runtime>(
-    state: tauri::State<'_, AppState>,
-    app: AppHandle<R>,
+// ── Timer operations ────────────────────────────────────────
+// Shared by the Tauri commands and other front-ends. Callers are
+// responsible for emitting `timer-state-changed` and for (re)spawning the
+// tick task after a start or resume.
+
+/// Start a new interval and record it as in progress in the DB.
+pub(crate) fn start_interval(
+    state: &AppState,
     interval_type: IntervalType,
     duration_seconds: u32,
 ) -> Result<TimerStatus, String> {
@@ -465,7 +789,7 @@ pub fn start_timer<R: Runtime>(
             == "true"
     };
 
-    let start_time = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let start_time = state.clock.timestamp();
     let interval_id =
         db_insert_interval(&state.db_path, interval_type, &start_time, duration_seconds)?;
 
@@ -480,44 +804,133 @@ pub fn start_timer<R: Runtime>(
             .map_err(String::from)?;
         timer.status()
     };
-
-    spawn_tick_task(app);
+    // The previous interval's alarm is no longer relevant
+    state.audio.stop_alarm();
+    hooks::fire_interval(&state.db_path, HookEvent::IntervalStarted, interval_id, &status);
     Ok(status)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn pause_timer(state: tauri::State<'_, AppState>) -> Result<TimerStatus, String> {
-    let mut timer = state
-        .timer
-        .lock()
-        .map_err(|e| format!("Lock error: {e}"))?;
-    timer.pause().map_err(String::from)?;
-    Ok(timer.status())
+/// Pause the running interval and open a pause record for it.
+pub(crate) fn pause_active_interval(state: &AppState) -> Result<TimerStatus, String> {
+    pause_interval(state, None)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn resume_timer<R: Runtime>(
-    state: tauri::State<'_, AppState>,
-    app: AppHandle<R>,
+/// Pause a running work interval after `idle` of inactivity. The pause is
+/// recorded from when the user went idle and that time is credited back.
+pub(crate) fn idle_pause_active_interval(
+    state: &AppState,
+    idle: Duration,
 ) -> Result<TimerStatus, String> {
-    let status = {
+    pause_interval(state, Some(idle))
+}
+
+fn pause_interval(state: &AppState, idle: Option<Duration>) -> Result<TimerStatus, String> {
+    let (interval_id, status) = {
         let mut timer = state
             .timer
             .lock()
             .map_err(|e| format!("Lock error: {e}"))?;
-        timer.resume().map_err(String::from)?;
-        timer.status()
+        if timer.state != TimerState::Running {
+            return Err("Timer is not running".into());
+        }
+        if idle.is_some() && timer.idle_candidate().is_none() {
+            return Err("Only a running work interval can be idle-paused".into());
+        }
+        // Record the pause first, so a failed write leaves the timer running
+        if let Some(id) = timer.interval_id {
+            let idle_since = idle
+                .and_then(|idle| chrono::Duration::from_std(idle).ok())
+                .unwrap_or_else(chrono::Duration::zero);
+            let paused_at = (state.clock.utc_now() - idle_since)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string();
+            db_insert_pause(&state.db_path, id, &paused_at)?;
+        }
+        match idle {
+            Some(idle) => timer.idle_pause(idle),
+            None => timer.pause(),
+        }
+        .map_err(String::from)?;
+        (timer.interval_id, timer.status())
     };
+    state.retire_tick_task();
+
+    if let Some(id) = interval_id {
+        hooks::fire_interval(&state.db_path, HookEvent::IntervalPaused, id, &status);
+    }
 
-    spawn_tick_task(app);
     Ok(status)
 }
 
-#[allow(clippy::needless_pass_by_value)]
-#[tauri::command]
-pub fn cancel_timer(state: tauri::State<'_, AppState>) -> Result<TimerStatus, String> {
+/// Resume the paused interval and close its open pause record.
+pub(crate) fn resume_active_interval(state: &AppState) -> Result<TimerStatus, String> {
+    resume_interval(state, None)
+}
+
+/// Resume after an idle pause. With `keep` the time away counts as focus
+/// time and the pause record is dropped; otherwise the pause is closed.
+pub(crate) fn resolve_idle_interval(state: &AppState, keep: bool) -> Result<TimerStatus, String> {
+    resume_interval(state, Some(keep))
+}
+
+fn resume_interval(state: &AppState, keep_idle: Option<bool>) -> Result<TimerStatus, String> {
+    let (interval_id, status) = {
+        let mut timer = state
+            .timer
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?;
+        if timer.state != TimerState::Paused {
+            return Err("Timer is not paused".into());
+        }
+        if keep_idle.is_some() && timer.idle_away().is_none() {
+            return Err("Timer was not paused for inactivity".into());
+        }
+        // Close the pause first, so a failed write leaves the timer paused
+        if let Some(id) = timer.interval_id {
+            if keep_idle == Some(true) {
+                db_discard_pause(&state.db_path, id)?;
+            } else {
+                db_end_pause(&state.db_path, id, &state.clock.timestamp())?;
+            }
+        }
+        match keep_idle {
+            Some(keep) => timer.resolve_idle(keep),
+            None => timer.resume(),
+        }
+        .map_err(String::from)?;
+        (timer.interval_id, timer.status())
+    };
+
+    if let Some(id) = interval_id {
+        hooks::fire_interval(&state.db_path, HookEvent::IntervalResumed, id, &status);
+    }
+
+    Ok(status)
+}
+
+/// Add time to the active interval and record the new planned duration.
+pub(crate) fn extend_active_interval(state: &AppState, seconds: u32) -> Result<TimerStatus, String> {
+    if seconds == 0 {
+        return Err("Extension must be greater than zero".into());
+    }
+    let (interval_id, status) = {
+        let mut timer = state
+            .timer
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?;
+        timer.extend(seconds).map_err(String::from)?;
+        (timer.interval_id, timer.status())
+    };
+
+    if let Some(id) = interval_id {
+        db_set_planned_duration(&state.db_path, id, status.planned_duration_seconds)?;
+    }
+
+    Ok(status)
+}
+
+/// Cancel the active interval and record it as cancelled in the DB.
+pub(crate) fn cancel_active_interval(state: &AppState) -> Result<TimerStatus, String> {
     let (interval_id, elapsed_seconds, was_overtime, status) = {
         let mut timer = state
             .timer
@@ -531,16 +944,232 @@ pub fn cancel_timer(state: tauri::State<'_, AppState>) -> Result<TimerStatus, St
         }
         (id, elapsed, was_ot, timer.status())
     };
+    state.retire_tick_task();
 
     // If in overtime, interval is already completed in DB — don't write again
     if !was_overtime {
-        let end_time = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let end_time = state.clock.timestamp();
+        db_end_pause(&state.db_path, interval_id, &end_time)?;
         db_cancel_interval(&state.db_path, interval_id, &end_time, elapsed_seconds)?;
+        hooks::fire_interval(
+            &state.db_path,
+            HookEvent::IntervalCancelled,
+            interval_id,
+            &status,
+        );
     }
 
     Ok(status)
 }
 
+/// Interval lengths and long-break cadence from `user_settings`.
+pub(crate) struct IntervalSettings {
+    work_seconds: u32,
+    short_break_seconds: u32,
+    long_break_seconds: u32,
+    pub(crate) long_break_frequency: u32,
+}
+
+impl IntervalSettings {
+    pub(crate) fn load(db_path: &Path) -> Result<Self, String> {
+        let conn = open_db(db_path)?;
+        let read = |key: &str, default: u32| {
+            conn.query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                [key],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default)
+        };
+        Ok(Self {
+            work_seconds: read("work_duration_minutes", 25) * 60,
+            short_break_seconds: read("short_break_duration_minutes", 5) * 60,
+            long_break_seconds: read("long_break_duration_minutes", 15) * 60,
+            long_break_frequency: read("long_break_frequency", 4),
+        })
+    }
+
+    pub(crate) fn duration_seconds(&self, interval_type: IntervalType) -> u32 {
+        match interval_type {
+            IntervalType::Work => self.work_seconds,
+            IntervalType::ShortBreak => self.short_break_seconds,
+            IntervalType::LongBreak => self.long_break_seconds,
+        }
+    }
+}
+
+/// Start whichever interval comes next in the cycle, using the configured
+/// durations. Break overtime is ended first.
+pub(crate) fn start_next_interval(state: &AppState) -> Result<TimerStatus, String> {
+    let settings = IntervalSettings::load(&state.db_path)?;
+    let (in_overtime, next) = {
+        let timer = state
+            .timer
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?;
+        (
+            timer.overtime,
+            timer.next_interval_type(settings.long_break_frequency),
+        )
+    };
+    if in_overtime {
+        cancel_active_interval(state)?;
+    }
+    start_interval(state, next, settings.duration_seconds(next))
+}
+
+/// Abandon the active interval (recorded as cancelled) and start the one
+/// that would have followed it.
+pub(crate) fn skip_active_interval(state: &AppState) -> Result<TimerStatus, String> {
+    let settings = IntervalSettings::load(&state.db_path)?;
+    let next = {
+        let timer = state
+            .timer
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?;
+        match timer.active_interval() {
+            Some((_, IntervalType::Work)) => break_after(
+                timer.completed_work_count + 1,
+                settings.long_break_frequency,
+            ),
+            Some(_) => IntervalType::Work,
+            // Break overtime: the break itself is already completed
+            None if timer.overtime => IntervalType::Work,
+            None => return Err("Timer is not active".into()),
+        }
+    };
+    cancel_active_interval(state)?;
+    start_interval(state, next, settings.duration_seconds(next))
+}
+
+// ── Actions ─────────────────────────────────────────────────
+
+/// Timer controls reachable outside the webview (tray, shortcuts, API).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerAction {
+    StartNext,
+    /// Start an interval of the given type with its configured duration.
+    Start(IntervalType),
+    Pause,
+    Resume,
+    /// Pause or resume the active interval, or start the next one.
+    Toggle,
+    Skip,
+    Cancel,
+    /// Add the given number of seconds to the active interval.
+    Extend(u32),
+}
+
+pub(crate) fn apply_action(state: &AppState, action: TimerAction) -> Result<TimerStatus, String> {
+    match action {
+        TimerAction::StartNext => start_next_interval(state),
+        TimerAction::Start(interval_type) => {
+            let settings = IntervalSettings::load(&state.db_path)?;
+            start_interval(state, interval_type, settings.duration_seconds(interval_type))
+        }
+        TimerAction::Toggle => {
+            let status = state
+                .timer
+                .lock()
+                .map_err(|e| format!("Lock error: {e}"))?
+                .status();
+            match status.state {
+                TimerState::Running if !status.overtime => pause_active_interval(state),
+                TimerState::Paused => resume_active_interval(state),
+                _ => start_next_interval(state),
+            }
+        }
+        TimerAction::Extend(seconds) => extend_active_interval(state, seconds),
+        TimerAction::Pause => pause_active_interval(state),
+        TimerAction::Resume => resume_active_interval(state),
+        TimerAction::Skip => skip_active_interval(state),
+        TimerAction::Cancel => cancel_active_interval(state),
+    }
+}
+
+/// Apply an action exactly as the equivalent command would: emit
+/// `timer-state-changed` and (re)start the tick task if now running.
+pub(crate) fn dispatch_action<R: Runtime>(
+    app: &AppHandle<R>,
+    action: TimerAction,
+) -> Result<TimerStatus, String> {
+    let status = apply_action(&app.state::<AppState>(), action)?;
+    emit_state_changed(app, &status);
+    if status.state == TimerState::Running {
+        spawn_tick_task(app.clone());
+    }
+    Ok(status)
+}
+
+// ── Tauri commands ──────────────────────────────────────────
+// `tauri::State` is injected by value per Tauri's command API; clippy's
+// suggestion to take a reference does not compile with the framework.
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn start_timer<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+    interval_type: IntervalType,
+    duration_seconds: u32,
+) -> Result<TimerStatus, String> {
+    let status = start_interval(&state, interval_type, duration_seconds)?;
+    emit_state_changed(&app, &status);
+    spawn_tick_task(app);
+    Ok(status)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn pause_timer<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+) -> Result<TimerStatus, String> {
+    let status = pause_active_interval(&state)?;
+    emit_state_changed(&app, &status);
+    Ok(status)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn resume_timer<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+) -> Result<TimerStatus, String> {
+    let status = resume_active_interval(&state)?;
+    emit_state_changed(&app, &status);
+    spawn_tick_task(app);
+    Ok(status)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn extend_timer<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+    seconds: u32,
+) -> Result<TimerStatus, String> {
+    let status = extend_active_interval(&state, seconds)?;
+    emit_state_changed(&app, &status);
+    if status.state == TimerState::Running {
+        spawn_tick_task(app);
+    }
+    Ok(status)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[tauri::command]
+pub fn cancel_timer<R: Runtime>(
+    state: tauri::State<'_, AppState>,
+    app: AppHandle<R>,
+) -> Result<TimerStatus, String> {
+    let status = cancel_active_interval(&state)?;
+    emit_state_changed(&app, &status);
+    Ok(status)
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[tauri::command]
 pub fn get_timer_state(state: tauri::State<'_, AppState>) -> Result<TimerStatus, String> {
@@ -870,6 +1499,58 @@ mod tests {
         assert_eq!(duration, 600);
     }
 
+    /// Helper: create a migrated database file in the temp directory.
+    fn setup_test_db_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("pomo-timer-{}-{name}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        crate::database::initialize(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn pause_and_resume_record_pause_duration() {
+        let path = setup_test_db_file("pause-resume");
+        let id = db_insert_interval(&path, IntervalType::Work, "2026-02-14T09:00:00Z", 1500).unwrap();
+
+        db_insert_pause(&path, id, "2026-02-14T09:10:00Z").unwrap();
+        db_end_pause(&path, id, "2026-02-14T09:12:30Z").unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+        let (resumed_at, duration): (String, i64) = conn
+            .query_row(
+                "SELECT resumed_at, duration_seconds FROM interval_pauses WHERE interval_id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(resumed_at, "2026-02-14T09:12:30Z");
+        assert_eq!(duration, 150);
+    }
+
+    #[test]
+    fn end_pause_only_closes_open_pause() {
+        let path = setup_test_db_file("end-pause");
+        let id = db_insert_interval(&path, IntervalType::Work, "2026-02-14T09:00:00Z", 1500).unwrap();
+
+        db_insert_pause(&path, id, "2026-02-14T09:05:00Z").unwrap();
+        db_end_pause(&path, id, "2026-02-14T09:06:00Z").unwrap();
+        db_insert_pause(&path, id, "2026-02-14T09:10:00Z").unwrap();
+        db_end_pause(&path, id, "2026-02-14T09:10:30Z").unwrap();
+        // No open pause left — must not touch closed rows
+        db_end_pause(&path, id, "2026-02-14T09:20:00Z").unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+        let (count, total): (i64, i64) = conn
+            .query_row(
+                "SELECT COUNT(*), SUM(duration_seconds) FROM interval_pauses WHERE interval_id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(total, 90);
+    }
+
     #[test]
     fn all_interval_types_insert_correctly() {
         let conn = setup_test_db();
@@ -1023,4 +1704,532 @@ mod tests {
         timer.start(IntervalType::Work, 1500, 2).unwrap();
         assert_eq!(timer.state, TimerState::Running);
     }
+
+    // ── State change sequence ───────────────────────────────
+
+    #[test]
+    fn seq_increments_on_every_transition() {
+        let mut timer = TimerInner::new();
+        assert_eq!(timer.status().seq, 0);
+
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        assert_eq!(timer.status().seq, 1);
+        timer.pause().unwrap();
+        assert_eq!(timer.status().seq, 2);
+        timer.resume().unwrap();
+        assert_eq!(timer.status().seq, 3);
+        timer.cancel().unwrap();
+        assert_eq!(timer.status().seq, 4);
+        timer.start(IntervalType::ShortBreak, 300, 2).unwrap();
+        timer.complete();
+        assert_eq!(timer.status().seq, 6);
+    }
+
+    #[test]
+    fn seq_not_changed_by_reads_or_rejected_transitions() {
+        let mut timer = TimerInner::new();
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        let seq = timer.status().seq;
+
+        let _ = timer.status();
+        assert!(timer.resume().is_err());
+        assert!(timer.start(IntervalType::Work, 1500, 2).is_err());
+        assert_eq!(timer.status().seq, seq);
+    }
+
+    #[test]
+    fn seq_increments_when_entering_overtime() {
+        let mut timer = TimerInner::new();
+        timer.start(IntervalType::ShortBreak, 300, 1).unwrap();
+        let before = timer.status().seq;
+        timer.enter_overtime();
+        assert!(timer.status().seq > before);
+    }
+
+    // ── Simulated clock ─────────────────────────────────────
+
+    use crate::clock::ManualClock;
+    use chrono::{TimeZone, Utc};
+
+    fn setup_manual_state(name: &str) -> (AppState, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2026, 2, 14, 9, 0, 0).unwrap(),
+        ));
+        let state = AppState::new(setup_test_db_file(name)).with_clock(clock.clone());
+        (state, clock)
+    }
+
+    fn read_interval(state: &AppState, id: i64) -> (String, Option<String>, Option<i64>) {
+        let conn = Connection::open(&state.db_path).unwrap();
+        conn.query_row(
+            "SELECT status, end_time, duration_seconds FROM timer_intervals WHERE id = ?1",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn simulated_work_interval_completes_at_deadline() {
+        let (state, clock) = setup_manual_state("sim-complete");
+        let id = start_interval(&state, IntervalType::Work, 1500)
+            .unwrap()
+            .interval_id
+            .unwrap();
+
+        clock.advance(Duration::from_secs(1499));
+        match advance_timer(&state) {
+            Tick::Running(tick) => assert_eq!(tick.remaining_ms, 1000),
+            _ => panic!("expected a running tick"),
+        }
+        assert_eq!(read_interval(&state, id).0, "in_progress");
+
+        clock.advance(Duration::from_secs(1));
+        match advance_timer(&state) {
+            Tick::Completed(payload, status) => {
+                assert_eq!(payload.interval_id, id);
+                assert_eq!(payload.completed_work_count, 1);
+                assert!(!payload.overtime);
+                assert_eq!(status.state, TimerState::Idle);
+            }
+            _ => panic!("expected completion"),
+        }
+        assert!(matches!(advance_timer(&state), Tick::Stopped));
+
+        let (status, end_time, duration) = read_interval(&state, id);
+        assert_eq!(status, "completed");
+        assert_eq!(end_time.as_deref(), Some("2026-02-14T09:25:00Z"));
+        assert_eq!(duration, Some(1500));
+    }
+
+    #[test]
+    fn failed_pause_writes_leave_the_timer_unchanged() {
+        let (state, clock) = setup_manual_state("sim-pause-fail");
+        start_interval(&state, IntervalType::Work, 1500).unwrap();
+        let conn = Connection::open(&state.db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TRIGGER no_pauses BEFORE INSERT ON interval_pauses
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+        )
+        .unwrap();
+
+        clock.advance(Duration::from_mins(5));
+        let err = pause_active_interval(&state).err().unwrap();
+        assert!(err.contains("disk full"));
+        assert!(matches!(advance_timer(&state), Tick::Running(_)));
+
+        conn.execute_batch(
+            "DROP TRIGGER no_pauses;
+             CREATE TRIGGER no_resumes BEFORE UPDATE ON interval_pauses
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+        )
+        .unwrap();
+        assert_eq!(pause_active_interval(&state).unwrap().state, TimerState::Paused);
+        clock.advance(Duration::from_mins(5));
+        let err = resume_active_interval(&state).err().unwrap();
+        assert!(err.contains("disk full"));
+        assert!(matches!(advance_timer(&state), Tick::Stopped));
+        assert_eq!(state.timer.lock().unwrap().state, TimerState::Paused);
+
+        conn.execute_batch("DROP TRIGGER no_resumes;").unwrap();
+        assert_eq!(resume_active_interval(&state).unwrap().state, TimerState::Running);
+        assert!(matches!(advance_timer(&state), Tick::Running(_)));
+    }
+
+    #[test]
+    fn simulated_pause_extends_deadline() {
+        let (state, clock) = setup_manual_state("sim-pause");
+        let id = start_interval(&state, IntervalType::Work, 1500)
+            .unwrap()
+            .interval_id
+            .unwrap();
+
+        clock.advance(Duration::from_mins(10));
+        pause_active_interval(&state).unwrap();
+        clock.advance(Duration::from_mins(30));
+        assert!(matches!(advance_timer(&state), Tick::Stopped));
+        resume_active_interval(&state).unwrap();
+
+        clock.advance(Duration::from_secs(899));
+        assert!(matches!(advance_timer(&state), Tick::Running(_)));
+        clock.advance(Duration::from_secs(1));
+        assert!(matches!(advance_timer(&state), Tick::Completed(..)));
+
+        let (status, end_time, _) = read_interval(&state, id);
+        assert_eq!(status, "completed");
+        assert_eq!(end_time.as_deref(), Some("2026-02-14T09:55:00Z"));
+
+        let conn = Connection::open(&state.db_path).unwrap();
+        let paused: i64 = conn
+            .query_row(
+                "SELECT duration_seconds FROM interval_pauses WHERE interval_id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(paused, 1800);
+    }
+
+    #[test]
+    fn simulated_break_enters_overtime() {
+        let (state, clock) = setup_manual_state("sim-overtime");
+        Connection::open(&state.db_path)
+            .unwrap()
+            .execute(
+                "UPDATE user_settings SET value = 'true' WHERE key = 'break_overtime_enabled'",
+                [],
+            )
+            .unwrap();
+        let id = start_interval(&state, IntervalType::ShortBreak, 300)
+            .unwrap()
+            .interval_id
+            .unwrap();
+
+        clock.advance(Duration::from_mins(5));
+        match advance_timer(&state) {
+            Tick::Completed(payload, status) => {
+                assert!(payload.overtime);
+                assert_eq!(status.state, TimerState::Running);
+                assert!(status.overtime);
+            }
+            _ => panic!("expected completion into overtime"),
+        }
+        assert_eq!(read_interval(&state, id).0, "completed");
+
+        clock.advance(Duration::from_secs(90));
+        match advance_timer(&state) {
+            Tick::Running(tick) => {
+                assert_eq!(tick.remaining_ms, 0);
+                assert_eq!(tick.overtime_ms, 90_000);
+            }
+            _ => panic!("expected an overtime tick"),
+        }
+
+        // Cancelling overtime leaves the completed row untouched
+        cancel_active_interval(&state).unwrap();
+        assert_eq!(read_interval(&state, id).0, "completed");
+        assert!(matches!(advance_timer(&state), Tick::Stopped));
+    }
+
+    #[test]
+    fn simulated_cancel_records_elapsed_time() {
+        let (state, clock) = setup_manual_state("sim-cancel");
+        let id = start_interval(&state, IntervalType::Work, 1500)
+            .unwrap()
+            .interval_id
+            .unwrap();
+
+        clock.advance(Duration::from_secs(421));
+        cancel_active_interval(&state).unwrap();
+
+        let (status, end_time, duration) = read_interval(&state, id);
+        assert_eq!(status, "cancelled");
+        assert_eq!(end_time.as_deref(), Some("2026-02-14T09:07:01Z"));
+        assert_eq!(duration, Some(421));
+    }
+
+    // ── Tick scheduling ─────────────────────────────────────
+
+    #[test]
+    fn next_wake_is_none_unless_running() {
+        let (state, _clock) = setup_manual_state("wake-idle");
+        assert!(state.timer.lock().unwrap().next_wake(FOCUSED_TICK_PERIOD).is_none());
+
+        start_interval(&state, IntervalType::Work, 1500).unwrap();
+        pause_active_interval(&state).unwrap();
+        assert!(state.timer.lock().unwrap().next_wake(FOCUSED_TICK_PERIOD).is_none());
+    }
+
+    #[test]
+    fn next_wake_aligns_to_period_and_hits_deadline() {
+        let (state, clock) = setup_manual_state("wake-align");
+        start_interval(&state, IntervalType::Work, 10).unwrap();
+        let wake = |period| state.timer.lock().unwrap().next_wake(period).unwrap();
+
+        assert_eq!(wake(UNFOCUSED_TICK_PERIOD), Duration::from_secs(1));
+        clock.advance(Duration::from_millis(300));
+        assert_eq!(wake(UNFOCUSED_TICK_PERIOD), Duration::from_millis(700));
+        assert_eq!(wake(FOCUSED_TICK_PERIOD), Duration::from_millis(200));
+
+        // Near the end the wake is the deadline itself, never past it
+        clock.advance(Duration::from_millis(9_500));
+        assert_eq!(wake(UNFOCUSED_TICK_PERIOD), Duration::from_millis(200));
+        clock.advance(Duration::from_millis(200));
+        assert_eq!(wake(UNFOCUSED_TICK_PERIOD), Duration::ZERO);
+        assert!(matches!(advance_timer(&state), Tick::Completed(..)));
+    }
+
+    #[test]
+    fn next_wake_in_overtime_follows_overtime_ms() {
+        let (state, clock) = setup_manual_state("wake-overtime");
+        Connection::open(&state.db_path)
+            .unwrap()
+            .execute(
+                "UPDATE user_settings SET value = 'true' WHERE key = 'break_overtime_enabled'",
+                [],
+            )
+            .unwrap();
+        start_interval(&state, IntervalType::ShortBreak, 60).unwrap();
+        clock.advance(Duration::from_mins(1));
+        assert!(matches!(advance_timer(&state), Tick::Completed(..)));
+
+        clock.advance(Duration::from_millis(2_400));
+        let wake = state.timer.lock().unwrap().next_wake(UNFOCUSED_TICK_PERIOD);
+        assert_eq!(wake, Some(Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn focus_changes_tick_period() {
+        let (state, _clock) = setup_manual_state("wake-focus");
+        assert_eq!(state.tick_period(), FOCUSED_TICK_PERIOD);
+        state.set_ui_focused(false);
+        assert_eq!(state.tick_period(), UNFOCUSED_TICK_PERIOD);
+        state.set_ui_focused(true);
+        assert_eq!(state.tick_period(), FOCUSED_TICK_PERIOD);
+    }
+
+    // ── Tick task lifecycle ─────────────────────────────────
+
+    #[test]
+    fn retiring_tick_task_invalidates_previous_generation() {
+        let (state, _clock) = setup_manual_state("tick-generation");
+        let first = state.retire_tick_task();
+        assert!(state.is_current_tick(first));
+
+        let second = state.retire_tick_task();
+        assert!(!state.is_current_tick(first));
+        assert!(state.is_current_tick(second));
+
+        start_interval(&state, IntervalType::Work, 1500).unwrap();
+        pause_active_interval(&state).unwrap();
+        assert!(!state.is_current_tick(second));
+    }
+
+    /// Clock that follows tokio's (pausable) time, so tick tasks run under
+    /// `start_paused` see the same time the runtime sleeps against.
+    struct TokioClock;
+
+    impl crate::clock::Clock for TokioClock {
+        fn now(&self) -> std::time::Instant {
+            tokio::time::Instant::now().into_std()
+        }
+
+        fn utc_now(&self) -> chrono::DateTime<Utc> {
+            Utc.with_ymd_and_hms(2026, 2, 14, 9, 0, 0).unwrap()
+        }
+    }
+
+    #[test]
+    fn respawned_tick_task_replaces_the_old_one() {
+        use std::sync::atomic::AtomicUsize;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        app.manage(
+            AppState::new(setup_test_db_file("tick-duplicate")).with_clock(Arc::new(TokioClock)),
+        );
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let completions = Arc::new(AtomicUsize::new(0));
+        let tick_log = Arc::clone(&ticks);
+        app.listen_any("timer-tick", move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            tick_log
+                .lock()
+                .unwrap()
+                .push(payload["remaining_ms"].as_u64().unwrap());
+        });
+        let complete_counter = Arc::clone(&completions);
+        app.listen_any("timer-complete", move |_| {
+            complete_counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let state = app.state::<AppState>();
+            start_interval(&state, IntervalType::Work, 1).unwrap();
+
+            // A resume whose pause the old task never observed: the first
+            // task is still armed and the timer is Running when the second
+            // spawns
+            let first = tokio::spawn(run_tick_task(
+                app.handle().clone(),
+                state.retire_tick_task(),
+            ));
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let second = tokio::spawn(run_tick_task(
+                app.handle().clone(),
+                state.retire_tick_task(),
+            ));
+            first.await.unwrap();
+            second.await.unwrap();
+        });
+
+        assert_eq!(completions.load(Ordering::SeqCst), 1);
+        assert_eq!(*ticks.lock().unwrap(), vec![750, 500, 250]);
+    }
+
+    // ── Cycle actions ───────────────────────────────────────
+
+    fn set_setting(state: &AppState, key: &str, value: &str) {
+        Connection::open(&state.db_path)
+            .unwrap()
+            .execute(
+                "UPDATE user_settings SET value = ?1 WHERE key = ?2",
+                [value, key],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn break_after_follows_long_break_frequency() {
+        assert_eq!(break_after(1, 4), IntervalType::ShortBreak);
+        assert_eq!(break_after(4, 4), IntervalType::LongBreak);
+        assert_eq!(break_after(8, 4), IntervalType::LongBreak);
+        assert_eq!(break_after(0, 4), IntervalType::ShortBreak);
+        assert_eq!(break_after(3, 0), IntervalType::ShortBreak);
+    }
+
+    #[test]
+    fn next_interval_is_break_only_after_completed_work() {
+        let mut timer = TimerInner::new();
+        assert_eq!(timer.next_interval_type(4), IntervalType::Work);
+
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        timer.cancel().unwrap();
+        assert_eq!(timer.next_interval_type(4), IntervalType::Work);
+
+        timer.start(IntervalType::Work, 1500, 2).unwrap();
+        timer.complete();
+        assert_eq!(timer.next_interval_type(4), IntervalType::ShortBreak);
+
+        timer.start(IntervalType::ShortBreak, 300, 3).unwrap();
+        timer.complete();
+        assert_eq!(timer.next_interval_type(4), IntervalType::Work);
+    }
+
+    #[test]
+    fn start_next_uses_configured_durations() {
+        let (state, clock) = setup_manual_state("start-next");
+        set_setting(&state, "work_duration_minutes", "50");
+        set_setting(&state, "short_break_duration_minutes", "10");
+
+        let status = apply_action(&state, TimerAction::StartNext).unwrap();
+        assert_eq!(status.interval_type, IntervalType::Work);
+        assert_eq!(status.planned_duration_seconds, 3000);
+
+        clock.advance(Duration::from_mins(50));
+        assert!(matches!(advance_timer(&state), Tick::Completed(..)));
+
+        let status = apply_action(&state, TimerAction::StartNext).unwrap();
+        assert_eq!(status.interval_type, IntervalType::ShortBreak);
+        assert_eq!(status.planned_duration_seconds, 600);
+    }
+
+    #[test]
+    fn start_next_ends_break_overtime() {
+        let (state, clock) = setup_manual_state("start-next-overtime");
+        set_setting(&state, "break_overtime_enabled", "true");
+        start_interval(&state, IntervalType::ShortBreak, 300).unwrap();
+        clock.advance(Duration::from_mins(5));
+        assert!(matches!(advance_timer(&state), Tick::Completed(..)));
+
+        let status = apply_action(&state, TimerAction::StartNext).unwrap();
+        assert_eq!(status.interval_type, IntervalType::Work);
+        assert!(!status.overtime);
+    }
+
+    #[test]
+    fn skip_work_cancels_it_and_starts_the_break() {
+        let (state, clock) = setup_manual_state("skip-work");
+        set_setting(&state, "long_break_frequency", "1");
+        let id = start_interval(&state, IntervalType::Work, 1500)
+            .unwrap()
+            .interval_id
+            .unwrap();
+        clock.advance(Duration::from_mins(3));
+
+        let status = apply_action(&state, TimerAction::Skip).unwrap();
+        assert_eq!(status.state, TimerState::Running);
+        assert_eq!(status.interval_type, IntervalType::LongBreak);
+        assert_eq!(status.completed_work_count, 0);
+        assert_eq!(read_interval(&state, id).0, "cancelled");
+    }
+
+    #[test]
+    fn skip_break_starts_work() {
+        let (state, _clock) = setup_manual_state("skip-break");
+        start_interval(&state, IntervalType::ShortBreak, 300).unwrap();
+        let status = apply_action(&state, TimerAction::Skip).unwrap();
+        assert_eq!(status.interval_type, IntervalType::Work);
+        assert_eq!(status.planned_duration_seconds, 1500);
+    }
+
+    #[test]
+    fn skip_requires_active_timer() {
+        let (state, _clock) = setup_manual_state("skip-idle");
+        assert_eq!(
+            apply_action(&state, TimerAction::Skip).err().as_deref(),
+            Some("Timer is not active")
+        );
+    }
+
+    // ── Extend ──────────────────────────────────────────────
+
+    #[test]
+    fn extend_moves_deadline_and_planned_duration() {
+        let (state, clock) = setup_manual_state("extend-running");
+        let id = start_interval(&state, IntervalType::Work, 1500)
+            .unwrap()
+            .interval_id
+            .unwrap();
+        clock.advance(Duration::from_mins(20));
+
+        let status = apply_action(&state, TimerAction::Extend(300)).unwrap();
+        assert_eq!(status.planned_duration_seconds, 1800);
+        assert_eq!(status.remaining_ms, 600_000);
+
+        clock.advance(Duration::from_mins(5));
+        assert!(matches!(advance_timer(&state), Tick::Running(_)));
+        clock.advance(Duration::from_mins(5));
+        assert!(matches!(advance_timer(&state), Tick::Completed(..)));
+
+        let (status, _, duration) = read_interval(&state, id);
+        assert_eq!(status, "completed");
+        assert_eq!(duration, Some(1800));
+    }
+
+    #[test]
+    fn extend_while_paused_adds_remaining() {
+        let mut timer = TimerInner::new();
+        timer.start(IntervalType::Work, 1500, 1).unwrap();
+        timer.pause().unwrap();
+        let before = timer.status().remaining_ms;
+        timer.extend(60).unwrap();
+        assert_eq!(timer.status().remaining_ms, before + 60_000);
+    }
+
+    #[test]
+    fn extend_rejected_when_idle_or_in_overtime() {
+        let mut timer = TimerInner::new();
+        assert!(timer.extend(300).is_err());
+
+        timer.start(IntervalType::ShortBreak, 300, 1).unwrap();
+        timer.complete();
+        timer.state = TimerState::Running;
+        timer.enter_overtime();
+        assert!(timer.extend(300).is_err());
+    }
+
+    #[test]
+    fn toggle_starts_pauses_and_resumes() {
+        let (state, _clock) = setup_manual_state("toggle");
+        let toggle = || apply_action(&state, TimerAction::Toggle).unwrap().state;
+        assert_eq!(toggle(), TimerState::Running);
+        assert_eq!(toggle(), TimerState::Paused);
+        assert_eq!(toggle(), TimerState::Running);
+    }
 }
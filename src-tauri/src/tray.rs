@@ -0,0 +1,272 @@
+use std::sync::Mutex;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{TrayIcon, TrayIconBuilder};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::timer::{
+    self, AppState, IntervalType, TimerAction, TimerState, TimerStatus, TimerTickPayload,
+};
+
+const TRAY_ID: &str = "main";
+
+// ── Tray state ──────────────────────────────────────────────
+
+pub struct TrayState<R: Runtime> {
+    tray: TrayIcon<R>,
+    start: MenuItem<R>,
+    pause: MenuItem<R>,
+    resume: MenuItem<R>,
+    skip: MenuItem<R>,
+    cancel: MenuItem<R>,
+    /// Last text pushed to the tray; ticks that would not change it are dropped.
+    text: Mutex<TrayText>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct TrayText {
+    /// Short countdown shown next to the icon where the platform supports it.
+    title: Option<String>,
+    tooltip: String,
+}
+
+// ── Formatting ──────────────────────────────────────────────
+
+/// Format milliseconds as mm:ss, rounding up so 00:00 only shows at the end.
+fn format_clock(ms: u64) -> String {
+    let secs = ms.div_ceil(1000);
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+fn tray_text(
+    state: TimerState,
+    interval_type: IntervalType,
+    remaining_ms: u64,
+    overtime_ms: Option<u64>,
+) -> TrayText {
+    let label = interval_type.label();
+    match (state, overtime_ms) {
+        (TimerState::Idle, _) => TrayText {
+            title: None,
+            tooltip: "Pomo — idle".into(),
+        },
+        (_, Some(overtime_ms)) => {
+            // Overtime counts up, so show whole elapsed seconds
+            let clock = format_clock(overtime_ms / 1000 * 1000);
+            TrayText {
+                title: Some(format!("+{clock}")),
+                tooltip: format!("{label} overtime — +{clock}"),
+            }
+        }
+        (TimerState::Paused, None) => {
+            let clock = format_clock(remaining_ms);
+            TrayText {
+                title: Some(clock.clone()),
+                tooltip: format!("{label} — {clock} (paused)"),
+            }
+        }
+        (TimerState::Running, None) => {
+            let clock = format_clock(remaining_ms);
+            TrayText {
+                title: Some(clock.clone()),
+                tooltip: format!("{label} — {clock}"),
+            }
+        }
+    }
+}
+
+fn status_text(status: &TimerStatus) -> TrayText {
+    tray_text(
+        status.state,
+        status.interval_type,
+        status.remaining_ms,
+        status.overtime.then_some(status.overtime_ms),
+    )
+}
+
+// ── Updates ─────────────────────────────────────────────────
+
+impl<R: Runtime> TrayState<R> {
+    fn set_text(&self, text: TrayText) {
+        let Ok(mut current) = self.text.lock() else {
+            return;
+        };
+        if *current == text {
+            return;
+        }
+        let _ = self.tray.set_title(text.title.as_deref());
+        let _ = self.tray.set_tooltip(Some(&text.tooltip));
+        *current = text;
+    }
+
+    fn set_enabled(&self, status: &TimerStatus) {
+        let active = status.state != TimerState::Idle;
+        let _ = self.start.set_enabled(!active || status.overtime);
+        let _ = self
+            .pause
+            .set_enabled(status.state == TimerState::Running && !status.overtime);
+        let _ = self.resume.set_enabled(status.state == TimerState::Paused);
+        let _ = self.skip.set_enabled(active);
+        let _ = self.cancel.set_enabled(active);
+    }
+}
+
+/// Sync menu items and text with a new timer status. No-op without a tray.
+pub(crate) fn refresh_status<R: Runtime>(app: &AppHandle<R>, status: &TimerStatus) {
+    if let Some(tray) = app.try_state::<TrayState<R>>() {
+        tray.set_enabled(status);
+        tray.set_text(status_text(status));
+    }
+}
+
+/// Update the countdown from a tick. Only touches the tray when the
+/// displayed mm:ss changes, so at most once a second.
+pub(crate) fn refresh_tick<R: Runtime>(app: &AppHandle<R>, tick: &TimerTickPayload) {
+    if let Some(tray) = app.try_state::<TrayState<R>>() {
+        // Ticks are only emitted while running; remaining hits 0 only in overtime
+        let overtime_ms = (tick.remaining_ms == 0).then_some(tick.overtime_ms);
+        tray.set_text(tray_text(
+            TimerState::Running,
+            tick.interval_type,
+            tick.remaining_ms,
+            overtime_ms,
+        ));
+    }
+}
+
+// ── Setup ───────────────────────────────────────────────────
+
+fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, event: &MenuEvent) {
+    let action = match event.id().as_ref() {
+        "start" => TimerAction::StartNext,
+        "pause" => TimerAction::Pause,
+        "resume" => TimerAction::Resume,
+        "skip" => TimerAction::Skip,
+        "cancel" => TimerAction::Cancel,
+        "show" => return show_main_window(app),
+        "quit" => return app.exit(0),
+        _ => return,
+    };
+    if let Err(e) = timer::dispatch_action(app, action) {
+        eprintln!("Tray action failed: {e}");
+    }
+}
+
+/// Create the tray icon and its timer menu. Without a tray (e.g. no
+/// system tray on the desktop) the app runs on as a plain window.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    if let Err(e) = build_tray(app) {
+        eprintln!("Failed to create tray icon: {e}");
+    }
+}
+
+fn build_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let start = MenuItem::with_id(app, "start", "Start next", true, None::<&str>)?;
+    let pause = MenuItem::with_id(app, "pause", "Pause", false, None::<&str>)?;
+    let resume = MenuItem::with_id(app, "resume", "Resume", false, None::<&str>)?;
+    let skip = MenuItem::with_id(app, "skip", "Skip", false, None::<&str>)?;
+    let cancel = MenuItem::with_id(app, "cancel", "Cancel", false, None::<&str>)?;
+    let show = MenuItem::with_id(app, "show", "Show Pomo", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &start,
+            &pause,
+            &resume,
+            &skip,
+            &cancel,
+            &PredefinedMenuItem::separator(app)?,
+            &show,
+            &quit,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Pomo — idle")
+        .menu(&menu)
+        .on_menu_event(|app, event| handle_menu_event(app, &event));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    app.manage(TrayState {
+        tray,
+        start,
+        pause,
+        resume,
+        skip,
+        cancel,
+        text: Mutex::new(TrayText::default()),
+    });
+
+    let state = app.state::<AppState>();
+    let status = state.timer.lock().map(|timer| timer.status());
+    if let Ok(status) = status {
+        refresh_status(app, &status);
+    }
+    Ok(())
+}
+
+// ── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_rounds_up_to_whole_seconds() {
+        assert_eq!(format_clock(1_500_000), "25:00");
+        assert_eq!(format_clock(1_499_001), "25:00");
+        assert_eq!(format_clock(1_499_000), "24:59");
+        assert_eq!(format_clock(1), "00:01");
+        assert_eq!(format_clock(0), "00:00");
+    }
+
+    #[test]
+    fn idle_has_no_title() {
+        let text = tray_text(TimerState::Idle, IntervalType::Work, 0, None);
+        assert_eq!(text.title, None);
+        assert_eq!(text.tooltip, "Pomo — idle");
+    }
+
+    #[test]
+    fn running_and_paused_show_countdown() {
+        let running = tray_text(TimerState::Running, IntervalType::Work, 754_200, None);
+        assert_eq!(running.title.as_deref(), Some("12:35"));
+        assert_eq!(running.tooltip, "Work — 12:35");
+
+        let paused = tray_text(TimerState::Paused, IntervalType::ShortBreak, 60_000, None);
+        assert_eq!(paused.tooltip, "Short break — 01:00 (paused)");
+    }
+
+    #[test]
+    fn overtime_counts_up() {
+        let text = tray_text(
+            TimerState::Running,
+            IntervalType::LongBreak,
+            0,
+            Some(90_700),
+        );
+        assert_eq!(text.title.as_deref(), Some("+01:30"));
+        assert_eq!(text.tooltip, "Long break overtime — +01:30");
+    }
+
+    #[test]
+    fn sub_second_ticks_produce_identical_text() {
+        // Four ticks within the same displayed second must not re-render
+        let texts: Vec<_> = [60_000, 59_750, 59_500, 59_250]
+            .iter()
+            .map(|ms| tray_text(TimerState::Running, IntervalType::Work, *ms, None))
+            .collect();
+        assert!(texts.windows(2).all(|w| w[0] == w[1]));
+    }
+}